@@ -0,0 +1,99 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use uno_cli::card::{Card, CardColor, CardValue, Deck};
+use uno_cli::game::{GameState, Turn, TurnResult};
+use uno_cli::hand::Hand;
+use uno_cli::player::Player;
+use uno_cli::rules::{self, Rules};
+use uno_cli::sim::Simulation;
+
+/// Plays the first card it can, so the benchmarks measure the engine rather than a bot.
+struct FirstCard {
+    name: String,
+}
+
+impl Player for FirstCard {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn execute_turn(&mut self, turn: &Turn) -> TurnResult {
+        match turn.playable_hand.first() {
+            Some(card) => {
+                let mut card = *card;
+                card.with_color(CardColor::Red);
+                TurnResult::Played(card)
+            }
+            None => TurnResult::Drew,
+        }
+    }
+
+    fn observe_turn(&self, _other: &dyn Player, _card: &Card) {}
+
+    fn observe_turn_skip(&self, _observed_cards: Option<&[Card]>) {}
+
+    fn wants_events(&self) -> bool {
+        false
+    }
+}
+
+fn bots() -> Vec<FirstCard> {
+    (0..4).map(|seat| FirstCard { name: format!("Bot {seat}") }).collect()
+}
+
+fn shuffle(c: &mut Criterion) {
+    let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+    c.bench_function("shuffle_deck", |b| b.iter_batched_ref(Deck::generate, |deck| deck.shuffle(&mut rng), BatchSize::SmallInput));
+}
+
+fn playable(c: &mut Criterion) {
+    // A big hand that has to be checked against a few different kinds of top card.
+    let hand = Deck::new_deck().into_iter().step_by(3).collect::<Hand>();
+    let tops = [
+        Card::Numeric { color: CardColor::Red, value: CardValue::Seven },
+        Card::Skip { color: CardColor::Blue },
+        Card::DrawTwo { color: CardColor::Green },
+        Card::Wild { color: Some(CardColor::Yellow) },
+    ];
+    let (rules, mut cards) = (Rules::default(), Vec::new());
+
+    c.bench_function("fill_playable", |b| b.iter(|| {
+        for top in &tops {
+            rules::fill_playable(&mut cards, &hand, top, 0, &rules);
+        }
+
+        cards.len()
+    }));
+}
+
+fn games(c: &mut Criterion) {
+    let mut seed = 0;
+
+    c.bench_function("headless_game", |b| b.iter(|| {
+        seed += 1;
+        let mut players = bots();
+        let mut game = GameState::with_seed(players.iter_mut().map(|p| p as &mut dyn Player).collect(), seed);
+
+        loop {
+            if let Some(outcome) = game.play_turn() {
+                return outcome.turns();
+            }
+        }
+    }));
+
+    let simulation = Simulation { games: 1000, ..Simulation::default() };
+    let mut group = c.benchmark_group("batch");
+    group.sample_size(10).throughput(Throughput::Elements(simulation.games as u64));
+
+    group.bench_function("1000_games", |b| b.iter(|| {
+        let mut players = bots();
+        simulation.run(&mut players.iter_mut().map(|p| p as &mut dyn Player).collect::<Vec<_>>())
+    }));
+
+    group.finish();
+}
+
+criterion_group!(benches, shuffle, playable, games);
+criterion_main!(benches);
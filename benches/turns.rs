@@ -0,0 +1,99 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use uno_cli::card::{Card, CardColor};
+use uno_cli::game::{GameState, Turn, TurnResult};
+use uno_cli::player::Player;
+
+/// Counts heap allocations, so the benchmark can report how many a turn makes.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// Plays the first card it can, so the benchmark measures the engine rather than a bot.
+struct FirstCard {
+    name: String,
+}
+
+impl Player for FirstCard {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn execute_turn(&mut self, turn: &Turn) -> TurnResult {
+        match turn.playable_hand.first() {
+            Some(card) => {
+                let mut card = *card;
+                card.with_color(CardColor::Red);
+                TurnResult::Played(card)
+            }
+            None => TurnResult::Drew,
+        }
+    }
+
+    fn observe_turn(&self, _other: &dyn Player, _card: &Card) {}
+
+    fn observe_turn_skip(&self, _observed_cards: Option<&[Card]>) {}
+
+    fn wants_events(&self) -> bool {
+        false
+    }
+}
+
+fn play_round(seed: u64) -> u32 {
+    let mut players = (0..4).map(|seat| FirstCard { name: format!("Bot {seat}") }).collect::<Vec<FirstCard>>();
+    let mut game = GameState::with_seed(players.iter_mut().map(|p| p as &mut dyn Player).collect(), seed);
+
+    loop {
+        if let Some(outcome) = game.play_turn() {
+            return outcome.turns();
+        }
+    }
+}
+
+fn turns(c: &mut Criterion) {
+    let (mut turns, before) = (0, ALLOCATIONS.load(Ordering::Relaxed));
+    let mut setup = 0;
+
+    for seed in 0..100 {
+        // Everything up to the deal is setup, so count it separately.
+        let start = ALLOCATIONS.load(Ordering::Relaxed);
+        let mut players = (0..4).map(|seat| FirstCard { name: format!("Bot {seat}") }).collect::<Vec<FirstCard>>();
+        let mut game = GameState::with_seed(players.iter_mut().map(|p| p as &mut dyn Player).collect(), seed);
+        game.play_turn();
+        setup += ALLOCATIONS.load(Ordering::Relaxed) - start;
+
+        turns += loop {
+            if let Some(outcome) = game.play_turn() {
+                break outcome.turns();
+            }
+        };
+    }
+
+    let during = ALLOCATIONS.load(Ordering::Relaxed) - before - setup;
+    println!("{:.2} allocations per turn over {turns} turns", during as f64 / turns as f64);
+
+    let mut seed = 0;
+    c.bench_function("play_round", |b| b.iter(|| {
+        seed += 1;
+        play_round(seed)
+    }));
+}
+
+criterion_group!(benches, turns);
+criterion_main!(benches);
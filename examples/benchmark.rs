@@ -0,0 +1,74 @@
+//! Plays a million games between computer players, spread over every core, with nothing shown
+//! and no pauses, and says how fast the engine got through them.
+//!
+//! ```text
+//! cargo run --release --example benchmark [GAMES]
+//! ```
+//!
+//! Each thread runs its own [`Simulation`] over its own range of seeds, so no two threads play
+//! the same game.
+
+use std::env;
+use std::process::ExitCode;
+use std::thread;
+use std::time::Instant;
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use uno_cli::player::{AIDifficulty, Ai, Player};
+use uno_cli::sim::{Simulation, SimulationReport};
+
+const LINEUP: [AIDifficulty; 4] = [AIDifficulty::Easy, AIDifficulty::Medium, AIDifficulty::Hard, AIDifficulty::Hard];
+
+fn main() -> ExitCode {
+    let games = match env::args().nth(1).map(|games| games.parse::<u32>()) {
+        None => 1_000_000,
+        Some(Ok(games)) => games,
+        Some(Err(_)) => {
+            eprintln!("usage: benchmark [GAMES]");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let threads = thread::available_parallelism().map_or(1, |threads| threads.get() as u32);
+    let started = Instant::now();
+
+    let reports = thread::scope(|scope| {
+        let batches = (0..threads).map(|thread| {
+            // The first threads take one more game each when they don't divide evenly.
+            let first = thread * (games / threads) + thread.min(games % threads);
+            let count = games / threads + u32::from(thread < games % threads);
+            scope.spawn(move || play(first as u64, count))
+        });
+
+        batches.collect::<Vec<_>>().into_iter().map(|batch| batch.join().expect("a batch panicked")).collect::<Vec<SimulationReport>>()
+    });
+
+    let elapsed = started.elapsed();
+    let mut report = SimulationReport::new(reports[0].players.clone());
+    report.games.extend(reports.into_iter().flat_map(|batch| batch.games));
+    let summary = report.summary();
+
+    println!(
+        "{} games on {threads} threads in {elapsed:.2?}: {:.0} games a second, {:.1} turns a game, {} unfinished",
+        summary.games,
+        summary.games as f64 / elapsed.as_secs_f64(),
+        summary.average_turns,
+        summary.unfinished,
+    );
+
+    for (seat, difficulty) in summary.seats.iter().zip(LINEUP) {
+        println!("  {:<8} {difficulty:?}: won {:5.1}%", seat.name, seat.win_rate * 100.0);
+    }
+
+    ExitCode::SUCCESS
+}
+
+/// Plays `count` games seeded from `seed` on, with bots whose own choices are seeded too.
+fn play(seed: u64, count: u32) -> SimulationReport {
+    let mut rngs = (0..LINEUP.len()).map(|bot| ChaCha8Rng::seed_from_u64(seed ^ ((bot as u64) << 56))).collect::<Vec<ChaCha8Rng>>();
+    let mut bots = rngs.iter_mut().zip(LINEUP).map(|(rng, difficulty)| Ai::new(rng, difficulty)).collect::<Vec<Ai<ChaCha8Rng>>>();
+    let mut players = bots.iter_mut().map(|bot| bot as &mut dyn Player).collect::<Vec<&mut dyn Player>>();
+
+    Simulation { seed, games: count, ..Simulation::default() }.run(&mut players)
+}
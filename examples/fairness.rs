@@ -0,0 +1,135 @@
+//! Measures how much where a player sits in the order of play is worth under a set of rules, for
+//! whoever designs a variant: bots of one difficulty play a large seeded batch, and wins are
+//! counted by place from whoever took the first turn, split by the way play started.
+//!
+//! ```text
+//! cargo run --release --example fairness [--rules PRESET] [--seats N] [--games N] [--seed N] [--bots easy|medium|hard] [--csv PATH]
+//! ```
+//!
+//! The batch is spread over every core like the benchmark's, each thread with its own range of
+//! seeds, so a run comes out the same however many cores there are. `--csv` needs the `report`
+//! feature.
+
+use std::env;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::thread;
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use uno_cli::player::{AIDifficulty, Ai, Player};
+use uno_cli::rules::Rules;
+use uno_cli::sim::{PositionReport, Simulation};
+
+const USAGE: &str = "usage: fairness [--rules PRESET] [--seats N] [--games N] [--seed N] [--bots easy|medium|hard] [--csv PATH]";
+
+fn main() -> ExitCode {
+    let (mut rules, mut difficulty, mut csv) = (Rules::default(), AIDifficulty::Hard, None::<PathBuf>);
+    let (mut seats, mut games, mut seed) = (4usize, 100_000u32, 0u64);
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        let value = args.next();
+
+        match (arg.as_str(), value.as_deref()) {
+            ("--rules", Some(name)) => match Rules::preset(name) {
+                Some(preset) => rules = preset,
+                None => return usage(),
+            },
+            ("--seats", Some(value)) => match value.parse() {
+                Ok(count @ 2..=6) => seats = count,
+                _ => return usage(),
+            },
+            ("--games", Some(value)) => match value.parse() {
+                Ok(count) => games = count,
+                Err(_) => return usage(),
+            },
+            ("--seed", Some(value)) => match value.parse() {
+                Ok(number) => seed = number,
+                Err(_) => return usage(),
+            },
+            ("--bots", Some("easy")) => difficulty = AIDifficulty::Easy,
+            ("--bots", Some("medium")) => difficulty = AIDifficulty::Medium,
+            ("--bots", Some("hard")) => difficulty = AIDifficulty::Hard,
+            ("--csv", Some(path)) => csv = Some(path.into()),
+            _ => return usage(),
+        }
+    }
+
+    let threads = thread::available_parallelism().map_or(1, |threads| threads.get() as u32);
+
+    let report = thread::scope(|scope| {
+        let batches = (0..threads).map(|thread| {
+            // Batches start on a multiple of the table, so the first turn still goes round it evenly.
+            let rounds = games.div_ceil(seats as u32);
+            let first = (thread * (rounds / threads) + thread.min(rounds % threads)) * seats as u32;
+            let count = ((rounds / threads + u32::from(thread < rounds % threads)) * seats as u32).min(games.saturating_sub(first));
+            let simulation = Simulation { seed: seed + u64::from(first), games: count, rules, ..Simulation::default() };
+            scope.spawn(move || play(&simulation, seats, difficulty))
+        });
+
+        batches.collect::<Vec<_>>().into_iter().fold(PositionReport::new(seats), |mut report, batch| {
+            report.merge(&batch.join().expect("a batch panicked"));
+            report
+        })
+    });
+
+    println!("{} games between {seats} {difficulty:?} bots, {} unfinished", report.games, report.unfinished);
+    println!("{:>5}  {:>7}  {:>9}  {:>16}", "place", "won", "clockwise", "counterclockwise");
+
+    let [clockwise, counterclockwise] = &report.directions;
+    let share = |wins: u32, games: u32| if games == 0 { 0.0 } else { wins as f64 / games as f64 * 100.0 };
+
+    for (place, rate) in report.win_rates().iter().enumerate() {
+        println!(
+            "{:>5}  {:>6.2}%  {:>8.2}%  {:>15.2}%",
+            place + 1,
+            rate * 100.0,
+            share(clockwise.wins[place], clockwise.games),
+            share(counterclockwise.wins[place], counterclockwise.games),
+        );
+    }
+
+    println!(
+        "{} games started clockwise, {} counterclockwise; the first player wins {:.3} times their fair share",
+        clockwise.games,
+        counterclockwise.games,
+        report.first_player_advantage(),
+    );
+
+    match csv.map(|path| write_csv(&report, &path)) {
+        Some(Err(error)) => {
+            eprintln!("couldn't write the report: {error}");
+            ExitCode::FAILURE
+        }
+        _ => ExitCode::SUCCESS,
+    }
+}
+
+/// Plays one batch with a bot of `difficulty` in every seat, so that only where they sit differs.
+fn play(simulation: &Simulation, seats: usize, difficulty: AIDifficulty) -> PositionReport {
+    let mut rngs = (0..seats).map(|bot| ChaCha8Rng::seed_from_u64(simulation.seed ^ ((bot as u64) << 56))).collect::<Vec<ChaCha8Rng>>();
+    let mut bots = rngs.iter_mut().map(|rng| Ai::new(rng, difficulty)).collect::<Vec<Ai<ChaCha8Rng>>>();
+    let mut players = bots.iter_mut().map(|bot| bot as &mut dyn Player).collect::<Vec<&mut dyn Player>>();
+
+    simulation.positions(&mut players)
+}
+
+fn usage() -> ExitCode {
+    eprintln!("{USAGE}");
+    ExitCode::FAILURE
+}
+
+#[cfg(feature = "report")]
+fn write_csv(report: &PositionReport, path: &std::path::Path) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+    report.write_csv(&mut writer)?;
+    writer.flush()
+}
+
+#[cfg(not(feature = "report"))]
+fn write_csv(_report: &PositionReport, _path: &std::path::Path) -> std::io::Result<()> {
+    Err(std::io::Error::other("this build doesn't include report writing (the `report` feature)"))
+}
@@ -0,0 +1,129 @@
+//! Steps through a recorded game, to build a replay viewer from. Without a seat, each move is
+//! shown with where it sent the cards, as [`diff`] has it between the table before and after. With
+//! one, the game is narrated as that seat was sent it, their hand and all.
+//!
+//! ```text
+//! cargo run --example replay_viewer --features persistence [PATH] [--seat N] [--step]
+//! ```
+//!
+//! `PATH` holds a [`Replay`] as JSON, such as one saved from [`GameState::replay`]. Without
+//! one, a game between bots is played to view. `--step` waits for Enter after every move, or with
+//! a seat, after everything it was sent.
+
+use std::env;
+use std::fs;
+use std::io::{self, BufRead};
+use std::process::ExitCode;
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use uno_cli::console::Console;
+use uno_cli::diff::diff;
+use uno_cli::game::{GameState, Spectator, TurnResult};
+use uno_cli::player::{AIDifficulty, Ai, Player};
+use uno_cli::render::{RenderConfig, RenderMode};
+use uno_cli::replay::Replay;
+
+const USAGE: &str = "usage: replay_viewer [PATH] [--seat N] [--step]";
+
+fn main() -> ExitCode {
+    let (mut path, mut seat, mut step) = (None, None, false);
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seat" => match args.next().and_then(|seat| seat.parse().ok()) {
+                Some(number) => seat = Some(number),
+                None => return usage(),
+            },
+            "--step" => step = true,
+            _ if path.is_none() && !arg.starts_with("--") => path = Some(arg),
+            _ => return usage(),
+        }
+    }
+
+    let replay = match path {
+        Some(path) => match fs::read_to_string(&path).map_err(|error| error.to_string()).and_then(|json| serde_json::from_str::<Replay>(&json).map_err(|error| error.to_string())) {
+            Ok(replay) => replay,
+            Err(error) => {
+                eprintln!("couldn't read a replay from {path}: {error}");
+                return ExitCode::FAILURE;
+            }
+        },
+        None => record(),
+    };
+
+    // A recording the engine no longer agrees with would show a different game.
+    if let Err(divergence) = replay.verify() {
+        eprintln!("the replay doesn't play out as recorded: {divergence:?}");
+        return ExitCode::FAILURE;
+    }
+
+    println!("seed {}, {} between {}", replay.seed, plural(replay.actions.len(), "move"), replay.players.join(", "));
+    let pause = || {
+        if step {
+            io::stdin().lock().read_line(&mut String::new()).unwrap_or_default();
+        }
+    };
+
+    match seat {
+        Some(seat) => {
+            let Some(perspective) = replay.perspective(seat) else {
+                eprintln!("there's no seat {seat}");
+                return ExitCode::FAILURE;
+            };
+
+            let mut console = Console::new(RenderConfig::new(RenderMode::Plain));
+
+            for frame in &perspective.frames {
+                console.observe_event(&frame.event, &frame.view);
+                pause();
+            }
+        }
+        None => {
+            let mut before = replay.snapshot_after(0).expect("a verified replay plays out");
+
+            // Two moves in one turn come out in the same snapshot, so the second of them shows
+            // no cards moving of its own.
+            for (index, action) in replay.actions.iter().enumerate() {
+                let after = replay.snapshot_after(index + 1).expect("a verified replay plays out");
+                let name = &replay.players[action.seat];
+
+                match action.result {
+                    TurnResult::Played(card) => println!("{}. {name} played {card}", index + 1),
+                    TurnResult::Drew => println!("{}. {name} drew", index + 1),
+                }
+
+                print!("{}", diff(&before, &after));
+                before = after;
+                pause();
+            }
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+/// Plays a quick seeded game between bots, for when there's no replay to view.
+fn record() -> Replay {
+    let mut rngs = [ChaCha8Rng::seed_from_u64(1), ChaCha8Rng::seed_from_u64(2), ChaCha8Rng::seed_from_u64(3)];
+    let [easy, medium, hard] = &mut rngs;
+    let mut bots = [Ai::new(easy, AIDifficulty::Easy), Ai::new(medium, AIDifficulty::Medium), Ai::new(hard, AIDifficulty::Hard)];
+
+    let mut game = GameState::with_seed(bots.iter_mut().map(|bot| bot as &mut dyn Player).collect(), 42);
+    while game.play_turn().is_none() {}
+
+    game.replay().expect("the game is seeded")
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    match count {
+        1 => format!("1 {noun}"),
+        count => format!("{count} {noun}s"),
+    }
+}
+
+fn usage() -> ExitCode {
+    eprintln!("{USAGE}");
+    ExitCode::FAILURE
+}
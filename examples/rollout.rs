@@ -0,0 +1,109 @@
+//! Rolls out episodes for reinforcement learning: a policy to be trained plays against the
+//! built-in bots, and every decision it made is written out with what it saw, what it did and
+//! what the episode came to, ready to learn from.
+//!
+//! ```text
+//! cargo run --release --example rollout --features training [EPISODES] [PATH]
+//! ```
+//!
+//! The policy here picks among its legal moves at random, which is where training starts. Swap
+//! in one that asks a model, using [`training`](uno_cli::training)'s encoding of the view, and
+//! the rest stays the same. Records go to `PATH`, or `rollouts.jsonl`, one per line.
+
+use std::env;
+use std::fs::File;
+use std::io::{self, BufWriter};
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use uno_cli::card::{Card, CardColor};
+use uno_cli::game::{GameState, Turn, TurnResult};
+use uno_cli::player::{AIDifficulty, Ai, Player};
+use uno_cli::summary::GameOutcome;
+use uno_cli::training::{self, TrainingRecord};
+
+/// Where the policy sits, with the bots after it.
+const SEAT: usize = 0;
+
+/// Episodes still going after this many turns are cut off, unfinished and worth nothing.
+const MAX_TURNS: u32 = 1000;
+
+/// Plays a legal move at random: any card it can, or a draw. A wild takes the color it holds
+/// the most of.
+struct RandomPolicy {
+    rng: ChaCha8Rng,
+}
+
+impl Player for RandomPolicy {
+    fn name(&self) -> &str {
+        "Policy"
+    }
+
+    fn execute_turn(&mut self, turn: &Turn) -> TurnResult {
+        let choice = self.rng.gen_range(0..=turn.playable_hand.len());
+
+        let Some(mut card) = turn.playable_hand.get(choice).copied() else {
+            return TurnResult::Drew;
+        };
+
+        if card.is_wild() {
+            let held = |color: CardColor| turn.full_hand.iter().filter(|card| !card.is_wild() && card.color() == Some(color)).count();
+            card.with_color(CardColor::ALL.into_iter().max_by_key(|color| held(*color)).unwrap_or(CardColor::Red));
+        }
+
+        TurnResult::Played(card)
+    }
+
+    fn observe_turn(&self, _other: &dyn Player, _card: &Card) {}
+
+    fn observe_turn_skip(&self, _observed_cards: Option<&[Card]>) {}
+}
+
+fn main() -> io::Result<()> {
+    let mut args = env::args().skip(1);
+    let episodes = args.next().and_then(|episodes| episodes.parse().ok()).unwrap_or(1000u64);
+    let path = args.next().unwrap_or_else(|| "rollouts.jsonl".to_string());
+
+    let mut policy = RandomPolicy { rng: ChaCha8Rng::seed_from_u64(0) };
+    let mut rngs = [ChaCha8Rng::seed_from_u64(1), ChaCha8Rng::seed_from_u64(2)];
+    let [easy, hard] = &mut rngs;
+    let mut bots = [Ai::new(easy, AIDifficulty::Easy), Ai::new(hard, AIDifficulty::Hard)];
+
+    let mut writer = BufWriter::new(File::create(&path)?);
+    let (mut decisions, mut wins, mut reward) = (0, 0, 0i64);
+
+    for episode in 0..episodes {
+        let [easy, hard] = &mut bots;
+        let mut game = GameState::with_seed(vec![&mut policy as &mut dyn Player, easy, hard], episode);
+        game.record_decisions();
+
+        let mut turns = 0;
+
+        let outcome = loop {
+            if let Some(outcome) = game.play_turn() {
+                break outcome;
+            }
+
+            turns += 1;
+
+            if turns >= MAX_TURNS {
+                break game.abort("turn limit reached");
+            }
+        };
+
+        let records = training::records(game.decisions(), &outcome).into_iter().filter(|record| record.seat == SEAT).collect::<Vec<TrainingRecord>>();
+        training::write_jsonl(&records, &mut writer)?;
+
+        decisions += records.len();
+        wins += matches!(&outcome, GameOutcome::Won(summary) if summary.ranking.first() == Some(&SEAT)) as u32;
+        reward += records.first().map_or(0, |record| record.reward as i64);
+    }
+
+    println!(
+        "{episodes} episodes, {decisions} decisions written to {path}: won {:.1}%, averaging {:.1} points an episode",
+        wins as f64 / episodes.max(1) as f64 * 100.0,
+        reward as f64 / episodes.max(1) as f64,
+    );
+
+    Ok(())
+}
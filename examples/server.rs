@@ -0,0 +1,212 @@
+//! A game hosted over WebSocket for two remote players, each a bot on its own connection, to
+//! start a server from. The server seats whoever joins with [`Sessions`], plays the game with a
+//! [`GameManager`] and sends every seat its updates; each client keeps its table with a
+//! [`ClientState`] and plays the first card it can.
+//!
+//! ```text
+//! cargo run --example server --features wire
+//! ```
+//!
+//! Every message is a text frame of JSON. The server's side of each connection runs on its own
+//! thread, which passes on what it reads and sends what the game has for it in between.
+
+use std::error::Error;
+use std::io::ErrorKind;
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread;
+use std::time::Duration;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use tungstenite::{Message, WebSocket};
+use uno_cli::console::Console;
+use uno_cli::game::Spectator;
+use uno_cli::render::{RenderConfig, RenderMode};
+use uno_cli::server::client::ClientState;
+use uno_cli::server::manager::{GameConfig, GameManager, ManagerError};
+use uno_cli::server::protocol::{Action, ClientMessage, Rejection, ServerMessage, PROTOCOL_VERSION};
+use uno_cli::server::session::{ConnectionId, Outcome, Sessions};
+use uno_cli::server::wire::Encoding;
+use uno_cli::summary::GameOutcome;
+
+type Result<T> = std::result::Result<T, Box<dyn Error + Send + Sync>>;
+
+const PLAYERS: [&str; 2] = ["Ada", "Grace"];
+
+/// How long a connection waits to read before seeing whether it has anything to send.
+const POLL: Duration = Duration::from_millis(10);
+
+fn main() -> Result<()> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let address = listener.local_addr()?;
+    println!("serving on ws://{address}");
+
+    let clients = PLAYERS.map(|name| thread::spawn(move || client(address, name)));
+    serve(listener)?;
+
+    for client in clients {
+        client.join().expect("a client panicked")?;
+    }
+
+    Ok(())
+}
+
+/// Takes a connection for every seat, then plays the game out with whatever they send.
+fn serve(listener: TcpListener) -> Result<()> {
+    let (messages, incoming) = mpsc::channel::<(ConnectionId, ClientMessage)>();
+    let mut connections = vec![];
+    let mut threads = vec![];
+
+    for connection in 0..PLAYERS.len() as ConnectionId {
+        let (stream, _) = listener.accept()?;
+        stream.set_read_timeout(Some(POLL))?;
+        let socket = tungstenite::accept(stream).map_err(|error| error.to_string())?;
+        let (outgoing, to_send) = mpsc::channel();
+        let messages = messages.clone();
+
+        threads.push(thread::spawn(move || connect(connection, socket, messages, to_send)));
+        connections.push(outgoing);
+    }
+
+    drop(messages);
+
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut sessions = Sessions::new(PLAYERS.len());
+    let mut manager = GameManager::with_seed(7);
+    let mut game = None;
+    let mut console = Console::new(RenderConfig::new(RenderMode::Plain));
+
+    while let Ok((connection, message)) = incoming.recv() {
+        match sessions.handle(connection, message, &mut rng) {
+            Outcome::Reply(reply) => send(&connections[connection as usize], &reply)?,
+            Outcome::Act { seat, action } => {
+                let Some(game) = game else {
+                    continue;
+                };
+
+                match manager.submit_action(game, seat, action) {
+                    Ok(()) => {}
+                    Err(ManagerError::Illegal(reason)) => send(&connections[connection as usize], &ServerMessage::Rejected { reason: Rejection::Illegal(reason) })?,
+                    Err(error) => return Err(error.to_string().into()),
+                }
+            }
+        }
+
+        if game.is_none() && sessions.len() == PLAYERS.len() {
+            let names = (0..sessions.len()).filter_map(|seat| sessions.name(seat)).map(String::from).collect();
+            game = Some(manager.create_game(GameConfig::default(), names).map_err(|error| error.to_string())?);
+        }
+
+        let Some(game) = game else {
+            continue;
+        };
+
+        for (seat, update) in manager.take_updates(game) {
+            // The first seat's updates, narrated, follow the game for whoever runs this.
+            if let (0, ServerMessage::Update { event, view }) = (seat, &update) {
+                console.observe_event(event, view);
+            }
+
+            if let Some(connection) = sessions.connection(seat) {
+                send(&connections[connection as usize], &update)?;
+            }
+        }
+
+        if let Some(outcome) = manager.outcome(game) {
+            match outcome {
+                GameOutcome::Won(summary) => println!("{} won, scoring {}", summary.winner, summary.points),
+                GameOutcome::Aborted { reason, .. } => println!("the game was stopped: {reason}"),
+            }
+
+            break;
+        }
+    }
+
+    // With nothing more to send, each connection closes, which tells its client the game is over.
+    drop(connections);
+
+    for thread in threads {
+        thread.join().expect("a connection panicked")?;
+    }
+
+    Ok(())
+}
+
+/// The server's side of a connection: passes on every message the client sends, and sends it
+/// what the game has for it, until the game has nothing more to send and the connection closes.
+fn connect(connection: ConnectionId, mut socket: WebSocket<TcpStream>, messages: Sender<(ConnectionId, ClientMessage)>, to_send: Receiver<String>) -> Result<()> {
+    loop {
+        loop {
+            match to_send.try_recv() {
+                Ok(text) => socket.send(Message::text(text))?,
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    socket.close(None)?;
+                    // Waits for the client to answer the close before hanging up.
+                    while socket.read().is_ok() {}
+                    return Ok(());
+                }
+            }
+        }
+
+        match socket.read() {
+            Ok(Message::Text(text)) => match Encoding::Json.decode(text.as_bytes()) {
+                Ok(message) => messages.send((connection, message)).unwrap_or_default(),
+                Err(error) => eprintln!("connection {connection} sent something unreadable: {error}"),
+            },
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(error)) if matches!(error.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed) => return Ok(()),
+            Err(error) => return Err(error.into()),
+        }
+    }
+}
+
+/// Joins the game at `address` as `name`, and plays until the server closes the connection.
+fn client(address: SocketAddr, name: &str) -> Result<()> {
+    let stream = TcpStream::connect(address)?;
+    let (mut socket, _) = tungstenite::client(format!("ws://{address}"), stream.try_clone()?).map_err(|error| error.to_string())?;
+    stream.set_read_timeout(Some(POLL))?;
+
+    let mut state = ClientState::new();
+    let mut unanswered = false;
+
+    let join = Encoding::Json.encode(&ClientMessage::Join { name: name.to_string(), protocol: PROTOCOL_VERSION })?;
+    socket.send(Message::text(String::from_utf8(join)?))?;
+
+    loop {
+        match socket.read() {
+            Ok(Message::Text(text)) => {
+                state.receive(&Encoding::Json.decode::<ServerMessage>(text.as_bytes())?);
+                unanswered = true;
+            }
+            Ok(_) => {}
+            // Updates come in bursts as the game plays on, and only the last of one shows where
+            // it stopped, so act once nothing more has come in for a moment.
+            Err(tungstenite::Error::Io(error)) if matches!(error.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                if !unanswered {
+                    continue;
+                }
+
+                unanswered = false;
+                let moves = state.legal_moves();
+                let first = moves.iter().find(|action| matches!(action, Action::Play { .. })).or(moves.last());
+
+                if let Some(action) = first {
+                    let message = Encoding::Json.encode(&state.act(action.clone())?)?;
+                    socket.send(Message::text(String::from_utf8(message)?))?;
+                }
+            }
+            Err(tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed) => return Ok(()),
+            Err(error) => return Err(error.into()),
+        }
+    }
+}
+
+fn send<T: serde::Serialize>(connection: &Sender<String>, message: &T) -> Result<()> {
+    let text = String::from_utf8(Encoding::Json.encode(message)?)?;
+    // A connection that has already closed has nobody left to tell.
+    connection.send(text).unwrap_or_default();
+    Ok(())
+}
@@ -0,0 +1,223 @@
+//! Achievements players earn over the course of a game, worked out from the events it sends.
+//!
+//! Add an [`Achievements`] to a game as a spectator, then read what was unlocked once it's over.
+
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use crate::card::Card;
+use crate::game::{GameEvent, GameView, Spectator};
+use crate::player::PlayerId;
+
+/// Something a player can earn. Definitions look at every event, with the spectators' view of
+/// the table, and say who has just earned them. They should clear anything they keep track of
+/// when a game starts.
+pub trait Achievement: Send {
+    /// Stays the same between versions, for saving who has earned what and for translations.
+    fn id(&self) -> &'static str;
+
+    /// The player who has just earned this with `event`, if anyone has.
+    fn observe(&mut self, event: &GameEvent, view: &GameView) -> Option<PlayerId>;
+}
+
+/// Players are kept by name, which unlike their [`PlayerId`] they keep from one game to the next.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unlocked {
+    pub player: String,
+    pub achievement: &'static str,
+}
+
+/// Checks a set of achievements against every event, and keeps a list of who has earned what.
+/// Each player earns each achievement once, however many games are played.
+pub struct Achievements {
+    definitions: Vec<Box<dyn Achievement>>,
+    unlocked: Vec<Unlocked>,
+    /// How much of `unlocked` [`take_new`](Achievements::take_new) has already returned.
+    taken: usize,
+}
+
+impl Achievements {
+    /// A tracker without any achievements; see [`builtin`](Achievements::builtin).
+    pub fn new() -> Achievements {
+        Achievements { definitions: Vec::new(), unlocked: Vec::new(), taken: 0 }
+    }
+
+    /// Every achievement the crate defines.
+    pub fn builtin() -> Achievements {
+        Achievements::new()
+            .with(DrawFourFinish::default())
+            .with(Comeback::new(20))
+            .with(NoDraws::default())
+            .with(Stacker::default())
+    }
+
+    pub fn with(mut self, achievement: impl Achievement + 'static) -> Achievements {
+        self.add(achievement);
+        self
+    }
+
+    pub fn add(&mut self, achievement: impl Achievement + 'static) {
+        self.definitions.push(Box::new(achievement));
+    }
+
+    /// Everything earned so far, in the order it was earned.
+    pub fn unlocked(&self) -> &[Unlocked] {
+        &self.unlocked
+    }
+
+    /// What was earned since this was last called.
+    pub fn take_new(&mut self) -> &[Unlocked] {
+        let new = &self.unlocked[self.taken..];
+        self.taken = self.unlocked.len();
+        new
+    }
+
+    pub fn has(&self, player: &str, achievement: &str) -> bool {
+        self.unlocked.iter().any(|unlocked| unlocked.player == player && unlocked.achievement == achievement)
+    }
+}
+
+impl Default for Achievements {
+    fn default() -> Self {
+        Achievements::new()
+    }
+}
+
+impl Spectator for Achievements {
+    fn observe_event(&mut self, event: &GameEvent, view: &GameView) {
+        for definition in self.definitions.iter_mut() {
+            let Some(player) = definition.observe(event, view).map(|player| view.name_of(player)) else {
+                continue;
+            };
+
+            let achievement = definition.id();
+
+            if !self.unlocked.iter().any(|unlocked| unlocked.player == player && unlocked.achievement == achievement) {
+                self.unlocked.push(Unlocked { player, achievement });
+            }
+        }
+    }
+}
+
+/// Went out by playing a Draw Four.
+#[derive(Debug, Default)]
+pub struct DrawFourFinish {
+    last: Option<(PlayerId, Card)>,
+}
+
+impl Achievement for DrawFourFinish {
+    fn id(&self) -> &'static str {
+        "draw-four-finish"
+    }
+
+    fn observe(&mut self, event: &GameEvent, _view: &GameView) -> Option<PlayerId> {
+        match event {
+            GameEvent::Started { .. } => self.last = None,
+            GameEvent::Played { player, card } => self.last = Some((*player, *card)),
+            GameEvent::Won { player } => {
+                return match &self.last {
+                    Some((last, Card::DrawFour { .. })) if last == player => Some(*player),
+                    _ => None,
+                };
+            }
+            _ => {}
+        }
+
+        None
+    }
+}
+
+/// Won after holding at least some number of cards at once.
+#[derive(Debug)]
+pub struct Comeback {
+    threshold: usize,
+    /// The most cards each player has held this game.
+    most: Vec<(PlayerId, usize)>,
+}
+
+impl Comeback {
+    pub fn new(threshold: usize) -> Comeback {
+        Comeback { threshold, most: Vec::new() }
+    }
+}
+
+impl Achievement for Comeback {
+    fn id(&self) -> &'static str {
+        "comeback"
+    }
+
+    fn observe(&mut self, event: &GameEvent, view: &GameView) -> Option<PlayerId> {
+        if let GameEvent::Started { .. } = event {
+            self.most.clear();
+        }
+
+        for player in &view.players {
+            match self.most.iter_mut().find(|(id, _)| *id == player.id) {
+                Some((_, most)) => *most = (*most).max(player.cards),
+                None => self.most.push((player.id, player.cards)),
+            }
+        }
+
+        match event {
+            GameEvent::Won { player } => self.most
+                .iter()
+                .any(|(id, most)| id == player && *most >= self.threshold)
+                .then_some(*player),
+            _ => None,
+        }
+    }
+}
+
+/// Won without drawing a single card.
+#[derive(Debug, Default)]
+pub struct NoDraws {
+    drew: Vec<PlayerId>,
+}
+
+impl Achievement for NoDraws {
+    fn id(&self) -> &'static str {
+        "no-draws"
+    }
+
+    fn observe(&mut self, event: &GameEvent, _view: &GameView) -> Option<PlayerId> {
+        match event {
+            GameEvent::Started { .. } => self.drew.clear(),
+            GameEvent::Drew { player, .. } => self.drew.push(*player),
+            GameEvent::Won { player } if !self.drew.contains(player) => return Some(*player),
+            _ => {}
+        }
+
+        None
+    }
+}
+
+/// Answered a pending draw by stacking another draw card on it.
+#[derive(Debug, Default)]
+pub struct Stacker {
+    /// The player whose turn it is, if they started it with cards to draw.
+    facing: Option<PlayerId>,
+}
+
+impl Achievement for Stacker {
+    fn id(&self) -> &'static str {
+        "stacker"
+    }
+
+    fn observe(&mut self, event: &GameEvent, view: &GameView) -> Option<PlayerId> {
+        match event {
+            GameEvent::TurnStarted { player } => {
+                self.facing = (view.to_draw > 0).then_some(*player);
+                None
+            }
+            GameEvent::Played { player, card } => {
+                let facing = self.facing.take();
+                let stacked = matches!(card, Card::DrawTwo { .. } | Card::DrawFour { .. }) && facing == Some(*player);
+                stacked.then_some(*player)
+            }
+            GameEvent::Drew { .. } => {
+                self.facing = None;
+                None
+            }
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rusqlite::{params, Connection, OptionalExtension, Result};
+
+use crate::game::GameEvent;
+use crate::history::History;
+use crate::summary::RoundSummary;
+use crate::window::Interjection;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS games (
+        id INTEGER PRIMARY KEY,
+        finished_at INTEGER NOT NULL,
+        winner TEXT NOT NULL,
+        points INTEGER NOT NULL,
+        turns INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS game_players (
+        game_id INTEGER NOT NULL REFERENCES games(id),
+        seat INTEGER NOT NULL,
+        name TEXT NOT NULL,
+        cards_left INTEGER NOT NULL,
+        points INTEGER NOT NULL,
+        PRIMARY KEY (game_id, seat)
+    );
+    CREATE TABLE IF NOT EXISTS game_turns (
+        game_id INTEGER NOT NULL REFERENCES games(id),
+        seq INTEGER NOT NULL,
+        turn INTEGER NOT NULL,
+        player TEXT,
+        action TEXT NOT NULL,
+        card TEXT,
+        count INTEGER,
+        PRIMARY KEY (game_id, seq)
+    );
+    CREATE INDEX IF NOT EXISTS game_players_name ON game_players(name);
+";
+
+/// Completed games stored in SQLite: metadata, final scores and every action taken.
+pub struct Archive {
+    connection: Connection,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchivedGame {
+    pub id: i64,
+    /// Seconds since the Unix epoch.
+    pub finished_at: i64,
+    pub winner: String,
+    pub points: u32,
+    pub turns: u32,
+    pub players: Vec<ArchivedPlayer>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchivedPlayer {
+    pub seat: u32,
+    pub name: String,
+    pub cards_left: u32,
+    pub points: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchivedTurn {
+    pub turn: u32,
+    pub player: Option<String>,
+    /// One of `started`, `played`, `drew`, `skipped` or `won`.
+    pub action: String,
+    pub card: Option<String>,
+    pub count: Option<u32>,
+}
+
+/// How two players have fared in the games they both took part in.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct HeadToHead {
+    pub games: u32,
+    pub wins: u32,
+    pub losses: u32,
+}
+
+impl Archive {
+    pub fn open(path: impl AsRef<Path>) -> Result<Archive> {
+        Archive::with_connection(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Archive> {
+        Archive::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(connection: Connection) -> Result<Archive> {
+        connection.execute_batch(SCHEMA)?;
+        Ok(Archive { connection })
+    }
+
+    /// Writes a finished game and returns its id.
+    pub fn store(&mut self, history: &History, summary: &RoundSummary) -> Result<i64> {
+        let finished_at = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs() as i64);
+
+        let transaction = self.connection.transaction()?;
+
+        transaction.execute(
+            "INSERT INTO games (finished_at, winner, points, turns) VALUES (?1, ?2, ?3, ?4)",
+            params![finished_at, summary.winner, summary.points, summary.turns],
+        )?;
+
+        let id = transaction.last_insert_rowid();
+
+        for (seat, player) in summary.players.iter().enumerate() {
+            transaction.execute(
+                "INSERT INTO game_players (game_id, seat, name, cards_left, points) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![id, seat as u32, player.name, player.hand.len() as u32, player.points],
+            )?;
+        }
+
+        let mut seq = 0;
+
+        for entry in &history.entries {
+            let (player, action, card, count) = match &entry.event {
+                GameEvent::Started { top_card } => (None, "started", Some(top_card.to_string()), None),
+                GameEvent::Played { player, card } => (Some(player), "played", Some(card.to_string()), None),
+                GameEvent::Drew { player, count } => (Some(player), "drew", None, Some(*count as u32)),
+                GameEvent::Skipped { player } => (Some(player), "skipped", None, None),
+                GameEvent::Traded { player, .. } => (Some(player), "traded", None, None),
+                GameEvent::Discarded { player, cards } => (Some(player), "discarded", None, Some(cards.len() as u32)),
+                GameEvent::Spared { player, count } => (Some(player), "spared", None, Some(*count as u32)),
+                GameEvent::Eliminated { player } => (Some(player), "eliminated", None, None),
+                GameEvent::PlayerLeft { player, .. } => (Some(player), "left", None, None),
+                GameEvent::Finished { player, place } => (Some(player), "finished", None, Some(*place as u32)),
+                GameEvent::Interjected { player, interjection } => match interjection {
+                    Interjection::CallOut => (Some(player), "called out", None, None),
+                    Interjection::Challenge => (Some(player), "challenged", None, None),
+                    Interjection::JumpIn(card) => (Some(player), "jumped in", Some(card.to_string()), None),
+                },
+                GameEvent::Stacked { player, to_draw } => (Some(player), "stacked", None, Some(*to_draw as u32)),
+                GameEvent::ColorsNamed { player, colors } => (Some(player), "named colors", Some(colors.to_string()), None),
+                GameEvent::Won { player } => (Some(player), "won", None, None),
+                GameEvent::Aborted { .. } => (None, "aborted", None, None),
+                GameEvent::Paused => (None, "paused", None, None),
+                GameEvent::Resumed => (None, "resumed", None, None),
+                GameEvent::Setup { .. }
+                | GameEvent::TurnStarted { .. }
+                | GameEvent::Emote { .. }
+                | GameEvent::DebugHands { .. }
+                | GameEvent::WindowOpened { .. }
+                // What everyone was left holding is kept with the players.
+                | GameEvent::Revealed { .. } => continue,
+            };
+
+            transaction.execute(
+                "INSERT INTO game_turns (game_id, seq, turn, player, action, card, count) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![id, seq, entry.turn, player.and_then(|player| history.name_of(*player)), action, card, count],
+            )?;
+
+            seq += 1;
+        }
+
+        transaction.commit()?;
+
+        Ok(id)
+    }
+
+    pub fn game(&self, id: i64) -> Result<Option<ArchivedGame>> {
+        let game = self.connection
+            .query_row(
+                "SELECT id, finished_at, winner, points, turns FROM games WHERE id = ?1",
+                params![id],
+                |row| Ok(ArchivedGame {
+                    id: row.get(0)?,
+                    finished_at: row.get(1)?,
+                    winner: row.get(2)?,
+                    points: row.get(3)?,
+                    turns: row.get(4)?,
+                    players: vec![],
+                }),
+            )
+            .optional()?;
+
+        match game {
+            Some(mut game) => {
+                game.players = self.players(id)?;
+                Ok(Some(game))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Every game's id, oldest first.
+    pub fn game_ids(&self) -> Result<Vec<i64>> {
+        self.connection
+            .prepare("SELECT id FROM games ORDER BY id")?
+            .query_map([], |row| row.get(0))?
+            .collect()
+    }
+
+    /// Every game `name` took part in, most recent first.
+    pub fn games_by_player(&self, name: &str) -> Result<Vec<ArchivedGame>> {
+        let ids = self.connection
+            .prepare("SELECT game_id FROM game_players WHERE name = ?1 ORDER BY game_id DESC")?
+            .query_map(params![name], |row| row.get(0))?
+            .collect::<Result<Vec<i64>>>()?;
+
+        let mut games = vec![];
+
+        for id in ids {
+            if let Some(game) = self.game(id)? {
+                games.push(game);
+            }
+        }
+
+        Ok(games)
+    }
+
+    /// The recorded actions of a game, in the order they happened.
+    pub fn turns(&self, id: i64) -> Result<Vec<ArchivedTurn>> {
+        self.connection
+            .prepare("SELECT turn, player, action, card, count FROM game_turns WHERE game_id = ?1 ORDER BY seq")?
+            .query_map(params![id], |row| Ok(ArchivedTurn {
+                turn: row.get(0)?,
+                player: row.get(1)?,
+                action: row.get(2)?,
+                card: row.get(3)?,
+                count: row.get(4)?,
+            }))?
+            .collect()
+    }
+
+    /// `player`'s record against `opponent` in the games they both played. Games someone else
+    /// won only count towards `games`.
+    pub fn head_to_head(&self, player: &str, opponent: &str) -> Result<HeadToHead> {
+        self.connection.query_row(
+            "SELECT COUNT(*), COALESCE(SUM(winner = ?1), 0), COALESCE(SUM(winner = ?2), 0) FROM games
+             WHERE id IN (SELECT game_id FROM game_players WHERE name = ?1)
+               AND id IN (SELECT game_id FROM game_players WHERE name = ?2)",
+            params![player, opponent],
+            |row| Ok(HeadToHead {
+                games: row.get(0)?,
+                wins: row.get(1)?,
+                losses: row.get(2)?,
+            }),
+        )
+    }
+
+    fn players(&self, id: i64) -> Result<Vec<ArchivedPlayer>> {
+        self.connection
+            .prepare("SELECT seat, name, cards_left, points FROM game_players WHERE game_id = ?1 ORDER BY seat")?
+            .query_map(params![id], |row| Ok(ArchivedPlayer {
+                seat: row.get(0)?,
+                name: row.get(1)?,
+                cards_left: row.get(2)?,
+                points: row.get(3)?,
+            }))?
+            .collect()
+    }
+}
@@ -0,0 +1,252 @@
+//! Checks that nothing a seat is sent gives away cards it shouldn't see: the other hands and
+//! the order of the deck.
+//!
+//! A view is checked by dealing the hidden cards out differently and making sure the seat
+//! can't tell. Snapshots and replays aren't checked, since they hold the whole game on purpose
+//! and are only for whoever runs it.
+
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use crate::card::{Card, CardColor};
+use crate::game::{GameEvent, GameState, GameView, Turn, TurnResult};
+use crate::player::Player;
+use crate::snapshot::{Snapshot, SnapshotError};
+
+/// Something a seat, or a spectator when `seat` is `None`, could learn a hidden card from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Leak {
+    /// The view changes when only cards hidden from it do.
+    View { seat: Option<usize> },
+    /// The event names a card that the view it came with doesn't show.
+    Event { seat: Option<usize>, event: Box<GameEvent> },
+}
+
+/// Whether every view of the game in `snapshot` stays the same when the cards each one can't
+/// see are dealt out differently. Fingerprints are left out of the comparison: they hash the
+/// whole table so clients can tell states apart, but don't say what's in it.
+pub fn check_views(snapshot: &Snapshot) -> Result<(), Leak> {
+    let seats = snapshot.hands.len();
+
+    for seat in (0..seats).map(Some).chain([None]) {
+        let mut hidden = snapshot.clone();
+        let mut pool = hidden.deck.clone();
+
+        for (_, hand) in hidden.hands.iter().enumerate().filter(|&(other, _)| Some(other) != seat) {
+            pool.extend(hand.iter().copied());
+        }
+
+        // Every hidden card moves one place along, so the hands and deck keep their sizes.
+        if !pool.is_empty() {
+            pool.rotate_left(1);
+        }
+
+        let mut pool = pool.into_iter();
+        hidden.deck = pool.by_ref().take(hidden.deck.len()).collect();
+
+        for (_, hand) in hidden.hands.iter_mut().enumerate().filter(|&(other, _)| Some(other) != seat) {
+            *hand = pool.by_ref().take(hand.len()).collect();
+        }
+
+        if view_of(snapshot, seat) != view_of(&hidden, seat) {
+            return Err(Leak::View { seat });
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether `event`, sent along with `view`, only names cards that the view shows.
+pub fn check_event(event: &GameEvent, view: &GameView) -> Result<(), Leak> {
+    let visible = |card: &Card| view.top_card.as_ref() == Some(card) || view.hand.contains(card);
+
+    let leaks = match event {
+        GameEvent::Started { top_card: card } | GameEvent::Played { card, .. } | GameEvent::WindowOpened { card, .. } => !visible(card),
+        // Acting out of turn shows the table what was done, a jumped-in card included.
+        GameEvent::Interjected { .. } => false,
+        // They go onto the pile face up, so the whole table sees them.
+        GameEvent::Discarded { .. } => false,
+        // Once the round is over, every hand is shown.
+        GameEvent::Revealed { .. } => false,
+        // Only ever meant for spectators.
+        GameEvent::DebugHands { .. } => view.seat.is_some(),
+        GameEvent::TurnStarted { .. }
+        | GameEvent::Drew { .. }
+        | GameEvent::Skipped { .. }
+        | GameEvent::Traded { .. }
+        | GameEvent::Spared { .. }
+        | GameEvent::Eliminated { .. }
+        | GameEvent::Finished { .. }
+        | GameEvent::Stacked { .. }
+        | GameEvent::ColorsNamed { .. }
+        | GameEvent::PlayerLeft { .. }
+        | GameEvent::Won { .. }
+        | GameEvent::Aborted { .. }
+        | GameEvent::Paused
+        | GameEvent::Resumed
+        | GameEvent::Emote { .. }
+        | GameEvent::Setup { .. } => false,
+    };
+
+    match leaks {
+        true => Err(Leak::Event { seat: view.seat, event: Box::new(event.clone()) }),
+        false => Ok(()),
+    }
+}
+
+/// Plays for another player, checking every event it's sent with [`check_event`].
+pub struct Audited<'p> {
+    player: &'p mut dyn Player,
+    leaks: Vec<Leak>,
+}
+
+impl<'p> Audited<'p> {
+    pub fn new(player: &'p mut dyn Player) -> Audited<'p> {
+        Audited { player, leaks: Vec::new() }
+    }
+
+    /// Every leak found so far.
+    pub fn leaks(&self) -> &[Leak] {
+        &self.leaks
+    }
+}
+
+impl Player for Audited<'_> {
+    fn name(&self) -> &str {
+        self.player.name()
+    }
+
+    fn execute_turn(&mut self, turn: &Turn) -> TurnResult {
+        self.player.execute_turn(turn)
+    }
+
+    fn observe_turn(&self, other: &dyn Player, card: &Card) {
+        self.player.observe_turn(other, card);
+    }
+
+    fn observe_turn_skip(&self, observed_cards: Option<&[Card]>) {
+        self.player.observe_turn_skip(observed_cards);
+    }
+
+    fn choose_swap_target(&mut self, view: &GameView, candidates: &[usize]) -> usize {
+        self.player.choose_swap_target(view, candidates)
+    }
+
+    fn choose_second_color(&mut self, view: &GameView, first: CardColor) -> Option<CardColor> {
+        self.player.choose_second_color(view, first)
+    }
+
+    fn observe_event(&mut self, event: &GameEvent, view: &GameView) {
+        if let Err(leak) = check_event(event, view) {
+            self.leaks.push(leak);
+        }
+
+        self.player.observe_event(event, view);
+    }
+
+    // Always on, so every event the game would send anyone is checked.
+    fn wants_events(&self) -> bool {
+        true
+    }
+}
+
+/// The view of `seat` in the game `snapshot` holds, with its fingerprint cleared.
+fn view_of(snapshot: &Snapshot, seat: Option<usize>) -> GameView {
+    let mut names = snapshot.players.iter().map(|name| Blank(name.clone())).collect::<Vec<Blank>>();
+
+    // A snapshot with its cards moved around no longer hashes to the fingerprint it was taken
+    // with, so it's given the one it does hash to.
+    let fingerprint = match GameState::from_snapshot(names.iter_mut().map(|name| name as &mut dyn Player).collect(), snapshot) {
+        Err(SnapshotError::Fingerprint { actual, .. }) => actual,
+        _ => snapshot.fingerprint,
+    };
+
+    let snapshot = Snapshot { fingerprint, ..snapshot.clone() };
+    let game = GameState::from_snapshot(names.iter_mut().map(|name| name as &mut dyn Player).collect(), &snapshot);
+
+    let mut view = game.expect("an audited snapshot restores").view(seat);
+    view.fingerprint = 0;
+    view
+}
+
+/// A seat that's only there to be looked at.
+struct Blank(String);
+
+impl Player for Blank {
+    fn name(&self) -> &str {
+        &self.0
+    }
+
+    fn execute_turn(&mut self, _turn: &Turn) -> TurnResult {
+        TurnResult::Drew
+    }
+
+    fn observe_turn(&self, _other: &dyn Player, _card: &Card) {}
+
+    fn observe_turn_skip(&self, _observed_cards: Option<&[Card]>) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    #[cfg(feature = "bots")]
+    use rand::SeedableRng;
+    #[cfg(feature = "bots")]
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    #[cfg(feature = "bots")]
+    use crate::player::{AIDifficulty, Ai};
+    #[cfg(feature = "bots")]
+    use crate::rules::Rules;
+
+    #[test]
+    #[cfg(feature = "bots")]
+    fn bot_games_leak_nothing() {
+        for seed in 0..20 {
+            let mut rngs = (0..4).map(|bot| ChaCha8Rng::seed_from_u64(seed * 4 + bot)).collect::<Vec<ChaCha8Rng>>();
+            let mut bots = rngs.iter_mut().map(|rng| Ai::new(rng, AIDifficulty::Hard)).collect::<Vec<Ai<ChaCha8Rng>>>();
+            let mut audited = bots.iter_mut().map(|bot| Audited::new(bot)).collect::<Vec<Audited>>();
+
+            let mut game = GameState::with_seed(audited.iter_mut().map(|seat| seat as &mut dyn Player).collect(), seed);
+            game.set_rules(if seed % 2 == 0 { Rules::official() } else { Rules::party() });
+
+            for _ in 0..300 {
+                if game.play_turn().is_some() {
+                    break;
+                }
+
+                assert_eq!(check_views(&game.snapshot()), Ok(()), "seed {seed}");
+            }
+
+            drop(game);
+
+            for seat in &audited {
+                assert_eq!(seat.leaks(), &[][..], "seed {seed}");
+            }
+        }
+    }
+
+    #[test]
+    fn finds_a_hand_shown_to_a_seat() {
+        let view = GameView {
+            seat: Some(0),
+            hand: vec![],
+            top_card: None,
+            active_color: None,
+            second_color: None,
+            players: vec![],
+            current_player: 0,
+            direction: crate::game::Direction::Clockwise,
+            to_draw: 0,
+            draw_chain: vec![],
+            draw_pile: None,
+            discard_pile: None,
+            fingerprint: 0,
+            at: None,
+        };
+        let event = GameEvent::DebugHands { hands: vec![] };
+
+        assert_eq!(check_event(&event, &view), Err(Leak::Event { seat: Some(0), event: Box::new(event.clone()) }));
+        assert_eq!(check_event(&event, &GameView { seat: None, ..view }), Ok(()));
+    }
+}
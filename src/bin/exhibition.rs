@@ -0,0 +1,298 @@
+//! Computer players against each other, to show the game off and to exercise the library end to
+//! end. Every game is seeded, and its replay is checked once it's over.
+//!
+//! ```text
+//! uno-exhibition [easy|medium|hard ...] [--games N] [--delay MS] [--seed N] [--max-turns N]
+//!                [--rules official|classic-stacking|party|tournament|attack] [--quiet] [--reveal] [--report PATH] [--plain|--unicode|--accessible]
+//! ```
+//!
+//! A report ending in `.json` gets every game and the totals in one document. Any other path
+//! gets a CSV of every game, with the totals next to it in a `.summary.csv`.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use uno_cli::console::Console;
+use uno_cli::game::GameState;
+use uno_cli::locale::Message;
+use uno_cli::player::{AIDifficulty, Ai, Player};
+use uno_cli::render::{RenderConfig, RenderMode};
+use uno_cli::rules::Rules;
+use uno_cli::sim::{SimulationReport, SimulationSummary};
+
+const USAGE: &str = "usage: uno-exhibition [easy|medium|hard ...] [--games N] [--delay MS] [--seed N] [--max-turns N] [--rules PRESET] [--quiet] [--reveal] [--report PATH] [--plain|--unicode|--accessible]";
+
+struct Options {
+    /// One per seat, in seating order.
+    bots: Vec<AIDifficulty>,
+    games: u32,
+    /// Defaults to a pause a person can follow, or none at all when nothing is being shown.
+    delay: Option<Duration>,
+    seed: Option<u64>,
+    max_turns: u32,
+    rules: Rules,
+    quiet: bool,
+    /// Shows every hand after each turn, to follow what the bots are thinking.
+    reveal: bool,
+    report: Option<PathBuf>,
+    render: RenderConfig,
+}
+
+impl Options {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Options, String> {
+        let mut options = Options {
+            bots: Vec::new(),
+            games: 5,
+            delay: None,
+            seed: None,
+            max_turns: 2000,
+            rules: Rules::default(),
+            quiet: false,
+            reveal: false,
+            report: None,
+            render: RenderConfig::default(),
+        };
+
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--games" => options.games = value(&arg, args.next())?,
+                "--delay" => options.delay = Some(Duration::from_millis(value(&arg, args.next())?)),
+                "--seed" => options.seed = Some(value(&arg, args.next())?),
+                "--max-turns" => options.max_turns = value(&arg, args.next())?,
+                "--rules" => {
+                    let name = args.next().ok_or("`--rules` needs a preset")?;
+                    options.rules = Rules::preset(&name).ok_or_else(|| format!("there's no `{name}` preset"))?;
+                }
+                "--quiet" => options.quiet = true,
+                "--reveal" => options.reveal = true,
+                "--report" => options.report = Some(args.next().ok_or("`--report` needs a path")?.into()),
+                "--plain" => options.render = RenderConfig::new(RenderMode::Plain),
+                "--unicode" => options.render = RenderConfig::new(RenderMode::Unicode),
+                "--accessible" => options.render = RenderConfig::new(RenderMode::Accessible),
+                "e" | "easy" => options.bots.push(AIDifficulty::Easy),
+                "m" | "medium" => options.bots.push(AIDifficulty::Medium),
+                "h" | "hard" => options.bots.push(AIDifficulty::Hard),
+                _ => return Err(format!("unknown argument `{arg}`")),
+            }
+        }
+
+        if options.bots.is_empty() {
+            options.bots = vec![AIDifficulty::Easy, AIDifficulty::Medium, AIDifficulty::Hard, AIDifficulty::Hard];
+        }
+
+        if !(2..=6).contains(&options.bots.len()) {
+            return Err(format!("between 2 and 6 bots can play, not {}", options.bots.len()));
+        }
+
+        Ok(options)
+    }
+}
+
+fn value<T: std::str::FromStr>(flag: &str, value: Option<String>) -> Result<T, String> {
+    value
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| format!("`{flag}` needs a number"))
+}
+
+fn main() -> ExitCode {
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{error}\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    enable_ansi_support::enable_ansi_support().unwrap();
+
+    let render = &options.render;
+    let locale = &render.locale;
+    let delay = options.delay.unwrap_or(if options.quiet { Duration::ZERO } else { Duration::from_millis(400) });
+
+    // One seed decides the bots and every game, so a run can be repeated exactly.
+    let mut seeds = StdRng::seed_from_u64(options.seed.unwrap_or_else(rand::random));
+
+    let mut rngs = options.bots
+        .iter()
+        .map(|_| StdRng::seed_from_u64(seeds.gen()))
+        .collect::<Vec<StdRng>>();
+    let mut bots = rngs
+        .iter_mut()
+        .zip(&options.bots)
+        .map(|(rng, &difficulty)| Ai::new(rng, difficulty))
+        .collect::<Vec<Ai<StdRng>>>();
+
+    let console = &mut Console::new(render.clone());
+    let mut report = SimulationReport::new(bots.iter().map(|bot| bot.name().to_string()).collect());
+    let mut cards_left = vec![0; bots.len()];
+    let mut diverged = false;
+
+    for number in 1..=options.games {
+        if !options.quiet {
+            println!("\n{}\n", locale.format(Message::ExhibitionGame, &[("number", &number), ("count", &options.games)]));
+        }
+
+        let (outcome, replay) = {
+            let players = bots.iter_mut().map(|bot| bot as &mut dyn Player).collect();
+            let seed = seeds.gen();
+            let mut game = GameState::with_seed(players, seed);
+            game.set_rules(options.rules);
+            game.set_reveal_hands(options.reveal);
+
+            if !options.quiet {
+                game.add_spectator(console);
+            }
+
+            let mut played = 0;
+
+            let outcome = loop {
+                if let Some(outcome) = game.play_turn() {
+                    break outcome;
+                }
+
+                played += 1;
+
+                if played >= options.max_turns {
+                    break game.abort(locale.format(Message::ExhibitionStopped, &[("turns", &played)]));
+                }
+
+                std::thread::sleep(delay);
+            };
+
+            report.push(seed, &outcome);
+            (outcome, game.replay())
+        };
+
+        if let Some(Err(divergence)) = replay.map(|replay| replay.verify()) {
+            eprintln!("{}", locale.format(Message::ExhibitionDiverged, &[("number", &number), ("divergence", &format!("{divergence:?}"))]));
+            diverged = true;
+        }
+
+        for (cards, player) in cards_left.iter_mut().zip(outcome.players()) {
+            *cards += player.hand.len();
+        }
+
+        if let Some(summary) = outcome.won().filter(|_| !options.quiet) {
+            println!("\n{}", summary.table(render));
+        }
+    }
+
+    let summary = report.summary();
+
+    println!("\n{}", table(&options, &summary, &cards_left));
+    println!("{}", locale.format(Message::ExhibitionResult, &[
+        ("games", &summary.games),
+        ("turns", &format!("{:.1}", summary.average_turns)),
+    ]));
+
+    if summary.unfinished > 0 {
+        println!("{}", locale.format(Message::ExhibitionUnfinished, &[("count", &summary.unfinished)]));
+    }
+
+    if let Some(path) = &options.report {
+        if let Err(error) = write_report(&report, path) {
+            eprintln!("{}", locale.format(Message::ReportError, &[("error", &error)]));
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if diverged {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+#[cfg(feature = "report")]
+fn write_report(report: &SimulationReport, path: &Path) -> io::Result<()> {
+    use std::fs::File;
+    use std::io::{BufWriter, Write};
+
+    let mut games = BufWriter::new(File::create(path)?);
+
+    if path.extension().is_some_and(|extension| extension == "json") {
+        report.write_json(&mut games)?;
+        return games.flush();
+    }
+
+    report.write_games_csv(&mut games)?;
+    games.flush()?;
+
+    let mut summary = BufWriter::new(File::create(path.with_extension("summary.csv"))?);
+    report.write_summary_csv(&mut summary)?;
+    summary.flush()
+}
+
+#[cfg(not(feature = "report"))]
+fn write_report(_report: &SimulationReport, _path: &Path) -> io::Result<()> {
+    Err(io::Error::other("this build doesn't include report writing (the `report` feature)"))
+}
+
+/// One row per seat, with how each bot did over the whole run.
+fn table(options: &Options, summary: &SimulationSummary, cards_left: &[usize]) -> String {
+    let locale = &options.render.locale;
+    let games = options.games.max(1);
+
+    let headers = [
+        locale.get(Message::StatsSeat),
+        locale.get(Message::SummaryPlayer),
+        locale.get(Message::StatsDifficulty),
+        locale.get(Message::StatsWins),
+        locale.get(Message::StatsWinRate),
+        locale.get(Message::SummaryPoints),
+        locale.get(Message::StatsAverageCards),
+    ];
+
+    let rows = summary.seats
+        .iter()
+        .zip(&options.bots)
+        .zip(cards_left)
+        .enumerate()
+        .map(|(seat, ((totals, difficulty), &cards))| [
+            (seat + 1).to_string(),
+            totals.name.clone(),
+            format!("{difficulty:?}"),
+            totals.wins.to_string(),
+            format!("{:.0}%", totals.win_rate * 100.0),
+            totals.points.to_string(),
+            format!("{:.1}", cards as f64 / f64::from(games)),
+        ])
+        .collect::<Vec<[String; 7]>>();
+
+    let widths = (0..headers.len())
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .chain([headers[column].chars().count()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<usize>>();
+
+    let line = |cells: &[&str]| {
+        cells.iter()
+            .zip(&widths)
+            .enumerate()
+            // Names and difficulties read best left-aligned, numbers right-aligned.
+            .map(|(column, (cell, &width))| if matches!(column, 1 | 2) { format!("{cell:<width$}") } else { format!("{cell:>width$}") })
+            .collect::<Vec<String>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+            + "\n"
+    };
+
+    let mut table = line(&headers);
+
+    for row in &rows {
+        table += &line(&row.each_ref().map(String::as_str));
+    }
+
+    table
+}
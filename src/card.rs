@@ -1,11 +1,14 @@
-use std::fmt;
-use std::fmt::{Display, Formatter};
-use std::str::FromStr;
+use alloc::{format, string::String, vec, vec::Vec};
+use core::fmt;
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
 
 use rand::prelude::SliceRandom;
 use rand::Rng;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum CardValue {
     Zero,
     One,
@@ -20,6 +23,8 @@ pub enum CardValue {
 }
 
 #[derive(Debug, Copy, Clone, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Card {
     Numeric { color: CardColor, value: CardValue },
     Skip { color: CardColor },
@@ -27,9 +32,23 @@ pub enum Card {
     DrawTwo { color: CardColor },
     Wild { color: Option<CardColor> },
     DrawFour { color: Option<CardColor> },
+    /// A wild from newer editions: whoever plays it picks a color and goes again straight
+    /// away. Only in the deck when the rules add it, through
+    /// [`Rules::skip_everyone_cards`](crate::rules::Rules::skip_everyone_cards).
+    WildSkipEveryone { color: Option<CardColor> },
+    /// From UNO Attack: whoever plays it swaps hands with the player holding the fewest cards.
+    /// Only in the deck when [`Rules::trade_hands_cards`](crate::rules::Rules::trade_hands_cards)
+    /// adds it.
+    TradeHands { color: CardColor },
+    /// From UNO Attack: whoever plays it discards every other card of its color along with it.
+    /// Only in the deck when [`Rules::discard_all_cards`](crate::rules::Rules::discard_all_cards)
+    /// adds it.
+    DiscardAll { color: CardColor },
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum CardColor {
     Red,
     Green,
@@ -37,13 +56,24 @@ pub enum CardColor {
     Yellow,
 }
 
+/// A shorter name for [`CardColor`].
+pub type Color = CardColor;
+
 pub struct Deck {
     pub(crate) cards: Vec<Card>,
+    /// How many cards on top were put there with [`place_on_top`](Deck::place_on_top). Shuffles
+    /// leave them where they are.
+    placed: usize,
 }
 
 impl Deck {
     pub fn generate() -> Self {
-        Deck { cards: Deck::new_deck() }
+        Deck::of(Deck::new_deck())
+    }
+
+    /// A deck of `cards`, with the next card to be drawn last.
+    pub(crate) fn of(cards: Vec<Card>) -> Deck {
+        Deck { cards, placed: 0 }
     }
 
     pub fn new_deck() -> Vec<Card> {
@@ -87,11 +117,13 @@ impl Deck {
         deck
     }
 
-    pub fn shuffle(&mut self) {
-        self.cards.shuffle(&mut rand::thread_rng());
+    pub fn shuffle<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let unplaced = self.cards.len() - self.placed;
+        self.cards[..unplaced].shuffle(rng);
     }
 
     pub fn draw(&mut self) -> Option<Card> {
+        self.placed = self.placed.saturating_sub(1);
         self.cards.pop()
     }
 
@@ -107,12 +139,121 @@ impl Deck {
         cards
     }
 
-    pub fn reinsert_random(&mut self, card: Card) {
-        let mut rng = rand::thread_rng();
-        let index = rng.gen_range(0..self.cards.len());
+    /// Draws up to `count` cards into `cards`, replacing what was there, to reuse its allocation.
+    pub fn draw_into(&mut self, count: u8, cards: &mut Vec<Card>) {
+        cards.clear();
+        let start = self.cards.len().saturating_sub(count as usize);
+        cards.extend(self.cards.drain(start..).rev());
+        self.placed = self.placed.min(self.cards.len());
+    }
+
+    pub fn reinsert_random<R: Rng + ?Sized>(&mut self, card: Card, rng: &mut R) {
+        let index = rng.gen_range(0..(self.cards.len() - self.placed).max(1));
 
         self.cards.insert(index, card);
     }
+
+    /// Adds `cards` to the deck under any that were placed on top, ready to be shuffled in.
+    pub(crate) fn put_back(&mut self, cards: impl IntoIterator<Item = Card>) {
+        let unplaced = self.cards.len() - self.placed;
+        self.cards.splice(unplaced..unplaced, cards);
+    }
+
+    /// The next `count` cards to be drawn, in the order they'll come off.
+    #[cfg(feature = "scenario")]
+    pub fn peek(&self, count: usize) -> impl Iterator<Item = &Card> {
+        self.cards.iter().rev().take(count)
+    }
+
+    /// Puts `card` on top, to be drawn next. Cards placed this way stay on top when the deck is
+    /// shuffled, including when the discard pile is shuffled back in: the game does that before
+    /// nearly every turn, so this is the only way to know what's coming.
+    #[cfg(feature = "scenario")]
+    pub fn place_on_top(&mut self, card: Card) {
+        self.cards.push(card);
+        self.placed += 1;
+    }
+
+    /// Takes one copy of `card` out of the deck, wherever it is. Returns whether there was one.
+    #[cfg(feature = "scenario")]
+    pub fn remove(&mut self, card: &Card) -> bool {
+        let Some(index) = self.cards.iter().rposition(|held| held == card) else {
+            return false;
+        };
+
+        if index >= self.cards.len() - self.placed {
+            self.placed -= 1;
+        }
+
+        self.cards.remove(index);
+        true
+    }
+
+    #[cfg(feature = "scenario")]
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    #[cfg(feature = "scenario")]
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+}
+
+impl CardColor {
+    /// Every color, in the order the deck is built in.
+    pub const ALL: [CardColor; 4] = [CardColor::Red, CardColor::Green, CardColor::Blue, CardColor::Yellow];
+}
+
+/// Some of the four colors, such as the ones a card can be played in after a wild declared as
+/// two under [`Rules::two_color_wilds`](crate::rules::Rules::two_color_wilds).
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+pub struct ColorSet(u8);
+
+impl ColorSet {
+    pub const EMPTY: ColorSet = ColorSet(0);
+
+    pub fn with(self, color: CardColor) -> ColorSet {
+        ColorSet(self.0 | 1 << color as u8)
+    }
+
+    pub fn contains(self, color: CardColor) -> bool {
+        self.0 & 1 << color as u8 != 0
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// The colors in the set, in the order of [`CardColor::ALL`].
+    pub fn iter(self) -> impl Iterator<Item = CardColor> {
+        CardColor::ALL.into_iter().filter(move |color| self.contains(*color))
+    }
+}
+
+impl FromIterator<CardColor> for ColorSet {
+    fn from_iter<I: IntoIterator<Item = CardColor>>(colors: I) -> ColorSet {
+        colors.into_iter().fold(ColorSet::EMPTY, ColorSet::with)
+    }
+}
+
+/// The colors joined with "or", as in `Red or Blue`.
+impl Display for ColorSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (index, color) in self.iter().enumerate() {
+            match index {
+                0 => write!(f, "{color}")?,
+                _ => write!(f, " or {color}")?,
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl Display for CardColor {
@@ -140,6 +281,23 @@ impl FromStr for CardColor {
     }
 }
 
+impl CardValue {
+    pub fn number(&self) -> u8 {
+        match self {
+            CardValue::Zero => 0,
+            CardValue::One => 1,
+            CardValue::Two => 2,
+            CardValue::Three => 3,
+            CardValue::Four => 4,
+            CardValue::Five => 5,
+            CardValue::Six => 6,
+            CardValue::Seven => 7,
+            CardValue::Eight => 8,
+            CardValue::Nine => 9,
+        }
+    }
+}
+
 impl Display for CardValue {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
@@ -163,12 +321,16 @@ impl Display for Card {
         match self {
             Card::Numeric { color, value } => write!(f, "{color} {value}"),
             Card::Skip { color } => write!(f, "{color} Skip"),
+            Card::TradeHands { color } => write!(f, "{color} Trade Hands"),
+            Card::DiscardAll { color } => write!(f, "{color} Discard All"),
             Card::Reverse { color } => write!(f, "{color} Reverse"),
             Card::DrawTwo { color } => write!(f, "{color} Draw Two"),
             Card::Wild { color: Some(color)} => write!(f, "Wild Card ({color})"),
             Card::DrawFour { color: Some(color) } => write!(f, "Draw Four ({color})"),
+            Card::WildSkipEveryone { color: Some(color) } => write!(f, "Wild Skip Everyone ({color})"),
             Card::Wild { color: None } => write!(f, "Wild Card"),
             Card::DrawFour { color: None } => write!(f, "Draw Four"),
+            Card::WildSkipEveryone { color: None } => write!(f, "Wild Skip Everyone"),
         }
     }
 }
@@ -180,10 +342,13 @@ impl PartialEq for Card {
                 c1 == c2 && v1 == v2
             }
             (Card::Skip { color: c1 }, Card::Skip { color: c2 }) => c1 == c2,
+            (Card::TradeHands { color: c1 }, Card::TradeHands { color: c2 }) => c1 == c2,
+            (Card::DiscardAll { color: c1 }, Card::DiscardAll { color: c2 }) => c1 == c2,
             (Card::Reverse { color: c1 }, Card::Reverse { color: c2 }) => c1 == c2,
             (Card::DrawTwo { color: c1 }, Card::DrawTwo { color: c2 }) => c1 == c2,
             (Card::Wild { .. }, Card::Wild { .. }) => true,
             (Card::DrawFour { .. }, Card::DrawFour { .. }) => true,
+            (Card::WildSkipEveryone { .. }, Card::WildSkipEveryone { .. }) => true,
             _ => false,
         }
     }
@@ -194,25 +359,46 @@ impl Card {
         match &self {
             Card::Numeric { color, .. } => Some(*color),
             Card::Skip { color } => Some(*color),
+            Card::TradeHands { color } => Some(*color),
+            Card::DiscardAll { color } => Some(*color),
             Card::Reverse { color } => Some(*color),
             Card::DrawTwo { color } => Some(*color),
             Card::Wild { color } => *color,
             Card::DrawFour { color } => *color,
+            Card::WildSkipEveryone { color } => *color,
         }
     }
-    
+
+    /// Whether the card is a wild, which needs a color chosen when it's played.
+    pub fn is_wild(&self) -> bool {
+        matches!(self, Card::Wild { .. } | Card::DrawFour { .. } | Card::WildSkipEveryone { .. })
+    }
+
     pub fn is_equivalent(&self, other: &Card) -> bool {
         match (self, other) {
             (Card::Numeric { value: v1, .. }, Card::Numeric { value: v2, .. }) => v1 == v2,
             (Card::Skip { .. }, Card::Skip { .. }) => true,
+            (Card::TradeHands { .. }, Card::TradeHands { .. }) => true,
+            (Card::DiscardAll { .. }, Card::DiscardAll { .. }) => true,
             (Card::Reverse { .. }, Card::Reverse { .. }) => true,
             (Card::DrawTwo { .. }, Card::DrawTwo { .. }) => true,
             (Card::Wild { .. }, Card::Wild { .. }) => true,
             (Card::DrawFour { .. }, Card::DrawFour { .. }) => true,
+            (Card::WildSkipEveryone { .. }, Card::WildSkipEveryone { .. }) => true,
             _ => false,
         }
     }
 
+    /// What the card is worth to the winner when it's left in someone's hand.
+    pub fn points(&self) -> u32 {
+        match self {
+            Card::Numeric { value, .. } => value.number() as u32,
+            Card::Skip { .. } | Card::Reverse { .. } | Card::DrawTwo { .. } => 20,
+            Card::TradeHands { .. } | Card::DiscardAll { .. } => 20,
+            Card::Wild { .. } | Card::DrawFour { .. } | Card::WildSkipEveryone { .. } => 50,
+        }
+    }
+
     pub fn with_color(&mut self, color: CardColor) -> Option<&Card> {
         match self {
             Card::Wild { .. } => {
@@ -223,6 +409,10 @@ impl Card {
                 *self = Card::DrawFour { color: Some(color) };
                 Some(self)
             }
+            Card::WildSkipEveryone { .. } => {
+                *self = Card::WildSkipEveryone { color: Some(color) };
+                Some(self)
+            }
             _ => None,
         }
     }
@@ -241,13 +431,82 @@ impl Card {
                 },
             ) => lc == rc || lvalue == rv,
             (Skip { color: _ }, Skip { color: _ }) => true,
+            (TradeHands { color: _ }, TradeHands { color: _ }) => true,
+            (DiscardAll { color: _ }, DiscardAll { color: _ }) => true,
             (Reverse { color: _ }, Reverse { color: _ }) => true,
             (DrawTwo { color: _ }, DrawTwo { color: _ }) => true,
             (Wild { color: _ }, _) => true,
             (DrawFour { color: _ }, _) => true,
+            (WildSkipEveryone { color: _ }, _) => true,
             (_, _) => {
                 self.color() == rhs.color()
             }
         }
     }
 }
+
+/// What a whole hand is worth under the standard point values; see [`Card::points`].
+pub fn hand_value(cards: &[Card]) -> u32 {
+    cards.iter().map(Card::points).sum()
+}
+
+/// What each kind of card scores when it's left in a hand, for house rules that change the
+/// standard values. Set it through [`Rules::points`](crate::rules::Rules::points).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PointValues {
+    /// Indexed by face value.
+    pub numbers: [u32; 10],
+    pub skip: u32,
+    pub reverse: u32,
+    pub draw_two: u32,
+    /// Also what a Wild Skip Everyone scores.
+    pub wild: u32,
+    pub draw_four: u32,
+    /// Trade Hands and Discard All. Values saved before there were any have them at 20.
+    #[cfg_attr(feature = "serde", serde(default = "standard_attack"))]
+    pub attack: u32,
+}
+
+#[cfg(feature = "serde")]
+fn standard_attack() -> u32 {
+    PointValues::standard().attack
+}
+
+impl PointValues {
+    /// Face value for numbers, 20 for the other colored cards and 50 for wilds.
+    pub fn standard() -> PointValues {
+        PointValues {
+            numbers: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+            skip: 20,
+            attack: 20,
+            reverse: 20,
+            draw_two: 20,
+            wild: 50,
+            draw_four: 50,
+        }
+    }
+
+    pub fn of(&self, card: &Card) -> u32 {
+        match card {
+            Card::Numeric { value, .. } => self.numbers[value.number() as usize],
+            Card::Skip { .. } => self.skip,
+            Card::TradeHands { .. } | Card::DiscardAll { .. } => self.attack,
+            Card::Reverse { .. } => self.reverse,
+            Card::DrawTwo { .. } => self.draw_two,
+            Card::Wild { .. } => self.wild,
+            Card::DrawFour { .. } => self.draw_four,
+            Card::WildSkipEveryone { .. } => self.wild,
+        }
+    }
+
+    pub fn hand_value(&self, cards: &[Card]) -> u32 {
+        cards.iter().map(|card| self.of(card)).sum()
+    }
+}
+
+impl Default for PointValues {
+    fn default() -> Self {
+        PointValues::standard()
+    }
+}
@@ -0,0 +1,63 @@
+//! Where the times on a game's events come from. A game only keeps time when it's given a
+//! [`Clock`], and the time never changes how it plays, so a headless game plays out the same
+//! however fast it runs.
+
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+/// Read by the game once for each event, to stamp it with
+/// [`GameView::at`](crate::game::GameView::at). Set one with
+/// [`GameState::set_clock`](crate::game::GameState::set_clock).
+pub trait Clock: Send {
+    /// How long the clock has been running. It must never go backwards.
+    fn now(&mut self) -> Duration;
+}
+
+/// The time since the clock was made, from the OS's monotonic clock.
+#[cfg(feature = "std")]
+#[derive(Debug, Copy, Clone)]
+pub struct Monotonic(Instant);
+
+#[cfg(feature = "std")]
+impl Monotonic {
+    pub fn new() -> Monotonic {
+        Monotonic(Instant::now())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for Monotonic {
+    fn default() -> Self {
+        Monotonic::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Clock for Monotonic {
+    fn now(&mut self) -> Duration {
+        self.0.elapsed()
+    }
+}
+
+/// Moves on by the same step every time it's read, starting from zero, for tests that need the
+/// same times on every run.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Stepped {
+    pub step: Duration,
+    now: Duration,
+}
+
+impl Stepped {
+    pub fn new(step: Duration) -> Stepped {
+        Stepped { step, now: Duration::ZERO }
+    }
+}
+
+impl Clock for Stepped {
+    fn now(&mut self) -> Duration {
+        let now = self.now;
+        self.now += self.step;
+        now
+    }
+}
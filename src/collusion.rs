@@ -0,0 +1,323 @@
+//! Looks through an [`Archive`] for pairs of players who seem to be helping each other, for
+//! operators of servers where friends can end up at the same table.
+//!
+//! Two patterns are checked, each as a one-sided binomial test of what a player did for a
+//! partner against what they'd have done by chance:
+//!
+//! - [`Pattern::SparedFromDraws`]: a player who plays far fewer draw cards when the partner is
+//!   next than when anyone else is.
+//! - [`Pattern::WildsForPartner`]: a partner who can play on a player's wilds far more often than
+//!   anyone else can play on a wild, as if the color were picked for them.
+//!
+//! Neither proves anything, and with enough pairs some will be flagged by chance alone, so the
+//! report is a list of games worth a person's look rather than a verdict.
+
+use std::collections::HashMap;
+
+use rusqlite::Result;
+
+use crate::archive::{Archive, ArchivedGame, ArchivedTurn};
+
+/// How unlikely a pattern has to be, and how much evidence it needs, to be reported.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Thresholds {
+    /// The highest chance, from 0 to 1, of seeing the pattern without collusion.
+    pub significance: f64,
+    /// Patterns expected to turn up fewer times than this by chance aren't tested, since too
+    /// few chances say nothing either way.
+    pub min_expected: f64,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Thresholds { significance: 0.001, min_expected: 5.0 }
+    }
+}
+
+/// What a flagged pair did.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Pattern {
+    /// `player`'s Draw Twos and Draw Fours hit `partner` less often than chance has it.
+    SparedFromDraws,
+    /// `partner` could play on `player`'s wilds more often than chance has it.
+    WildsForPartner,
+}
+
+/// One pair whose play came out too unlikely.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suspicion {
+    pub player: String,
+    pub partner: String,
+    pub pattern: Pattern,
+    /// How many times it happened.
+    pub observed: u32,
+    /// How many times it would have happened by chance.
+    pub expected: f64,
+    /// The chance of it coming out at least this far from `expected` without collusion.
+    pub chance: f64,
+    /// The games the pair shared, for looking through by hand.
+    pub games: Vec<i64>,
+}
+
+/// Everything [`audit`] found.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CollusionReport {
+    /// How many games were looked at.
+    pub games: usize,
+    /// The least likely first.
+    pub suspicions: Vec<Suspicion>,
+}
+
+/// Hands are compared in sizes up to this, with anything bigger counted as this big.
+const MOST_CARDS: usize = 12;
+
+/// A turn that went to the seat that was next, with who followed it.
+struct Follow<'t> {
+    game: i64,
+    player: &'t str,
+    next: &'t str,
+    /// How many cards each of them held when their turn started.
+    cards: usize,
+    next_cards: usize,
+    /// Whether the player played a draw card.
+    drew: bool,
+    /// Whether the player finished on a wild, and whether the next player could play on it.
+    wild: bool,
+    answered: bool,
+}
+
+/// What a pattern came to for one pair.
+#[derive(Debug, Clone, Default)]
+struct Tally {
+    tries: u32,
+    observed: u32,
+    expected: f64,
+}
+
+/// A rate for each hand size, from how often something happened at each.
+#[derive(Debug, Clone, Default)]
+struct Rates([(u32, u32); MOST_CARDS + 1]);
+
+impl Rates {
+    fn count(&mut self, cards: usize, happened: bool) {
+        let (tries, times) = &mut self.0[cards.min(MOST_CARDS)];
+        *tries += 1;
+        *times += happened as u32;
+    }
+
+    fn at(&self, cards: usize) -> f64 {
+        match self.0[cards.min(MOST_CARDS)] {
+            (0, _) => 0.0,
+            (tries, times) => times as f64 / tries as f64,
+        }
+    }
+}
+
+/// Checks every game in `archive`.
+///
+/// Players hold more cards early in a game than late, and play changes with it, so each
+/// pattern is held against what happens at the same hand sizes: a player's draws against how
+/// often they play one with that many cards, whoever's next, and a partner's answers to wilds
+/// against how often anyone with that many cards can play on one.
+pub fn audit(archive: &Archive, thresholds: Thresholds) -> Result<CollusionReport> {
+    let ids = archive.game_ids()?;
+    let mut games = vec![];
+
+    for id in &ids {
+        let Some(game) = archive.game(*id)? else {
+            continue;
+        };
+
+        games.push((game, archive.turns(*id)?));
+    }
+
+    let follows = games.iter().flat_map(|(game, turns)| follows(game, turns)).collect::<Vec<Follow>>();
+
+    let mut draws = HashMap::<&str, Rates>::new();
+    let mut answers = Rates::default();
+
+    for follow in &follows {
+        draws.entry(follow.player).or_default().count(follow.cards, follow.drew);
+
+        if follow.wild {
+            answers.count(follow.next_cards, follow.answered);
+        }
+    }
+
+    let mut pairs = HashMap::<(&str, &str, Pattern), (Tally, Vec<i64>)>::new();
+
+    for follow in &follows {
+        let mut tally = |pattern, happened: bool, chance: f64| {
+            let (tally, games) = pairs.entry((follow.player, follow.next, pattern)).or_default();
+            tally.tries += 1;
+            tally.observed += happened as u32;
+            tally.expected += chance;
+
+            if games.last() != Some(&follow.game) {
+                games.push(follow.game);
+            }
+        };
+
+        tally(Pattern::SparedFromDraws, follow.drew, draws[follow.player].at(follow.cards));
+
+        if follow.wild {
+            tally(Pattern::WildsForPartner, follow.answered, answers.at(follow.next_cards));
+        }
+    }
+
+    let mut suspicions = vec![];
+
+    for ((player, partner, pattern), (tally, games)) in pairs {
+        if tally.expected < thresholds.min_expected {
+            continue;
+        }
+
+        let rate = tally.expected / tally.tries as f64;
+        let chance = match pattern {
+            Pattern::SparedFromDraws => binomial_at_most(tally.tries, rate, tally.observed),
+            Pattern::WildsForPartner => binomial_at_least(tally.tries, rate, tally.observed),
+        };
+
+        if chance <= thresholds.significance {
+            suspicions.push(Suspicion {
+                player: player.to_string(),
+                partner: partner.to_string(),
+                pattern,
+                observed: tally.observed,
+                expected: tally.expected,
+                chance,
+                games,
+            });
+        }
+    }
+
+    suspicions.sort_by(|a, b| a.chance.total_cmp(&b.chance));
+    Ok(CollusionReport { games: games.len(), suspicions })
+}
+
+/// Every turn of `game` that was followed by the seat that was next when it started.
+fn follows<'t>(game: &'t ArchivedGame, turns: &'t [ArchivedTurn]) -> Vec<Follow<'t>> {
+    let taken = taken(game, turns);
+    let mut follows = vec![];
+
+    for index in 0..taken.len().saturating_sub(1) {
+        let (turn, next) = (&taken[index], &taken[index + 1]);
+        let facing = index.checked_sub(1).is_some_and(|before| cards_played(&taken[before].actions).iter().any(|card| is_draw(card)));
+        let played = cards_played(&turn.actions);
+
+        // Whoever follows a skip or a reverse isn't who was next, and wouldn't have been hit
+        // by a draw. A draw stacked on one played at the player only goes on to the next
+        // because it can't go back, so it says nothing about who they'd pick.
+        if turn.player == next.player || facing || played.iter().any(|card| steers(card)) {
+            continue;
+        }
+
+        follows.push(Follow {
+            game: game.id,
+            player: turn.player,
+            next: next.player,
+            cards: turn.cards,
+            next_cards: next.cards,
+            drew: played.iter().any(|card| is_draw(card)),
+            wild: played.last().is_some_and(|card| card.starts_with("Wild Card")),
+            answered: next.actions.iter().any(|action| action.action == "played"),
+        });
+    }
+
+    follows
+}
+
+/// A turn with the archived actions in it.
+struct Taken<'t> {
+    player: &'t str,
+    actions: Vec<&'t ArchivedTurn>,
+    /// How many cards the player held when it started.
+    cards: usize,
+}
+
+/// The archived actions grouped into turns. Actions that aren't anyone's turn, such as the
+/// first card being turned over, are left out.
+///
+/// Hand sizes are worked back from what everyone was left holding, so they're right however
+/// many cards were dealt, except across traded hands, where the archive doesn't say who with.
+fn taken<'t>(game: &'t ArchivedGame, turns: &'t [ArchivedTurn]) -> Vec<Taken<'t>> {
+    let mut taken: Vec<(u32, Taken)> = vec![];
+
+    for turn in turns {
+        let Some(player) = turn.player.as_deref().filter(|_| matches!(turn.action.as_str(), "played" | "drew")) else {
+            if let Some((_, last)) = taken.last_mut().filter(|(number, _)| *number == turn.turn) {
+                last.actions.push(turn);
+            }
+
+            continue;
+        };
+
+        match taken.last_mut() {
+            Some((number, last)) if *number == turn.turn => last.actions.push(turn),
+            _ => taken.push((turn.turn, Taken { player, actions: vec![turn], cards: 0 })),
+        }
+    }
+
+    let mut cards = game.players.iter().map(|player| (player.name.as_str(), player.cards_left as i64)).collect::<HashMap<&str, i64>>();
+
+    for (_, turn) in taken.iter_mut().rev() {
+        for action in turn.actions.iter().rev() {
+            let Some(held) = action.player.as_deref().and_then(|player| cards.get_mut(player)) else {
+                continue;
+            };
+
+            let count = action.count.unwrap_or_default() as i64;
+
+            match action.action.as_str() {
+                "played" | "jumped in" => *held += 1,
+                "drew" => *held -= count,
+                "discarded" => *held += count,
+                _ => {}
+            }
+        }
+
+        turn.cards = cards.get(turn.player).copied().unwrap_or_default().max(0) as usize;
+    }
+
+    taken.into_iter().map(|(_, turn)| turn).collect()
+}
+
+/// Whether an archived card, as [`Display`](std::fmt::Display) writes it, makes the next player
+/// draw.
+fn is_draw(card: &str) -> bool {
+    card.ends_with("Draw Two") || card.starts_with("Draw Four")
+}
+
+/// The cards played in a turn.
+fn cards_played<'t>(actions: &[&'t ArchivedTurn]) -> Vec<&'t str> {
+    actions.iter().filter(|turn| turn.action == "played").filter_map(|turn| turn.card.as_deref()).collect()
+}
+
+/// Whether an archived card changes who plays next.
+fn steers(card: &str) -> bool {
+    card.ends_with("Skip") || card.ends_with("Reverse") || card.starts_with("Wild Skip Everyone")
+}
+
+/// The chance of at most `k` successes in `n` tries that each succeed with chance `p`.
+fn binomial_at_most(n: u32, p: f64, k: u32) -> f64 {
+    (0..=k.min(n)).map(|i| binomial(n, p, i)).sum::<f64>().min(1.0)
+}
+
+/// The chance of at least `k` successes in `n` tries.
+fn binomial_at_least(n: u32, p: f64, k: u32) -> f64 {
+    (k..=n).map(|i| binomial(n, p, i)).sum::<f64>().min(1.0)
+}
+
+/// The chance of exactly `k` successes, worked out in logs so long runs don't overflow.
+fn binomial(n: u32, p: f64, k: u32) -> f64 {
+    if p <= 0.0 {
+        return (k == 0) as u32 as f64;
+    }
+
+    if p >= 1.0 {
+        return (k == n) as u32 as f64;
+    }
+
+    let choose = (1..=k).map(|i| ((n - k + i) as f64).ln() - (i as f64).ln()).sum::<f64>();
+    (choose + k as f64 * p.ln() + (n - k) as f64 * (1.0 - p).ln()).exp()
+}
@@ -0,0 +1,322 @@
+//! Rule conformance fixtures: scripted positions, each with what the rules say comes of them,
+//! for checking that a frontend or a fork drives the engine the way the official rules play.
+//!
+//! The fixtures are JSON files in the crate's `conformance` directory, one [`Scenario`] each,
+//! with cards written in the [notation](crate::notation). [`fixtures`] has them all, and [`run`]
+//! plays one through the engine and compares the table with what it expects. A frontend can
+//! read the same files and check that feeding the moves through it gives the same table.
+
+use std::fmt::{self, Display, Formatter};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::card::Card;
+use crate::game::{Direction, GameEvent, GameState, GameView, Spectator, Turn, TurnResult};
+use crate::notation;
+use crate::player::Player;
+use crate::rules::{Rules, TurnOrder};
+use crate::snapshot::Position;
+use crate::summary::GameOutcome;
+use crate::window::{Interjection, Window};
+
+/// The fixtures that ship with the crate, by file name.
+const FIXTURES: [(&str, &str); 5] = [
+    ("stacking_off.json", include_str!("../conformance/stacking_off.json")),
+    ("two_player_reverse.json", include_str!("../conformance/two_player_reverse.json")),
+    ("reshuffle.json", include_str!("../conformance/reshuffle.json")),
+    ("last_card_wins.json", include_str!("../conformance/last_card_wins.json")),
+    ("mismatched_card_refused.json", include_str!("../conformance/mismatched_card_refused.json")),
+];
+
+/// A position, the moves made from it, and what the table should look like afterwards.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Scenario {
+    pub name: String,
+    /// What the scenario checks, and which rule says so.
+    pub description: String,
+    pub rules: Rules,
+    /// Every seat's hand.
+    pub hands: Vec<Vec<String>>,
+    /// The draw pile, with the next card to be drawn last.
+    pub deck: Vec<String>,
+    /// The discard pile, with the top card last.
+    pub discard: Vec<String>,
+    /// The seat that plays first.
+    pub to_play: usize,
+    pub direction: Direction,
+    #[serde(default)]
+    pub to_draw: u8,
+    /// What any shuffle is seeded with.
+    #[serde(default)]
+    pub seed: u64,
+    /// Whether every card opens a [`Window`] for the rest of the table.
+    #[serde(default)]
+    pub windows: bool,
+    /// How many turns to play.
+    pub turns: u32,
+    /// What the players choose when they're asked, in order: `P` and a card to play it, or `D`
+    /// to draw. A player facing a draw they can't stack on takes it without being asked.
+    pub moves: Vec<String>,
+    /// What players do out of turn.
+    #[serde(default)]
+    pub interjections: Vec<ScriptedInterjection>,
+    pub expect: Expected,
+}
+
+/// An interjection made in answer to the window a move opened.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScriptedInterjection {
+    pub seat: usize,
+    /// The move, by its index in [`Scenario::moves`].
+    pub after: usize,
+    pub interjection: Interjection,
+}
+
+/// What the table should look like once a scenario's turns are played. Anything left out isn't
+/// checked.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Expected {
+    /// Who plays next.
+    pub to_play: Option<usize>,
+    pub hand_sizes: Option<Vec<usize>>,
+    pub top_card: Option<String>,
+    pub to_draw: Option<u8>,
+    pub direction: Option<Direction>,
+    pub deck_size: Option<usize>,
+    pub discard_size: Option<usize>,
+    /// The seat that won, when the game should be over.
+    pub winner: Option<usize>,
+    /// The move the engine should refuse, by its index in [`Scenario::moves`]. Nothing is
+    /// played past it.
+    pub refused: Option<usize>,
+    /// The interjections heard, in order.
+    pub heard: Option<Vec<ScriptedInterjection>>,
+}
+
+/// Why a scenario didn't play out as expected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mismatch {
+    /// The fixture itself is broken, such as a card that isn't in the notation.
+    Invalid(String),
+    /// The moves ran out while a player was still being asked for one.
+    ScriptEnded,
+    /// The table came out differently.
+    Differs { what: &'static str, expected: String, actual: String },
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Mismatch::Invalid(problem) => write!(f, "the scenario is invalid: {problem}"),
+            Mismatch::ScriptEnded => write!(f, "a player was asked for a move after the last one"),
+            Mismatch::Differs { what, expected, actual } => write!(f, "expected {what} to be {expected}, but it was {actual}"),
+        }
+    }
+}
+
+impl std::error::Error for Mismatch {}
+
+/// Every fixture that ships with the crate.
+pub fn fixtures() -> Vec<Scenario> {
+    FIXTURES
+        .iter()
+        .map(|(file, json)| serde_json::from_str(json).unwrap_or_else(|error| panic!("{file} doesn't parse: {error}")))
+        .collect()
+}
+
+/// Plays `scenario` through the engine and checks the table it leaves against what it expects.
+pub fn run(scenario: &Scenario) -> Result<(), Mismatch> {
+    let cards = |cards: &[String]| cards.iter().map(|card| notation::parse_card(card).map_err(Mismatch::Invalid)).collect::<Result<Vec<Card>, Mismatch>>();
+    let seats = scenario.hands.len();
+
+    let script = Mutex::new(Script {
+        moves: scenario.moves.iter().map(|action| parse_move(action)).collect::<Result<Vec<TurnResult>, Mismatch>>()?,
+        made: 0,
+        refused: None,
+        ended: false,
+        interjections: scenario.interjections.clone(),
+    });
+
+    let hands = scenario.hands.iter().map(|hand| cards(hand)).collect::<Result<Vec<Vec<Card>>, Mismatch>>()?;
+    let names = (0..seats).map(|seat| format!("Seat {seat}")).collect::<Vec<String>>();
+    let position = Position {
+        hands,
+        deck: cards(&scenario.deck)?,
+        discard: cards(&scenario.discard)?,
+        to_play: scenario.to_play,
+        direction: scenario.direction,
+        to_draw: scenario.to_draw,
+        rules: scenario.rules,
+        out: vec![],
+        seed: scenario.seed,
+    };
+
+    let mut players = names.iter().enumerate().map(|(seat, name)| ScriptedSeat { name, seat, script: &script }).collect::<Vec<ScriptedSeat>>();
+    let mut heard = Heard { script: &script, heard: vec![] };
+    let mut game = GameState::from_position(players.iter_mut().map(|seat| seat as &mut dyn Player).collect(), &position)
+        .map_err(|error| Mismatch::Invalid(error.to_string()))?;
+
+    if scenario.windows {
+        game.set_window(Some(Duration::ZERO));
+    }
+
+    game.add_spectator(&mut heard);
+
+    let mut outcome = None;
+
+    for _ in 0..scenario.turns {
+        outcome = game.play_turn();
+        let script = script.lock().unwrap();
+
+        if script.ended {
+            return Err(Mismatch::ScriptEnded);
+        }
+
+        if outcome.is_some() || script.refused.is_some() {
+            break;
+        }
+    }
+
+    let table = game.snapshot();
+    drop(game);
+    let heard = heard.heard;
+
+    let script = script.into_inner().unwrap();
+    let expect = &scenario.expect;
+    let next = TurnOrder {
+        current: table.current_player,
+        seats,
+        direction: table.direction,
+        to_draw: table.to_draw,
+        out: table.out.clone(),
+    }.next();
+    let winner = match &outcome {
+        Some(GameOutcome::Won(summary)) => summary.ranking.first().copied(),
+        _ => None,
+    };
+
+    check("the move refused", expect.refused.map(Some), script.refused)?;
+    check("the winner", expect.winner.map(Some), winner)?;
+    check("who plays next", expect.to_play, next)?;
+    check("the hand sizes", expect.hand_sizes.clone(), table.hands.iter().map(Vec::len).collect())?;
+    check("the top card", expect.top_card.as_deref().map(notation::parse_card).transpose().map_err(Mismatch::Invalid)?, *table.discard.last().unwrap())?;
+    check("the pending draw", expect.to_draw, table.to_draw)?;
+    check("the direction", expect.direction, table.direction)?;
+    check("the deck size", expect.deck_size, table.deck.len())?;
+    check("the discard pile's size", expect.discard_size, table.discard.len())?;
+    check("the interjections heard", expect.heard.clone(), heard)?;
+
+    Ok(())
+}
+
+fn check<T: PartialEq + fmt::Debug>(what: &'static str, expected: Option<T>, actual: T) -> Result<(), Mismatch> {
+    match expected {
+        Some(expected) if expected != actual => Err(Mismatch::Differs { what, expected: format!("{expected:?}"), actual: format!("{actual:?}") }),
+        _ => Ok(()),
+    }
+}
+
+fn parse_move(action: &str) -> Result<TurnResult, Mismatch> {
+    match action.trim() {
+        "D" => Ok(TurnResult::Drew),
+        action => match action.strip_prefix("P ") {
+            Some(card) => notation::parse_card(card.trim()).map(TurnResult::Played).map_err(Mismatch::Invalid),
+            None => Err(Mismatch::Invalid(format!("{action} is not a valid move"))),
+        },
+    }
+}
+
+/// The moves still to be made, shared by the seats.
+struct Script {
+    moves: Vec<TurnResult>,
+    made: usize,
+    refused: Option<usize>,
+    ended: bool,
+    interjections: Vec<ScriptedInterjection>,
+}
+
+/// Makes the scenario's moves for one seat. Once one is refused, or the moves run out, it only
+/// draws, and the runner stops at the end of the turn.
+struct ScriptedSeat<'s> {
+    name: &'s str,
+    seat: usize,
+    script: &'s Mutex<Script>,
+}
+
+impl Player for ScriptedSeat<'_> {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn execute_turn(&mut self, turn: &Turn) -> TurnResult {
+        let mut script = self.script.lock().unwrap();
+
+        if script.refused.is_some() || script.ended {
+            return TurnResult::Drew;
+        }
+
+        let Some(&result) = script.moves.get(script.made) else {
+            script.ended = true;
+            return TurnResult::Drew;
+        };
+
+        script.made += 1;
+
+        match result {
+            TurnResult::Played(card) if !turn.playable_hand.contains(&card) || card.is_wild() && card.color().is_none() => {
+                script.refused = Some(script.made - 1);
+                TurnResult::Drew
+            }
+            result => result,
+        }
+    }
+
+    fn observe_turn(&self, _other: &dyn Player, _card: &Card) {}
+
+    fn observe_turn_skip(&self, _observed_cards: Option<&[Card]>) {}
+
+    fn wants_events(&self) -> bool {
+        false
+    }
+
+    fn interject(&mut self, _window: &Window, _view: &GameView) -> Option<Interjection> {
+        let mut script = self.script.lock().unwrap();
+        let made = script.made;
+        let index = script.interjections.iter().position(|scripted| scripted.seat == self.seat && scripted.after + 1 == made)?;
+
+        Some(script.interjections.remove(index).interjection)
+    }
+}
+
+/// Collects the interjections the game hears, with the move each answered.
+struct Heard<'s> {
+    script: &'s Mutex<Script>,
+    heard: Vec<ScriptedInterjection>,
+}
+
+impl Spectator for Heard<'_> {
+    fn observe_event(&mut self, event: &GameEvent, view: &GameView) {
+        if let GameEvent::Interjected { player, interjection } = event {
+            let seat = view.seat_of(*player).unwrap_or_default();
+            let after = self.script.lock().unwrap().made.saturating_sub(1);
+            self.heard.push(ScriptedInterjection { seat, after, interjection: *interjection });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_fixture_passes() {
+        for scenario in fixtures() {
+            if let Err(mismatch) = run(&scenario) {
+                panic!("{}: {mismatch}", scenario.name);
+            }
+        }
+    }
+}
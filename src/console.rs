@@ -0,0 +1,200 @@
+use crate::card::Card;
+use crate::game::{Direction, GameEvent, GameView, Spectator};
+use crate::locale::Message;
+use crate::player::PlayerId;
+use crate::render::{RenderConfig, RenderMode};
+use crate::window::Interjection;
+
+/// Narrates the game to stdout; this is what the CLI prints between turns.
+pub struct Console {
+    render: RenderConfig,
+}
+
+impl Console {
+    pub fn new(render: RenderConfig) -> Console {
+        Console { render }
+    }
+
+    fn print_interjection(&self, player: &str, interjection: Interjection) {
+        let locale = &self.render.locale;
+
+        println!("{}", match interjection {
+            Interjection::CallOut => locale.format(Message::CalledOut, &[("player", &player)]),
+            Interjection::Challenge => locale.format(Message::Challenged, &[("player", &player)]),
+            Interjection::JumpIn(card) => locale.format(Message::JumpedIn, &[("player", &player), ("card", &card.display(&self.render))]),
+        });
+    }
+
+    fn print_hands(&self, hands: &[Vec<Card>], view: &GameView) {
+        for (player, hand) in view.players.iter().zip(hands) {
+            let cards = hand
+                .iter()
+                .map(|card| card.display(&self.render).to_string())
+                .collect::<Vec<String>>()
+                .join(", ");
+
+            println!("{}", self.render.locale.format(Message::DebugHand, &[("player", &player.name), ("cards", &cards)]));
+        }
+    }
+
+    fn print_reveal(&self, player: PlayerId, cards: &[Card], points: u32, view: &GameView) {
+        if cards.is_empty() {
+            return;
+        }
+
+        let cards = cards.iter().map(|card| card.display(&self.render).to_string()).collect::<Vec<String>>().join(", ");
+        println!("{}", self.render.locale.format(Message::Revealed, &[("player", &view.name_of(player)), ("cards", &cards), ("points", &points)]));
+    }
+
+    /// Screen-reader narration: every change is spelled out, including whose turn it is and how
+    /// many cards everyone is left holding.
+    fn narrate_verbose(&self, event: &GameEvent, view: &GameView) {
+        let locale = &self.render.locale;
+
+        let cards_of = |player: &PlayerId| view.seat_of(*player).map_or(0, |seat| view.players[seat].cards);
+
+        let direction = locale.get(match view.direction {
+            Direction::Clockwise => Message::Clockwise,
+            Direction::CounterClockwise => Message::CounterClockwise,
+        });
+
+        match event {
+            GameEvent::Started { top_card } => {
+                println!("{}", locale.format(Message::VerboseStarted, &[
+                    ("count", &view.players.len()),
+                    ("card", &top_card.display(&self.render)),
+                    ("direction", &direction),
+                ]));
+            }
+            GameEvent::TurnStarted { player } => {
+                println!("{}", locale.format(Message::VerboseTurn, &[("player", &view.name_of(*player)), ("count", &cards_of(player))]));
+            }
+            GameEvent::Played { player, card } => {
+                println!("{}", locale.format(Message::VerbosePlayed, &[
+                    ("player", &view.name_of(*player)),
+                    ("card", &card.display(&self.render)),
+                    ("count", &cards_of(player)),
+                ]));
+
+                match card {
+                    Card::Reverse { .. } => {
+                        println!("{}", locale.format(Message::VerboseReversed, &[("direction", &direction)]));
+                    }
+                    Card::WildSkipEveryone { .. } => {
+                        println!("{}", locale.format(Message::VerboseGoesAgain, &[("player", &view.name_of(*player))]));
+                    }
+                    _ => {}
+                }
+            }
+            GameEvent::Drew { player, count } => {
+                println!("{}", locale.format(Message::VerboseDrew, &[("player", &view.name_of(*player)), ("count", count), ("total", &cards_of(player))]));
+            }
+            GameEvent::Skipped { player } => {
+                println!("{}", locale.format(Message::VerboseSkipped, &[("player", &view.name_of(*player))]));
+            }
+            GameEvent::Traded { player, with } => {
+                println!("{}", locale.format(Message::VerboseTraded, &[("player", &view.name_of(*player)), ("with", &view.name_of(*with)), ("total", &cards_of(player))]));
+            }
+            GameEvent::Discarded { player, cards } => {
+                println!("{}", locale.format(Message::VerboseDiscarded, &[("player", &view.name_of(*player)), ("count", &cards.len()), ("total", &cards_of(player))]));
+            }
+            GameEvent::Spared { player, count } => {
+                println!("{}", locale.format(Message::VerboseSpared, &[("player", &view.name_of(*player)), ("count", count), ("total", &cards_of(player))]));
+            }
+            GameEvent::Eliminated { player } => {
+                println!("{}", locale.format(Message::VerboseEliminated, &[("player", &view.name_of(*player))]));
+            }
+            GameEvent::PlayerLeft { player, hand } => {
+                println!("{}", locale.format(Message::VerbosePlayerLeft, &[("player", &view.name_of(*player)), ("hand", &locale.get(Message::left_hand(*hand)))]));
+            }
+            GameEvent::Finished { player, place } => {
+                println!("{}", locale.format(Message::VerboseFinished, &[("player", &view.name_of(*player)), ("place", place)]));
+            }
+            GameEvent::Interjected { player, interjection } => self.print_interjection(&view.name_of(*player), *interjection),
+            GameEvent::Stacked { to_draw, .. } => {
+                println!("{}", locale.format(Message::VerbosePendingDraw, &[("count", to_draw)]));
+            }
+            GameEvent::Aborted { reason } => {
+                println!("{}", locale.format(Message::Aborted, &[("reason", reason)]));
+            }
+            GameEvent::Paused => println!("{}", locale.get(Message::Paused)),
+            GameEvent::Resumed => println!("{}", locale.get(Message::Resumed)),
+            // Spectators only get emotes sent to the whole table.
+            GameEvent::Emote { player, emote, .. } => {
+                println!("{}", locale.format(Message::Emote, &[("player", &view.name_of(*player)), ("emote", &locale.get(Message::emote(*emote)))]));
+            }
+            GameEvent::DebugHands { hands } => self.print_hands(hands, view),
+            GameEvent::Revealed { player, cards, points } => self.print_reveal(*player, cards, *points, view),
+            GameEvent::ColorsNamed { player, colors } => {
+                println!("{}", locale.format(Message::ColorsNamed, &[("player", &view.name_of(*player)), ("colors", colors)]));
+            }
+            // The winner is announced by the caller, which knows who "you" are.
+            // The card was just announced as played.
+            GameEvent::WindowOpened { .. } => {}
+            GameEvent::Setup { .. } | GameEvent::Won { .. } => {}
+        }
+    }
+}
+
+impl Spectator for Console {
+    fn observe_event(&mut self, event: &GameEvent, view: &GameView) {
+        if self.render.mode == RenderMode::Accessible {
+            return self.narrate_verbose(event, view);
+        }
+
+        let locale = &self.render.locale;
+
+        match event {
+            GameEvent::Started { top_card } => {
+                println!("{}", locale.format(Message::TopCard, &[("card", &top_card.display(&self.render))]));
+            }
+            GameEvent::Played { player, card } => {
+                println!("{}", locale.format(Message::Played, &[("player", &view.name_of(*player)), ("card", &card.display(&self.render))]));
+            }
+            GameEvent::Drew { player, count } => {
+                println!("{}", locale.format(Message::Drew, &[("player", &view.name_of(*player)), ("count", count)]));
+            }
+            GameEvent::Skipped { player } => {
+                println!("{}", locale.format(Message::Skipped, &[("player", &view.name_of(*player))]));
+            }
+            GameEvent::Traded { player, with } => {
+                println!("{}", locale.format(Message::Traded, &[("player", &view.name_of(*player)), ("with", &view.name_of(*with))]));
+            }
+            GameEvent::Discarded { player, cards } => {
+                println!("{}", locale.format(Message::Discarded, &[("player", &view.name_of(*player)), ("count", &cards.len())]));
+            }
+            GameEvent::Spared { player, count } => {
+                println!("{}", locale.format(Message::Spared, &[("player", &view.name_of(*player)), ("count", count)]));
+            }
+            GameEvent::Eliminated { player } => {
+                println!("{}", locale.format(Message::Eliminated, &[("player", &view.name_of(*player))]));
+            }
+            GameEvent::PlayerLeft { player, .. } => {
+                println!("{}", locale.format(Message::PlayerLeft, &[("player", &view.name_of(*player))]));
+            }
+            GameEvent::Finished { player, place } => {
+                println!("{}", locale.format(Message::Finished, &[("player", &view.name_of(*player)), ("place", place)]));
+            }
+            GameEvent::Interjected { player, interjection } => self.print_interjection(&view.name_of(*player), *interjection),
+            GameEvent::Stacked { player, to_draw } => {
+                println!("{}", locale.format(Message::Stacked, &[("player", &view.name_of(*player)), ("count", to_draw)]));
+            }
+            GameEvent::Aborted { reason } => {
+                println!("{}", locale.format(Message::Aborted, &[("reason", reason)]));
+            }
+            GameEvent::Paused => println!("{}", locale.get(Message::Paused)),
+            GameEvent::Resumed => println!("{}", locale.get(Message::Resumed)),
+            // Spectators only get emotes sent to the whole table.
+            GameEvent::Emote { player, emote, .. } => {
+                println!("{}", locale.format(Message::Emote, &[("player", &view.name_of(*player)), ("emote", &locale.get(Message::emote(*emote)))]));
+            }
+            GameEvent::DebugHands { hands } => self.print_hands(hands, view),
+            GameEvent::Revealed { player, cards, points } => self.print_reveal(*player, cards, *points, view),
+            GameEvent::ColorsNamed { player, colors } => {
+                println!("{}", locale.format(Message::ColorsNamed, &[("player", &view.name_of(*player)), ("colors", colors)]));
+            }
+            // The winner is announced by the caller, which knows who "you" are.
+            GameEvent::Setup { .. } | GameEvent::TurnStarted { .. } | GameEvent::WindowOpened { .. } | GameEvent::Won { .. } => {}
+        }
+    }
+}
@@ -0,0 +1,114 @@
+//! The daily challenge: one game a day against the computer, the same for everyone. The deal,
+//! the bots and where the player sits all come from the date, so two people who play the same
+//! moves on the same day see the same game, and can compare how they did by sharing a
+//! [`DailyResult`].
+
+use std::time::Duration;
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::game::GameState;
+use crate::locale::{Locale, Message};
+use crate::player::{AIDifficulty, Ai, Player};
+use crate::rules::Rules;
+use crate::summary::GameOutcome;
+
+/// The game for one day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DailyChallenge {
+    /// As it was given, such as `2026-10-14`. Any text will do, so long as everyone taking part
+    /// writes the day the same way.
+    pub date: String,
+    /// What the game is seeded with, and its bots after it.
+    pub seed: u64,
+    /// The computer players, in seating order with the player left out.
+    pub bots: Vec<AIDifficulty>,
+    /// Where the player sits.
+    pub seat: usize,
+    pub rules: Rules,
+}
+
+/// How a player did at a [`DailyChallenge`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailyResult {
+    pub date: String,
+    pub seed: u64,
+    pub seat: usize,
+    pub outcome: GameOutcome,
+}
+
+impl DailyChallenge {
+    /// The challenge for `date`, with surrounding whitespace ignored.
+    pub fn for_date(date: &str) -> DailyChallenge {
+        let date = date.trim();
+        // FNV-1a, so the seed for a day never changes between releases.
+        let seed = date.bytes().fold(0xcbf2_9ce4_8422_2325, |hash: u64, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3));
+
+        let mut lineup = ChaCha8Rng::seed_from_u64(seed);
+        let bots = (0..lineup.gen_range(1..=3))
+            .map(|_| [AIDifficulty::Easy, AIDifficulty::Medium, AIDifficulty::Hard][lineup.gen_range(0..3)])
+            .collect::<Vec<AIDifficulty>>();
+        let seat = lineup.gen_range(0..=bots.len());
+
+        DailyChallenge { date: date.to_string(), seed, bots, seat, rules: Rules::default() }
+    }
+
+    /// Plays the day's game with `player` in their seat, pausing `delay` between turns.
+    pub fn play(&self, player: &mut dyn Player, delay: Duration) -> DailyResult {
+        // Each bot has its own generator, so one bot's choices don't depend on another's.
+        let mut rngs = (0..self.bots.len())
+            .map(|bot| ChaCha8Rng::seed_from_u64(self.seed.wrapping_add(bot as u64 + 1)))
+            .collect::<Vec<ChaCha8Rng>>();
+        let mut bots = rngs.iter_mut().zip(&self.bots).map(|(rng, difficulty)| Ai::new(rng, *difficulty)).collect::<Vec<Ai<ChaCha8Rng>>>();
+
+        let mut players = bots.iter_mut().map(|bot| bot as &mut dyn Player).collect::<Vec<&mut dyn Player>>();
+        players.insert(self.seat, player);
+
+        let mut game = GameState::with_seed(players, self.seed);
+        game.set_rules(self.rules);
+        game.set_turn_delay(delay);
+
+        DailyResult { date: self.date.clone(), seed: self.seed, seat: self.seat, outcome: game.start() }
+    }
+}
+
+impl DailyResult {
+    /// Where the player finished, from 1, or `None` if the game was never finished.
+    pub fn place(&self) -> Option<usize> {
+        match &self.outcome {
+            GameOutcome::Won(summary) => summary.ranking.iter().position(|seat| *seat == self.seat).map(|place| place + 1),
+            GameOutcome::Aborted { .. } => None,
+        }
+    }
+
+    /// One line to post for others who played the same day.
+    pub fn share(&self, locale: &Locale) -> String {
+        let turns = self.outcome.turns();
+
+        match (&self.outcome, self.place()) {
+            (GameOutcome::Won(summary), Some(1)) => locale.format(Message::DailyWon, &[("date", &self.date), ("turns", &turns), ("points", &summary.points)]),
+            (GameOutcome::Won(summary), Some(place)) => {
+                locale.format(Message::DailyPlaced, &[("date", &self.date), ("place", &place), ("players", &summary.players.len()), ("turns", &turns)])
+            }
+            _ => locale.format(Message::DailyUnfinished, &[("date", &self.date), ("turns", &turns)]),
+        }
+    }
+
+    /// The result as a JSON document, for sharing where a line of text won't do: the date, the
+    /// seed and the player's seat, with the outcome as [`GameOutcome::to_json`] has it.
+    #[cfg(feature = "report")]
+    pub fn to_json(&self) -> String {
+        #[derive(serde::Serialize)]
+        struct Document<'a> {
+            date: &'a str,
+            seed: u64,
+            seat: usize,
+            outcome: serde_json::Value,
+        }
+
+        let outcome = serde_json::from_str(&self.outcome.to_json()).expect("the outcome's JSON is valid");
+        let document = Document { date: &self.date, seed: self.seed, seat: self.seat, outcome };
+        serde_json::to_string_pretty(&document).expect("the result serializes")
+    }
+}
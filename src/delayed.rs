@@ -0,0 +1,153 @@
+//! Holding a spectator's events back, so that someone watching a stream of the game can't
+//! pass on what they see to the players while it still matters.
+
+use alloc::collections::VecDeque;
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
+use crate::game::{GameEvent, GameView, Spectator};
+
+/// How far behind the game a [`Delayed`] spectator is kept.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SpectatorDelay {
+    /// This many turns behind: an event is passed on once this many more turns have started.
+    Turns(u32),
+    /// This long behind. Events only go out when another one arrives or
+    /// [`release`](Delayed::release) is called, so call it regularly to keep them flowing in
+    /// a quiet game.
+    #[cfg(feature = "std")]
+    Time(Duration),
+}
+
+/// Passes events on to another spectator after a [`SpectatorDelay`], each with the view it was
+/// sent with.
+pub struct Delayed<S> {
+    inner: S,
+    delay: SpectatorDelay,
+    held: VecDeque<Held>,
+    turn: u32,
+}
+
+struct Held {
+    event: GameEvent,
+    view: GameView,
+    turn: u32,
+    #[cfg(feature = "std")]
+    at: Instant,
+}
+
+impl<S: Spectator> Delayed<S> {
+    pub fn new(inner: S, delay: SpectatorDelay) -> Delayed<S> {
+        Delayed { inner, delay, held: VecDeque::new(), turn: 0 }
+    }
+
+    /// Passes on every event that's been held back long enough.
+    pub fn release(&mut self) {
+        #[cfg(feature = "std")]
+        let now = Instant::now();
+
+        while let Some(held) = self.held.front() {
+            let due = match self.delay {
+                SpectatorDelay::Turns(turns) => held.turn + turns <= self.turn,
+                #[cfg(feature = "std")]
+                SpectatorDelay::Time(delay) => now.duration_since(held.at) >= delay,
+            };
+
+            if !due {
+                break;
+            }
+
+            let held = self.held.pop_front().unwrap();
+            self.inner.observe_event(&held.event, &held.view);
+        }
+    }
+
+    /// Passes on everything still held back, for once the game is over and there's nothing
+    /// left to give away.
+    pub fn flush(&mut self) {
+        for held in self.held.drain(..) {
+            self.inner.observe_event(&held.event, &held.view);
+        }
+    }
+
+    /// How many events are waiting to go out.
+    pub fn held(&self) -> usize {
+        self.held.len()
+    }
+
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    /// Stops delaying, returning the spectator behind. Anything still held back is dropped;
+    /// [`flush`](Delayed::flush) first to keep it.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: Spectator> Spectator for Delayed<S> {
+    fn observe_event(&mut self, event: &GameEvent, view: &GameView) {
+        if let GameEvent::TurnStarted { .. } = event {
+            self.turn += 1;
+        }
+
+        self.held.push_back(Held {
+            event: event.clone(),
+            view: view.clone(),
+            turn: self.turn,
+            #[cfg(feature = "std")]
+            at: Instant::now(),
+        });
+
+        self.release();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+    use crate::history::History;
+    use crate::player::PlayerId;
+
+    fn view() -> GameView {
+        GameView {
+            seat: None,
+            hand: vec![],
+            top_card: None,
+            active_color: None,
+            second_color: None,
+            players: vec![],
+            current_player: 0,
+            direction: crate::game::Direction::Clockwise,
+            to_draw: 0,
+            draw_chain: vec![],
+            draw_pile: None,
+            discard_pile: None,
+            fingerprint: 0,
+            at: None,
+        }
+    }
+
+    #[test]
+    fn turns_behind_hold_each_turn_back() {
+        let mut delayed = Delayed::new(History::new(), SpectatorDelay::Turns(2));
+        let turn = |player| GameEvent::TurnStarted { player: PlayerId(player) };
+
+        delayed.observe_event(&GameEvent::Paused, &view());
+        delayed.observe_event(&turn(0), &view());
+        assert_eq!(delayed.inner().entries.len(), 0);
+
+        delayed.observe_event(&turn(1), &view());
+        assert_eq!(delayed.inner().entries.len(), 1);
+
+        delayed.observe_event(&turn(0), &view());
+        assert_eq!(delayed.inner().entries.len(), 2);
+        assert_eq!(delayed.held(), 2);
+
+        delayed.flush();
+        assert_eq!(delayed.inner().entries.len(), 4);
+    }
+}
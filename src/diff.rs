@@ -0,0 +1,128 @@
+//! What happened to the cards between two snapshots of a game, for tracking down a rule variant
+//! or a [`Hook`](crate::hook::Hook) that puts cards in the wrong place, or loses them.
+//!
+//! Diff two [`GameState::snapshot`](crate::game::GameState::snapshot)s, or two points in a
+//! recording with [`Replay::snapshot_after`](crate::replay::Replay::snapshot_after). Cards of
+//! the same kind can't be told apart, so a diff only says how many of each went from one place
+//! to another, not which copy.
+
+use alloc::{vec, vec::Vec};
+use core::fmt::{self, Display, Formatter};
+
+use crate::card::Card;
+use crate::snapshot::Snapshot;
+
+/// Where a card can be.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Place {
+    Deck,
+    Discard,
+    /// The hand of a seat.
+    Hand(usize),
+}
+
+/// One card that ended up somewhere else.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Move {
+    /// As it is now: a wild keeps the color it was played as until it's covered and shuffled in.
+    pub card: Card,
+    pub from: Place,
+    pub to: Place,
+}
+
+/// Everything that changed about the cards, from [`diff`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnapshotDiff {
+    pub moves: Vec<Move>,
+    /// Cards in the later snapshot that weren't anywhere in the earlier one.
+    pub appeared: Vec<(Card, Place)>,
+    /// Cards in the earlier snapshot that are nowhere in the later one.
+    pub vanished: Vec<(Card, Place)>,
+}
+
+impl SnapshotDiff {
+    /// Whether every card is still somewhere, and no more turned up. The engine only breaks it
+    /// for a draw bigger than every card left to draw, when it brings in another deck; so can a
+    /// [scenario](crate::game::GameState::set_hand) that sets hands by hand.
+    pub fn conserves_cards(&self) -> bool {
+        self.appeared.is_empty() && self.vanished.is_empty()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty() && self.conserves_cards()
+    }
+}
+
+/// Which cards moved from where to where between `before` and `after`. Cards that stayed where
+/// they were aren't listed, however their pile was reordered, so a reshuffle shows up as the
+/// discard pile moving into the deck and nothing about the deck's order.
+pub fn diff(before: &Snapshot, after: &Snapshot) -> SnapshotDiff {
+    let mut gone = cards(before);
+    let mut came = cards(after);
+
+    // Whatever is still where it was first, so a card is only counted as moving if there's no
+    // copy of it that stayed.
+    came.retain(|(card, place)| match gone.iter().position(|(was, from)| from == place && same(was, card)) {
+        Some(index) => {
+            gone.swap_remove(index);
+            false
+        }
+        None => true,
+    });
+
+    let mut moves = vec![];
+    let mut appeared = vec![];
+
+    for (card, to) in came {
+        match gone.iter().position(|(was, _)| same(was, &card)) {
+            Some(index) => moves.push(Move { card, from: gone.remove(index).1, to }),
+            None => appeared.push((card, to)),
+        }
+    }
+
+    SnapshotDiff { moves, appeared, vanished: gone }
+}
+
+/// Every card at the table in `snapshot`, with where it is.
+fn cards(snapshot: &Snapshot) -> Vec<(Card, Place)> {
+    let deck = snapshot.deck.iter().map(|card| (*card, Place::Deck));
+    let discard = snapshot.discard.iter().map(|card| (*card, Place::Discard));
+    let hands = snapshot.hands.iter().enumerate().flat_map(|(seat, hand)| hand.iter().map(move |card| (*card, Place::Hand(seat))));
+
+    deck.chain(discard).chain(hands).collect()
+}
+
+/// Whether two cards are copies of the same one. Wilds are whatever color they were played as.
+fn same(a: &Card, b: &Card) -> bool {
+    a == b || a.is_wild() && a.is_equivalent(b)
+}
+
+impl Display for Place {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Place::Deck => write!(f, "the deck"),
+            Place::Discard => write!(f, "the discard pile"),
+            Place::Hand(seat) => write!(f, "seat {seat}'s hand"),
+        }
+    }
+}
+
+/// A line for each card: where it came from and where it went, then any that appeared or
+/// vanished.
+impl Display for SnapshotDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for Move { card, from, to } in &self.moves {
+            writeln!(f, "{card}: {from} -> {to}")?;
+        }
+
+        for (card, place) in &self.appeared {
+            writeln!(f, "{card}: appeared in {place}")?;
+        }
+
+        for (card, place) in &self.vanished {
+            writeln!(f, "{card}: vanished from {place}")?;
+        }
+
+        Ok(())
+    }
+}
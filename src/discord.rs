@@ -0,0 +1,362 @@
+//! Maps the engine onto a Discord-style interaction model.
+//!
+//! Every seat gets a [`DiscordPlayer`] for the game and a [`DiscordSeat`] for the bot. The game
+//! runs on its own thread; the bot forwards each [`Reply`] the seat produces to the player as an
+//! ephemeral message, and feeds the player's button presses and menu selections back through
+//! [`DiscordSeat::interact`], which never blocks and so is safe to call from async handlers.
+
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+use crate::card::{Card, CardColor};
+use crate::game::{GameEvent, GameView, Turn, TurnResult};
+use crate::locale::Message;
+use crate::player::Player;
+use crate::render::RenderConfig;
+
+/// Discord allows five rows of five buttons; one button is kept for drawing.
+const MAX_BUTTONS: usize = 24;
+const MAX_OPTIONS: usize = 25;
+
+const COLORS: [CardColor; 4] = [CardColor::Red, CardColor::Green, CardColor::Blue, CardColor::Yellow];
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ButtonStyle {
+    Primary,
+    Secondary,
+    Success,
+    Danger,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Button {
+    pub custom_id: String,
+    pub label: String,
+    pub style: ButtonStyle,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectOption {
+    pub value: String,
+    pub label: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Component {
+    Button(Button),
+    Select { custom_id: String, placeholder: String, options: Vec<SelectOption> },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActionRow {
+    pub components: Vec<Component>,
+}
+
+/// A message for the bot to send.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reply {
+    pub content: String,
+    pub ephemeral: bool,
+    pub rows: Vec<ActionRow>,
+}
+
+/// What a player did with the components of a [`Reply`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Interaction {
+    Button { custom_id: String },
+    Select { custom_id: String, values: Vec<String> },
+}
+
+/// The bot's end of a seat.
+pub struct DiscordSeat {
+    pub replies: Receiver<Reply>,
+    interactions: Sender<Interaction>,
+}
+
+/// A seat whose decisions arrive as Discord interactions.
+pub struct DiscordPlayer {
+    name: String,
+    render: RenderConfig,
+    replies: Sender<Reply>,
+    interactions: Receiver<Interaction>,
+}
+
+/// Creates a connected player and seat.
+pub fn seat(name: String, render: RenderConfig) -> (DiscordPlayer, DiscordSeat) {
+    let (reply_tx, reply_rx) = channel();
+    let (interaction_tx, interaction_rx) = channel();
+
+    let player = DiscordPlayer {
+        name,
+        render,
+        replies: reply_tx,
+        interactions: interaction_rx,
+    };
+
+    (player, DiscordSeat { replies: reply_rx, interactions: interaction_tx })
+}
+
+impl DiscordSeat {
+    /// Passes an interaction on to the player. Returns false once the game has ended.
+    pub fn interact(&self, interaction: Interaction) -> bool {
+        self.interactions.send(interaction).is_ok()
+    }
+}
+
+/// Every move available on `turn`, as `(id, move)` pairs. Wild cards appear once per color.
+pub fn legal_moves(turn: &Turn) -> Vec<(String, TurnResult)> {
+    let mut moves = vec![];
+
+    for (index, card) in turn.playable_hand.iter().enumerate() {
+        if card.is_wild() {
+            for color in COLORS {
+                let mut card = *card;
+                card.with_color(color);
+                moves.push((format!("{index}:{}", color.to_string().to_lowercase()), TurnResult::Played(card)));
+            }
+        } else {
+            moves.push((index.to_string(), TurnResult::Played(*card)));
+        }
+    }
+
+    moves.push(("draw".to_string(), TurnResult::Drew));
+    moves
+}
+
+/// Finds the move an interaction with a [`turn_reply`] picked, if it's still legal.
+pub fn resolve(turn: &Turn, interaction: &Interaction) -> Option<TurnResult> {
+    let id = match interaction {
+        Interaction::Button { custom_id } => custom_id.strip_prefix("uno:")?,
+        Interaction::Select { custom_id, values } if custom_id == "uno:play" => values.first()?.as_str(),
+        Interaction::Select { .. } => return None,
+    };
+
+    legal_moves(turn).into_iter().find(|(move_id, _)| move_id == id).map(|(_, result)| result)
+}
+
+/// The page of the select menu a button on a [`turn_page`] asked for, if it was one of those.
+pub fn requested_page(interaction: &Interaction) -> Option<usize> {
+    match interaction {
+        Interaction::Button { custom_id } => custom_id.strip_prefix("uno:page:")?.parse().ok(),
+        Interaction::Select { .. } => None,
+    }
+}
+
+fn button_style(card: &Card) -> ButtonStyle {
+    match card.color() {
+        Some(CardColor::Red) => ButtonStyle::Danger,
+        Some(CardColor::Green) => ButtonStyle::Success,
+        Some(CardColor::Blue) => ButtonStyle::Primary,
+        Some(CardColor::Yellow) | None => ButtonStyle::Secondary,
+    }
+}
+
+/// An ephemeral prompt with a component for every legal move on `turn`. Buttons are used while
+/// they fit; larger hands get a select menu instead, paged through with [`turn_page`] when it
+/// has more moves than a menu holds.
+pub fn turn_reply(turn: &Turn, render: &RenderConfig) -> Reply {
+    turn_page(turn, render, 0)
+}
+
+/// [`turn_reply`], with the select menu, if there is one, on `page`, or the last if there are
+/// fewer. Buttons on either side of drawing turn the page.
+pub fn turn_page(turn: &Turn, render: &RenderConfig, page: usize) -> Reply {
+    let locale = &render.locale;
+
+    let plays = legal_moves(turn)
+        .into_iter()
+        .filter_map(|(id, result)| match result {
+            TurnResult::Played(card) => Some((id, card)),
+            TurnResult::Drew => None,
+        })
+        .collect::<Vec<(String, Card)>>();
+
+    let draw = Component::Button(Button {
+        custom_id: "uno:draw".to_string(),
+        label: locale.get(Message::DiscordDraw).to_string(),
+        style: ButtonStyle::Secondary,
+    });
+
+    let mut rows = vec![];
+
+    if plays.len() <= MAX_BUTTONS {
+        let mut buttons = plays
+            .iter()
+            .map(|(id, card)| Component::Button(Button {
+                custom_id: format!("uno:{id}"),
+                label: card.display(render).to_string(),
+                style: button_style(card),
+            }))
+            .collect::<Vec<Component>>();
+
+        buttons.push(draw);
+
+        for chunk in buttons.chunks(5) {
+            rows.push(ActionRow { components: chunk.to_vec() });
+        }
+    } else {
+        let pages = plays.len().div_ceil(MAX_OPTIONS);
+        let page = page.min(pages - 1);
+
+        let options = plays
+            .iter()
+            .skip(page * MAX_OPTIONS)
+            .take(MAX_OPTIONS)
+            .map(|(id, card)| SelectOption { value: id.clone(), label: card.display(render).to_string() })
+            .collect();
+
+        rows.push(ActionRow {
+            components: vec![Component::Select {
+                custom_id: "uno:play".to_string(),
+                placeholder: locale.get(Message::DiscordSelect).to_string(),
+                options,
+            }],
+        });
+
+        let turn_to = |page: usize, message| Component::Button(Button {
+            custom_id: format!("uno:page:{page}"),
+            label: locale.get(message).to_string(),
+            style: ButtonStyle::Secondary,
+        });
+
+        let mut buttons = vec![];
+        buttons.extend((page > 0).then(|| turn_to(page - 1, Message::DiscordPrevious)));
+        buttons.push(draw);
+        buttons.extend((page + 1 < pages).then(|| turn_to(page + 1, Message::DiscordNext)));
+
+        rows.push(ActionRow { components: buttons });
+    }
+
+    Reply {
+        content: locale.format(Message::DiscordTurn, &[("card", &turn.last_card.display(render))]),
+        ephemeral: true,
+        rows,
+    }
+}
+
+/// An ephemeral look at the player's own hand and the table.
+pub fn hand_reply(view: &GameView, render: &RenderConfig) -> Reply {
+    let hand = view.hand
+        .iter()
+        .map(|card| card.display(render).to_string())
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    let opponents = view.players
+        .iter()
+        .enumerate()
+        .filter(|(seat, _)| Some(*seat) != view.seat)
+        .map(|(_, player)| format!("{} ({})", player.name, player.cards))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    Reply {
+        content: render.locale.format(Message::DiscordHand, &[("cards", &hand), ("opponents", &opponents)]),
+        ephemeral: true,
+        rows: vec![],
+    }
+}
+
+impl Player for DiscordPlayer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn execute_turn(&mut self, turn: &Turn) -> TurnResult {
+        let _ = self.replies.send(turn_reply(turn, &self.render));
+
+        loop {
+            // A seat that's gone away can't play; drawing keeps the game moving.
+            let Ok(interaction) = self.interactions.recv() else {
+                return TurnResult::Drew;
+            };
+
+            if let Some(page) = requested_page(&interaction) {
+                let _ = self.replies.send(turn_page(turn, &self.render, page));
+                continue;
+            }
+
+            if let Some(result) = resolve(turn, &interaction) {
+                return result;
+            }
+
+            let _ = self.replies.send(Reply {
+                content: self.render.locale.get(Message::DiscordIllegal).to_string(),
+                ephemeral: true,
+                rows: vec![],
+            });
+        }
+    }
+
+    fn observe_turn(&self, _other: &dyn Player, _card: &Card) {
+        // The bot narrates the table itself.
+    }
+
+    fn observe_turn_skip(&self, _observed_cards: Option<&[Card]>) {
+        // Draws are shown through the refreshed hand view instead.
+    }
+
+    fn observe_event(&mut self, event: &GameEvent, view: &GameView) {
+        let refresh = match event {
+            GameEvent::Started { .. } => true,
+            GameEvent::Drew { player, .. } => view.player_id() == Some(*player),
+            _ => false,
+        };
+
+        if refresh {
+            let _ = self.replies.send(hand_reply(view, &self.render));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::CardValue;
+    use crate::hand::Hand;
+    use crate::render::RenderMode;
+
+    fn select(reply: &Reply) -> Vec<String> {
+        match &reply.rows[0].components[0] {
+            Component::Select { options, .. } => options.iter().map(|option| option.value.clone()).collect(),
+            Component::Button(_) => panic!("a hand this big gets a select menu"),
+        }
+    }
+
+    fn buttons(reply: &Reply) -> Vec<String> {
+        reply.rows[1].components.iter().filter_map(|component| match component {
+            Component::Button(button) => Some(button.custom_id.clone()),
+            Component::Select { .. } => None,
+        }).collect()
+    }
+
+    #[test]
+    fn every_move_of_a_big_hand_is_on_some_page() {
+        // Seven wilds, four ways each, and a red five come to more than a menu holds.
+        let mut playable = vec![Card::Wild { color: None }; 7];
+        playable.push(Card::Numeric { color: CardColor::Red, value: CardValue::Five });
+        let hand = playable.iter().copied().collect::<Hand>();
+        let last_card = Card::Numeric { color: CardColor::Red, value: CardValue::Two };
+        let turn = Turn { to_draw: 0, full_hand: &hand, playable_hand: &mut playable, last_card, drawn: None };
+        let render = RenderConfig::new(RenderMode::Plain);
+
+        let first = turn_reply(&turn, &render);
+        assert_eq!(select(&first).len(), MAX_OPTIONS);
+        assert_eq!(buttons(&first), ["uno:draw", "uno:page:1"]);
+
+        let next = Interaction::Button { custom_id: "uno:page:1".to_string() };
+        assert_eq!(requested_page(&next), Some(1));
+        assert_eq!(resolve(&turn, &next), None);
+
+        let second = turn_page(&turn, &render, 1);
+        assert_eq!(buttons(&second), ["uno:page:0", "uno:draw"]);
+        // Past the last page is the last page.
+        assert_eq!(turn_page(&turn, &render, 5), second);
+
+        let listed = [select(&first), select(&second)].concat();
+        let plays = legal_moves(&turn).into_iter().filter(|(_, result)| *result != TurnResult::Drew).map(|(id, _)| id).collect::<Vec<String>>();
+        assert_eq!(listed, plays);
+
+        let last = Interaction::Select { custom_id: "uno:play".to_string(), values: vec![listed[listed.len() - 1].clone()] };
+        assert_eq!(resolve(&turn, &last), Some(TurnResult::Played(Card::Numeric { color: CardColor::Red, value: CardValue::Five })));
+    }
+}
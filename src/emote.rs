@@ -0,0 +1,49 @@
+use core::fmt::{self, Display, Formatter};
+
+/// A canned reaction a player can send to the whole table or to one other player, with
+/// [`GameState::emote`](crate::game::GameState::emote). A fixed set keeps them translatable and
+/// leaves nothing to moderate.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Emote {
+    Hello,
+    GoodLuck,
+    WellPlayed,
+    Oops,
+    Thinking,
+    Taunt,
+}
+
+/// How many emotes each seat may send within a stretch of turns.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct EmoteLimit {
+    pub count: usize,
+    pub turns: u32,
+}
+
+impl Default for EmoteLimit {
+    fn default() -> Self {
+        EmoteLimit { count: 3, turns: 4 }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum EmoteError {
+    /// Emotes are turned off for this game.
+    Disabled,
+    /// The seat has used up its emotes for now.
+    RateLimited,
+    UnknownSeat(usize),
+}
+
+impl Display for EmoteError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            EmoteError::Disabled => write!(f, "emotes are turned off for this game"),
+            EmoteError::RateLimited => write!(f, "too many emotes, try again in a few turns"),
+            EmoteError::UnknownSeat(seat) => write!(f, "there's no seat {seat}"),
+        }
+    }
+}
+
+impl core::error::Error for EmoteError {}
@@ -0,0 +1,72 @@
+//! How good a hand is to hold, by the same rough measure the built-in bots play by, so a custom
+//! bot can start from a tuned baseline and adjust it rather than starting from nothing.
+
+use crate::card::Card;
+use crate::game::GameView;
+use crate::hand::Hand;
+
+/// Scores `hand` at the table `view` shows, higher being better, from `-1.0` for a hand that's
+/// nothing but trouble to `1.0` for one that's already gone out. What counts:
+///
+/// - how much of the hand is one color, wilds counting towards any, since a concentrated hand
+///   keeps on matching;
+/// - how much of it is action cards, which keep the initiative;
+/// - how much can be played on the top card right now;
+/// - how few cards there are;
+/// - and against all that, the points it would hand an opponent, which matter more the closer
+///   the nearest opponent is to going out.
+pub fn evaluate_hand(hand: &Hand, view: &GameView) -> f32 {
+    let fewest = view.players
+        .iter()
+        .enumerate()
+        .filter(|(seat, player)| Some(*seat) != view.seat && !player.out)
+        .map(|(_, player)| player.cards)
+        .min();
+
+    score(hand, view.top_card.as_ref(), fewest)
+}
+
+/// [`evaluate_hand`] from the parts of the table it looks at, for bots that only see their turn.
+/// Without `fewest` cards held by an opponent, they're taken to be halfway through a hand.
+pub(crate) fn score(hand: &Hand, top: Option<&Card>, fewest: Option<usize>) -> f32 {
+    if hand.is_empty() {
+        return 1.0;
+    }
+
+    let mut colors = [0u32; 4];
+    let (mut wilds, mut actions, mut playable, mut points) = (0u32, 0u32, 0u32, 0u32);
+
+    for card in hand.iter() {
+        points += card.points();
+
+        match card.color() {
+            _ if card.is_wild() => wilds += 1,
+            Some(color) => colors[color as usize] += 1,
+            None => {}
+        }
+
+        if !matches!(card, Card::Numeric { .. }) {
+            actions += 1;
+        }
+
+        if top.is_some_and(|top| card.can_play_on(top)) {
+            playable += 1;
+        }
+    }
+
+    let cards = hand.len() as f32;
+    let concentration = (colors.iter().max().copied().unwrap_or_default() + wilds) as f32 / cards;
+    let action_density = actions as f32 / cards;
+    let playable = playable as f32 / cards;
+    // A full starting hand counts for nothing, and every card past it counts against.
+    let size = 1.0 - cards / 7.0;
+    // Every card worth as much as a wild is as bad as it gets.
+    let liability = points as f32 / (cards * 50.0);
+    let threat = match fewest.unwrap_or(4) {
+        0 | 1 => 1.0,
+        fewest => 1.0 / fewest as f32,
+    };
+
+    let score = 0.3 * concentration + 0.2 * action_density + 0.15 * playable + 0.2 * size - 0.35 * liability * (0.5 + threat);
+    score.clamp(-1.0, 1.0)
+}
@@ -0,0 +1,156 @@
+//! Provably fair shuffling for online play.
+//!
+//! Before a game the server picks a secret seed and publishes its [commitment](FairShuffle::commitment),
+//! a SHA-256 hash. Players then send seeds of their own. The game is shuffled with ChaCha20
+//! seeded from a hash of all of them, so neither side can steer the deck: the server is bound to
+//! a seed it chose before seeing the players', and the players can't predict the server's. After
+//! the game the server publishes a [`Reveal`], which anyone can [verify](Reveal::verify).
+
+use std::fmt::{self, Display, Formatter};
+
+use rand::rngs::OsRng;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use sha2::{Digest, Sha256};
+
+use crate::game::GameState;
+use crate::player::{Player, ScriptedPlayer};
+
+pub struct FairShuffle {
+    server_seed: [u8; 32],
+    client_seeds: Vec<String>,
+}
+
+/// Everything needed to check a game's shuffle once it's over.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Reveal {
+    pub commitment: String,
+    pub server_seed: String,
+    /// In the order they were added.
+    pub client_seeds: Vec<String>,
+    pub seats: usize,
+    /// The game's fingerprint once the cards were dealt.
+    pub dealt: u64,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FairnessError {
+    /// The server seed isn't 64 hex digits.
+    InvalidServerSeed,
+    /// The server seed doesn't hash to the commitment published before the game.
+    CommitmentMismatch,
+    /// The seeds produce a different deal from the one that was played.
+    DealMismatch { expected: u64, actual: u64 },
+}
+
+impl FairShuffle {
+    /// Picks a new server seed from the operating system's secure generator.
+    pub fn new() -> FairShuffle {
+        let mut server_seed = [0; 32];
+        OsRng.fill_bytes(&mut server_seed);
+
+        FairShuffle { server_seed, client_seeds: vec![] }
+    }
+
+    /// The hash to publish before accepting any client seeds.
+    pub fn commitment(&self) -> String {
+        hex(&Sha256::digest(self.server_seed))
+    }
+
+    pub fn add_client_seed(&mut self, seed: impl Into<String>) {
+        self.client_seeds.push(seed.into());
+    }
+
+    /// A game shuffled from the combined seeds. Add every client seed first.
+    pub fn game<'a>(&self, players: Vec<&'a mut dyn Player>) -> GameState<'a> {
+        GameState::with_rng(players, combined_rng(&self.server_seed, &self.client_seeds))
+    }
+
+    /// The values to publish once `game` is over.
+    pub fn reveal(&self, game: &GameState) -> Reveal {
+        Reveal {
+            commitment: self.commitment(),
+            server_seed: hex(&self.server_seed),
+            client_seeds: self.client_seeds.clone(),
+            seats: game.view(None).players.len(),
+            dealt: game.dealt(),
+        }
+    }
+}
+
+impl Default for FairShuffle {
+    fn default() -> FairShuffle {
+        FairShuffle::new()
+    }
+}
+
+impl Reveal {
+    /// Checks the server seed against the commitment, then deals again from the seeds and
+    /// checks the result matches the deal that was played.
+    pub fn verify(&self) -> Result<(), FairnessError> {
+        let server_seed = unhex(&self.server_seed).ok_or(FairnessError::InvalidServerSeed)?;
+
+        if hex(&Sha256::digest(server_seed)) != self.commitment.to_lowercase() {
+            return Err(FairnessError::CommitmentMismatch);
+        }
+
+        let mut seats = (0..self.seats)
+            .map(|seat| ScriptedPlayer::new(seat.to_string(), []))
+            .collect::<Vec<ScriptedPlayer>>();
+
+        let mut game = GameState::with_rng(
+            seats.iter_mut().map(|seat| seat as &mut dyn Player).collect(),
+            combined_rng(&server_seed, &self.client_seeds),
+        );
+        game.play_turn();
+
+        match game.dealt() {
+            actual if actual == self.dealt => Ok(()),
+            actual => Err(FairnessError::DealMismatch { expected: self.dealt, actual }),
+        }
+    }
+}
+
+impl Display for FairnessError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FairnessError::InvalidServerSeed => write!(f, "the revealed server seed isn't valid"),
+            FairnessError::CommitmentMismatch => write!(f, "the server seed doesn't match its commitment"),
+            FairnessError::DealMismatch { .. } => write!(f, "the seeds don't reproduce the deal that was played"),
+        }
+    }
+}
+
+impl std::error::Error for FairnessError {}
+
+fn combined_rng(server_seed: &[u8; 32], client_seeds: &[String]) -> ChaCha20Rng {
+    let mut hasher = Sha256::new();
+    hasher.update(server_seed);
+
+    // Length prefixes keep ["ab", "c"] and ["a", "bc"] from combining the same way.
+    for seed in client_seeds {
+        hasher.update((seed.len() as u32).to_le_bytes());
+        hasher.update(seed.as_bytes());
+    }
+
+    ChaCha20Rng::from_seed(hasher.finalize().into())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn unhex(s: &str) -> Option<[u8; 32]> {
+    let mut bytes = [0; 32];
+
+    if s.len() != 64 || !s.is_ascii() {
+        return None;
+    }
+
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&s[i * 2..i * 2 + 2], 16).ok()?;
+    }
+
+    Some(bytes)
+}
@@ -0,0 +1,105 @@
+//! [`Arbitrary`] support, so fuzzers and property tests can drive the engine with random but
+//! valid input and check that it holds up.
+
+use std::collections::HashMap;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::card::{Card, Deck};
+use crate::game::{card_code, GameState};
+use crate::player::{AIDifficulty, Ai, Player};
+use crate::summary::{GameOutcome, RoundSummary};
+
+/// Any ordering of a full deck.
+impl<'a> Arbitrary<'a> for Deck {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut cards = Deck::new_deck();
+
+        for i in (1..cards.len()).rev() {
+            cards.swap(i, u.int_in_range(0..=i)?);
+        }
+
+        Ok(Deck::of(cards))
+    }
+}
+
+/// The deck has 60 cards, so six hands of seven still leave a pile to draw from.
+const MAX_SEATS: usize = 6;
+
+/// A table of 2 to 6 computer players, seeded so a failing input reproduces exactly.
+#[derive(Debug, Clone, Arbitrary)]
+pub struct ArbitraryGame {
+    pub seed: u64,
+    pub difficulties: Vec<AIDifficulty>,
+}
+
+impl ArbitraryGame {
+    /// Plays up to `max_turns` turns, checking after every one that no card has appeared or gone
+    /// missing. Returns the summary if someone won in time, or a description of the first broken
+    /// invariant. Anything that panics is a bug too.
+    pub fn play(&self, max_turns: u32) -> std::result::Result<Option<RoundSummary>, String> {
+        let mut difficulties = self.difficulties.clone();
+        difficulties.resize(difficulties.len().clamp(2, MAX_SEATS), AIDifficulty::Easy);
+
+        let mut rngs = (0..difficulties.len() as u64)
+            .map(|seat| ChaCha8Rng::seed_from_u64(self.seed ^ seat))
+            .collect::<Vec<ChaCha8Rng>>();
+
+        let mut ais = rngs
+            .iter_mut()
+            .zip(difficulties)
+            .map(|(rng, difficulty)| Ai::new(rng, difficulty))
+            .collect::<Vec<Ai<ChaCha8Rng>>>();
+
+        let mut game = GameState::with_seed(ais.iter_mut().map(|ai| ai as &mut dyn Player).collect(), self.seed);
+
+        let expected = card_counts(Deck::new_deck().into_iter());
+
+        for _ in 0..=max_turns {
+            let outcome = game.play_turn();
+
+            let counts = card_counts(game.cards());
+            let decks = counts.values().sum::<usize>() / Deck::new_deck().len();
+
+            if expected.iter().any(|(code, count)| counts.get(code) != Some(&(count * decks))) || counts.len() != expected.len() {
+                return Err(format!("cards were lost or duplicated by turn {}", game.actions().len()));
+            }
+
+            if let Some(outcome) = outcome {
+                let GameOutcome::Won(summary) = outcome else {
+                    return Err(format!("the game was aborted after {} turns", outcome.turns()));
+                };
+
+                let scored = summary.players.iter().map(|player| player.points).sum::<u32>();
+
+                if scored != summary.points {
+                    return Err(format!("the winner scored {} points, but the hands are worth {scored}", summary.points));
+                }
+
+                return Ok(Some(summary));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// How many of each card there are, ignoring the colors chosen for wild cards.
+fn card_counts(cards: impl Iterator<Item = Card>) -> HashMap<u8, usize> {
+    let mut counts = HashMap::new();
+
+    for card in cards {
+        let card = match card {
+            Card::Wild { .. } => Card::Wild { color: None },
+            Card::DrawFour { .. } => Card::DrawFour { color: None },
+            Card::WildSkipEveryone { .. } => Card::WildSkipEveryone { color: None },
+            card => card,
+        };
+
+        *counts.entry(card_code(&card)).or_insert(0) += 1;
+    }
+
+    counts
+}
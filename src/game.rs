@@ -1,283 +1,2301 @@
+use alloc::{boxed::Box, collections::VecDeque, format, string::{String, ToString}, vec, vec::Vec};
+use core::fmt::{self, Display, Formatter};
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
 
+use rand::seq::SliceRandom;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 
-use crate::card::{Card, CardColor, Deck};
-use crate::player::Player;
+use crate::card::{Card, CardColor, ColorSet, Deck};
+use crate::clock::Clock;
+use crate::emote::{Emote, EmoteError, EmoteLimit};
+use crate::hand::Hand;
+use crate::hook::{Effect, Hook};
+#[cfg(feature = "std")]
+use crate::pacer::Delay;
+use crate::pacer::{NoPacing, Pacer};
+use crate::player::{Player, PlayerId};
+use crate::replay::{self, EventDivergence, Replay, ReplayAction};
+use crate::rules::{self, Draw, Mercy, PileSize, Rules, TurnOrder, ENGINE_VERSION};
+use crate::snapshot::{Position, PositionError, Snapshot, SnapshotError};
+use crate::summary::{GameOutcome, PlayerSummary, RoundSummary};
+#[cfg(feature = "training")]
+use crate::training::Decision;
+use crate::window::{self, Interjection, Window};
 
+/// A round of UNO.
+///
+/// Games are [`Send`], so a server can hand a table to a worker thread, or hold one across an
+/// `.await` in a task that owns its players. They aren't [`Sync`]: to read a game from several
+/// threads at once, share the owned [`GameView`]s it produces instead.
 pub struct GameState<'a> {
     deck: Deck,
     discard: Vec<Card>,
-    players: Vec<(&'a mut dyn Player, Vec<Card>)>,
+    players: Vec<(&'a mut dyn Player, Hand)>,
+    spectators: Vec<&'a mut dyn Spectator>,
+    /// What's been emitted since an [`Events`] last took from it, while one is running.
+    events: Option<VecDeque<GameEvent>>,
+    hooks: Vec<&'a mut dyn Hook>,
+    rules: Rules,
+    /// For each seat, where its player was in the list the game was made with.
+    seating: Vec<usize>,
+    /// Whether [`GameState::shuffle_seats`] was used, so replays know to shuffle again even if
+    /// everyone happened to stay where they were.
+    seats_shuffled: bool,
+    first_player: Option<usize>,
+    opening: Opening,
+    /// By the player's place in the list the game was made with.
+    hand_sizes: Vec<u8>,
     current_player: usize,
     direction: Direction,
+    /// The color the next card has to match. After a wild it's the color its player chose, which
+    /// nothing but the card object on the discard pile would otherwise remember.
+    active_color: Option<CardColor>,
+    /// The other color named for the wild on top, under [`Rules::two_color_wilds`].
+    second_color: Option<CardColor>,
     to_draw: u8,
+    /// The cards behind `to_draw`.
+    draw_chain: Vec<StackedDraw>,
+    /// Effects of the card just played, waiting to be carried out. Always empty between turns.
+    pending: VecDeque<PendingEffect>,
+    /// For each seat, what it's shown of how it plays.
+    tendencies: Vec<Tendencies>,
+    /// Seats out of the round, in the order they went out.
+    out: Vec<usize>,
+    /// Those of `out` that went out by playing their last card, rather than being eliminated.
+    finished: Vec<usize>,
+    turns: u32,
+    seed: Option<u64>,
+    rng: Shuffler<'a>,
+    pacer: Box<dyn Pacer + 'a>,
+    clock: Option<Box<dyn Clock + 'a>>,
+    /// When the last event happened, by `clock`.
+    at: Option<Duration>,
+    dealt: u64,
+    actions: Vec<ReplayAction>,
+    playable: Vec<Card>,
+    drawn: Vec<Card>,
+    aborted: Option<String>,
+    paused: bool,
+    emote_limit: Option<EmoteLimit>,
+    /// The seat and turn of every emote still inside the limit's window.
+    emotes: Vec<(usize, u32)>,
+    reveal_hands: bool,
+    /// How long windows for acting out of turn stay open, when they're on.
+    window: Option<Duration>,
+    /// Who the current player chose to swap hands with, until it's logged with their move.
+    swapped_with: Option<usize>,
+    #[cfg(feature = "std")]
+    handle: Option<GameHandle>,
+    #[cfg(feature = "training")]
+    decisions: Option<Vec<Decision>>,
+}
+
+/// Where a game's shuffles come from. Seeded games keep their generator's concrete type, so a
+/// snapshot can record how far through its stream they are.
+enum Shuffler<'a> {
+    Seeded(Box<ChaCha8Rng>),
+    Custom(Box<dyn RngCore + Send + 'a>),
+}
+
+/// An effect waiting to be carried out, with the seat of the player whose card caused it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct PendingEffect {
+    seat: usize,
+    effect: Effect,
+}
+
+/// Controls a game from another thread or task: aborts it, or pauses it once the current turn
+/// is over. Made with [`GameState::handle`].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Default)]
+pub struct GameHandle(Arc<(Mutex<Control>, Condvar)>);
+
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+struct Control {
+    abort: Option<String>,
+    paused: bool,
+    /// Left by a game that [`GameState::start`] is holding while it's paused.
+    snapshot: Option<Snapshot>,
+    emotes: Vec<(usize, Option<usize>, Emote)>,
+    /// Sent in for the window that's open, if any.
+    interjections: Vec<(usize, Interjection)>,
 }
 
 pub struct Turn<'a> {
     pub to_draw: u8,
-    pub full_hand: &'a Vec<Card>,
+    pub full_hand: &'a Hand,
     pub playable_hand: &'a mut Vec<Card>,
     pub last_card: Card,
+    /// The card the player just drew, when it's the only one they may play.
+    pub drawn: Option<Card>,
 }
 
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TurnResult {
     Played(Card),
     Drew,
 }
 
+/// What becomes of the hand of a player who leaves, for [`GameState::remove_player`].
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LeftHand {
+    /// Shuffled into the deck.
+    #[default]
+    ShuffleIn,
+    /// Dealt out one at a time to the players still in, starting with whoever plays next.
+    Distribute,
+    /// Put under the top of the discard pile, as an eliminated player's is.
+    Discard,
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
-enum Direction {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
     Clockwise,
     CounterClockwise,
 }
 
+/// Something that happened at the table, in the order it happened. Players are named by their
+/// [`PlayerId`], which a [`GameView`] turns back into a name with [`GameView::name_of`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GameEvent {
+    Started { top_card: Card },
+    TurnStarted { player: PlayerId },
+    Played { player: PlayerId, card: Card },
+    Drew { player: PlayerId, count: usize },
+    Skipped { player: PlayerId },
+    Won { player: PlayerId },
+    Aborted { reason: String },
+    Paused,
+    Resumed,
+    /// `to` is set for an emote sent to one player, which only the two of them see.
+    Emote { player: PlayerId, to: Option<PlayerId>, emote: Emote },
+    /// For debugging only: every hand in seating order, sent to spectators after each turn
+    /// while [`GameState::set_reveal_hands`] is on.
+    DebugHands { hands: Vec<Vec<Card>> },
+    /// How the game was set up, sent as it's dealt so a log of events has everything
+    /// [`GameState::from_events`] needs. `seating` is empty if the seats weren't shuffled.
+    Setup {
+        rules: Rules,
+        seating: Vec<usize>,
+        first_player: Option<usize>,
+        #[cfg_attr(feature = "serde", serde(default))]
+        opening: Opening,
+        /// See [`GameState::set_hand_sizes`].
+        #[cfg_attr(feature = "serde", serde(default))]
+        hand_sizes: Vec<u8>,
+        /// See [`ENGINE_VERSION`](rules::ENGINE_VERSION).
+        #[cfg_attr(feature = "serde", serde(default))]
+        engine: u32,
+    },
+    /// A Trade Hands, or a 0 under [`Rules::zero_swap`], was played: `player` and `with`
+    /// swapped every card they held.
+    Traded { player: PlayerId, with: PlayerId },
+    /// A Discard All was played: `player` put `cards` under the top of the discard pile.
+    Discarded { player: PlayerId, cards: Vec<Card> },
+    /// `player` was let off `count` cards by a [`Mercy::Cap`].
+    Spared { player: PlayerId, count: usize },
+    /// `player` drew past a [`Mercy::Eliminate`] and is out of the round.
+    Eliminated { player: PlayerId },
+    /// `player` went out while others play on, as [`Rules::play_to_last`] has it, finishing in
+    /// `place`, from 1.
+    Finished { player: PlayerId, place: usize },
+    /// `card` opened a [`Window`] for the rest of the table to act out of turn.
+    WindowOpened { player: PlayerId, card: Card },
+    /// `player` was heard in the window that's just closed.
+    Interjected { player: PlayerId, interjection: Interjection },
+    /// `player`'s card added to the pending draw, which the next player now faces `to_draw`
+    /// cards of unless they stack on it.
+    Stacked { player: PlayerId, to_draw: u8 },
+    /// `player` was taken out of the game by [`GameState::remove_player`], and their hand went
+    /// where `hand` says.
+    PlayerLeft { player: PlayerId, hand: LeftHand },
+    /// The round is over, and `player` shows the `cards` they were left holding, worth `points`
+    /// by [`Rules::points`]. Sent for every player, in seating order, after
+    /// [`Won`](GameEvent::Won) or [`Aborted`](GameEvent::Aborted), so the score can be checked.
+    Revealed { player: PlayerId, cards: Vec<Card>, points: u32 },
+    /// `player` named a second color for their wild under [`Rules::two_color_wilds`], so the
+    /// next card can match any of `colors`.
+    ColorsNamed { player: PlayerId, colors: ColorSet },
+}
+
+/// What a given seat is allowed to see of the table. Spectators get a view without a hand.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameView {
+    pub seat: Option<usize>,
+    pub hand: Vec<Card>,
+    pub top_card: Option<Card>,
+    /// See [`GameState::active_color`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub active_color: Option<CardColor>,
+    /// See [`GameState::active_colors`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub second_color: Option<CardColor>,
+    pub players: Vec<PlayerView>,
+    pub current_player: usize,
+    pub direction: Direction,
+    pub to_draw: u8,
+    /// The cards behind `to_draw`, in the order they were played.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub draw_chain: Vec<StackedDraw>,
+    /// How many cards are left to draw and how many are on the discard pile, as far as
+    /// [`Rules::pile_sizes`] lets anyone know.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub draw_pile: Option<PileSize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub discard_pile: Option<PileSize>,
+    /// [`GameState::fingerprint`] at the time of the view.
+    pub fingerprint: u64,
+    /// When the last event happened, by the game's [`Clock`], or `None` if it doesn't have one.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub at: Option<Duration>,
+}
+
+/// What a game starts with that it didn't deal itself, carried over from the game before it in
+/// a match. Set with [`GameState::set_opening`].
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Opening {
+    /// Cards whoever takes the first turn is left to draw, on top of any Draw Two turned up.
+    pub draw: u8,
+    /// A wild to start the discard pile with, in the color it was played as, instead of turning
+    /// a card over. It's taken out of the deck, so it does nothing if the rules don't put one in.
+    pub wild: Option<Card>,
+}
+
+/// Why a game can't be dealt as it's set up, from [`GameState::check_deal`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DealError {
+    /// A [handicap](GameState::set_hand_sizes) gave `actual` hand sizes for `expected` players.
+    HandSizes { expected: usize, actual: usize },
+    /// The hands take `needed` cards, and with one more to turn up that's more than the `deck`
+    /// holds.
+    TooManyCards { needed: usize, deck: usize },
+}
+
+impl Display for DealError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            DealError::HandSizes { expected, actual } => write!(f, "there are {expected} players but {actual} hand sizes were given"),
+            DealError::TooManyCards { needed, deck } => write!(f, "the hands take {needed} cards, but the deck only has {deck} with one to turn up"),
+        }
+    }
+}
+
+impl core::error::Error for DealError {}
+
+/// A card that added to a pending draw, for [`GameView::draw_chain`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StackedDraw {
+    /// Who played it.
+    pub seat: usize,
+    pub card: Card,
+    /// How much it added.
+    pub count: u8,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerView {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub id: PlayerId,
+    /// Numbered if others at the table go by the same name, as in `Bot (2)`.
+    pub name: String,
+    pub cards: usize,
+    /// Out of the round, and passed over by play.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub out: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tendencies: Tendencies,
+}
+
+/// What a player has shown of how they play this round, as anyone at the table could have seen
+/// it, kept by the engine so bots can adapt to their opponents without keeping count themselves.
+/// Counts by color are indexed by [`CardColor`] as a `usize`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tendencies {
+    pub plays: u32,
+    /// Times they drew with no draw pending, whether they had nothing to play or kept it back.
+    pub draws: u32,
+    /// Cards they've played of each color, wilds by the color they chose.
+    pub played: [u32; 4],
+    /// Times they drew with each color to match.
+    pub drew_on: [u32; 4],
+    /// Colors they drew on, or discarded all of, and haven't played since, so probably aren't
+    /// holding. A traded hand takes them with it.
+    pub avoiding: Vec<CardColor>,
+}
+
+impl Tendencies {
+    /// How often they draw when they could have played, from 0 to 1.
+    pub fn draw_rate(&self) -> f64 {
+        match self.plays + self.draws {
+            0 => 0.0,
+            turns => self.draws as f64 / turns as f64,
+        }
+    }
+
+    pub fn avoids(&self, color: CardColor) -> bool {
+        self.avoiding.contains(&color)
+    }
+
+    fn played(&mut self, card: &Card) {
+        self.plays += 1;
+
+        if let Some(color) = card.color() {
+            self.played[color as usize] += 1;
+            self.avoiding.retain(|avoided| *avoided != color);
+        }
+    }
+
+    fn drew_on(&mut self, color: CardColor) {
+        self.draws += 1;
+        self.drew_on[color as usize] += 1;
+        self.avoid(color);
+    }
+
+    fn avoid(&mut self, color: CardColor) {
+        if !self.avoids(color) {
+            self.avoiding.push(color);
+        }
+    }
+}
+
+impl GameView {
+    /// The id of whoever the view is for, if it's for a seat.
+    pub fn player_id(&self) -> Option<PlayerId> {
+        self.seat.map(|seat| self.players[seat].id)
+    }
+
+    /// Where the player with `id` sits.
+    pub fn seat_of(&self, id: PlayerId) -> Option<usize> {
+        self.players.iter().position(|player| player.id == id)
+    }
+
+    /// What every other player still in the round has shown of how they play, by seat.
+    pub fn opponent_profiles(&self) -> impl Iterator<Item = (usize, &Tendencies)> + '_ {
+        self.players
+            .iter()
+            .enumerate()
+            .filter(|(seat, player)| Some(*seat) != self.seat && !player.out)
+            .map(|(seat, player)| (seat, &player.tendencies))
+    }
+
+    /// See [`GameState::active_colors`].
+    pub fn active_colors(&self) -> ColorSet {
+        self.active_color.into_iter().chain(self.second_color).collect()
+    }
+
+    /// What the player with `id` is called at this table, or their id if they aren't at it.
+    pub fn name_of(&self, id: PlayerId) -> String {
+        match self.players.iter().find(|player| player.id == id) {
+            Some(player) => player.name.clone(),
+            None => id.to_string(),
+        }
+    }
+}
+
+// Every seat and spectator is `Send`, so this only breaks if a new field isn't.
+const _: fn() = || {
+    fn assert_send<T: Send>() {}
+    assert_send::<GameState<'static>>();
+};
+
+/// The events of a game as it's played, from [`GameState::events`].
+pub struct Events<'g, 'a> {
+    game: &'g mut GameState<'a>,
+    over: bool,
+}
+
+impl Iterator for Events<'_, '_> {
+    type Item = GameEvent;
+
+    fn next(&mut self) -> Option<GameEvent> {
+        loop {
+            if let Some(event) = self.game.events.as_mut().and_then(VecDeque::pop_front) {
+                return Some(event);
+            }
+
+            if self.over {
+                return None;
+            }
+
+            self.over = self.game.play_turn().is_some();
+
+            // A paused game won't move until it's resumed, so there's nothing more to wait for.
+            if self.game.paused && self.game.events.as_ref().is_some_and(VecDeque::is_empty) {
+                return None;
+            }
+        }
+    }
+}
+
+impl Drop for Events<'_, '_> {
+    fn drop(&mut self) {
+        self.game.events = None;
+    }
+}
+
+/// Receives every event without taking part in the game (console output, frontends, logging).
+pub trait Spectator: Send {
+    fn observe_event(&mut self, event: &GameEvent, view: &GameView);
+}
+
+impl RngCore for Shuffler<'_> {
+    fn next_u32(&mut self) -> u32 {
+        self.rng().next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng().next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng().fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.rng().try_fill_bytes(dest)
+    }
+}
+
+impl<'a> Shuffler<'a> {
+    fn rng(&mut self) -> &mut (dyn RngCore + Send + 'a) {
+        match self {
+            Shuffler::Seeded(rng) => &mut **rng,
+            Shuffler::Custom(rng) => &mut **rng,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl GameHandle {
+    /// Asks the game to stop, even if it's paused. If it's asked more than once, the first
+    /// reason is the one given.
+    pub fn abort(&self, reason: impl Into<String>) {
+        self.control().abort.get_or_insert_with(|| reason.into());
+        self.0.1.notify_all();
+    }
+
+    pub fn is_aborted(&self) -> bool {
+        self.control().abort.is_some()
+    }
+
+    /// Asks the game to pause once the turn being played is over.
+    pub fn pause(&self) {
+        self.control().paused = true;
+    }
+
+    pub fn resume(&self) {
+        self.control().paused = false;
+        self.0.1.notify_all();
+    }
+
+    /// Sends an emote with [`GameState::emote`] before the next turn. Emotes that are turned
+    /// off or over the limit are dropped.
+    pub fn emote(&self, seat: usize, to: Option<usize>, emote: Emote) {
+        self.control().emotes.push((seat, to, emote));
+    }
+
+    /// Answers the [`Window`] that's open for `seat`. Anything sent while no window is open is
+    /// dropped when the next one opens.
+    pub fn interject(&self, seat: usize, interjection: Interjection) {
+        self.control().interjections.push((seat, interjection));
+    }
+
+    /// Whether the game has been asked to pause and not to resume since.
+    pub fn is_paused(&self) -> bool {
+        self.control().paused
+    }
+
+    /// The game as it stands, while [`start`](GameState::start) is holding it paused. Saving
+    /// this lets it carry on after a restart.
+    pub fn snapshot(&self) -> Option<Snapshot> {
+        self.control().snapshot.clone()
+    }
+
+    /// Everything sent in with [`interject`](GameHandle::interject) over the next `timeout`,
+    /// or until the game is aborted.
+    fn wait_for_interjections(&self, timeout: Duration) -> Vec<(usize, Interjection)> {
+        let (control, _) = self.0.1
+            .wait_timeout_while(self.control(), timeout, |control| control.abort.is_none())
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let mut control = control;
+        core::mem::take(&mut control.interjections)
+    }
+
+    fn control(&self) -> MutexGuard<'_, Control> {
+        self.0.0.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
 
 impl<'a> GameState<'a> {
+    #[cfg(feature = "std")]
     pub fn new(players: Vec<&'a mut dyn Player>) -> GameState<'a> {
+        GameState::with_seed(players, rand::thread_rng().next_u64())
+    }
+
+    /// A game whose shuffles all come from `seed`, so the same seed and the same moves always
+    /// play out the same way.
+    pub fn with_seed(players: Vec<&'a mut dyn Player>, seed: u64) -> GameState<'a> {
+        let mut game = GameState::with_shuffler(players, Shuffler::Seeded(Box::new(ChaCha8Rng::seed_from_u64(seed))));
+        game.seed = Some(seed);
+        game
+    }
+
+    /// A game that shuffles with `rng`: a fixed generator for tests, or a cryptographically
+    /// secure one for online play. These games can't be replayed from a seed.
+    pub fn with_rng(players: Vec<&'a mut dyn Player>, rng: impl RngCore + Send + 'a) -> GameState<'a> {
+        GameState::with_shuffler(players, Shuffler::Custom(Box::new(rng)))
+    }
+
+    /// Carries on a game from a [`snapshot`](GameState::snapshot), with `players` taking its
+    /// seats in order. Seeded games go on shuffling exactly as they would have; others need
+    /// [`from_snapshot_with_rng`](GameState::from_snapshot_with_rng). A game that was paused
+    /// stays paused until it's resumed.
+    pub fn from_snapshot(players: Vec<&'a mut dyn Player>, snapshot: &Snapshot) -> Result<GameState<'a>, SnapshotError> {
+        let (Some(seed), Some(position)) = (snapshot.seed, snapshot.stream_position) else {
+            return Err(SnapshotError::Unseeded);
+        };
+
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        rng.set_word_pos(position);
+
+        GameState::restore(players, snapshot, Shuffler::Seeded(Box::new(rng)))
+    }
+
+    /// Carries on a game from a snapshot, shuffling with `rng` from here on.
+    pub fn from_snapshot_with_rng(
+        players: Vec<&'a mut dyn Player>,
+        snapshot: &Snapshot,
+        rng: impl RngCore + Send + 'a,
+    ) -> Result<GameState<'a>, SnapshotError> {
+        GameState::restore(players, snapshot, Shuffler::Custom(Box::new(rng)))
+    }
+
+    fn restore(players: Vec<&'a mut dyn Player>, snapshot: &Snapshot, rng: Shuffler<'a>) -> Result<GameState<'a>, SnapshotError> {
+        let game = GameState::place(players, snapshot, rng)?;
+
+        match game.fingerprint() {
+            actual if actual == snapshot.fingerprint => Ok(game),
+            actual => Err(SnapshotError::Fingerprint { expected: snapshot.fingerprint, actual }),
+        }
+    }
+
+    /// Sets up the table `position` describes, with `players` taking its seats in order, ready
+    /// for the first seat's turn. There's no deal to play again, so the game is treated as
+    /// unseeded: it has no [`replay`](GameState::replay), and restoring a
+    /// [`snapshot`](GameState::snapshot) of it needs a generator.
+    pub fn from_position(players: Vec<&'a mut dyn Player>, position: &Position) -> Result<GameState<'a>, PositionError> {
+        let seats = position.hands.len();
+
+        if players.len() != seats {
+            return Err(PositionError::SeatCount { expected: seats, actual: players.len() });
+        }
+
+        if let Some(seat) = position.out.iter().chain([&position.to_play]).find(|seat| **seat >= seats) {
+            return Err(PositionError::NoSuchSeat(*seat));
+        }
+
+        if position.discard.is_empty() {
+            return Err(PositionError::NoTopCard);
+        }
+
+        let mut game = GameState::with_shuffler(players, Shuffler::Custom(Box::new(ChaCha8Rng::seed_from_u64(position.seed))));
+
+        for ((_, hand), cards) in game.players.iter_mut().zip(&position.hands) {
+            hand.extend(cards.iter().copied());
+        }
+
+        game.deck = Deck::of(position.deck.clone());
+        game.discard = position.discard.clone();
+        game.active_color = game.discard.last().and_then(Card::color);
+        // A turn starts by moving on from whoever played last, so it's as if the seat before
+        // the first had just played.
+        game.current_player = rules::next_seat(position.to_play, seats, rules::reversed(position.direction));
+        game.direction = position.direction;
+        game.to_draw = position.to_draw;
+        game.rules = position.rules;
+        game.out = position.out.clone();
+
+        Ok(game)
+    }
+
+    fn place(players: Vec<&'a mut dyn Player>, snapshot: &Snapshot, rng: Shuffler<'a>) -> Result<GameState<'a>, SnapshotError> {
+        if snapshot.engine > ENGINE_VERSION {
+            return Err(SnapshotError::Version { found: snapshot.engine });
+        }
+
+        if players.len() != snapshot.hands.len() {
+            return Err(SnapshotError::SeatCount { expected: snapshot.hands.len(), actual: players.len() });
+        }
+
+        let mut game = GameState::with_shuffler(players, rng);
+
+        for ((_, hand), cards) in game.players.iter_mut().zip(&snapshot.hands) {
+            hand.extend(cards.iter().copied());
+        }
+
+        game.deck = Deck::of(snapshot.deck.clone());
+        game.discard = snapshot.discard.clone();
+        game.active_color = game.discard.last().and_then(Card::color);
+        game.second_color = snapshot.second_color;
+        game.current_player = snapshot.current_player;
+        game.direction = snapshot.direction;
+        game.to_draw = snapshot.to_draw;
+        game.draw_chain = snapshot.draw_chain.clone();
+        game.turns = snapshot.turns;
+        game.dealt = snapshot.dealt;
+        game.actions = snapshot.actions.clone();
+        game.paused = snapshot.paused;
+        game.aborted = snapshot.aborted.clone();
+        game.rules = snapshot.rules;
+        game.first_player = snapshot.first_player;
+        game.opening = snapshot.opening;
+        game.hand_sizes = snapshot.hand_sizes.clone();
+        game.out = snapshot.out.clone();
+        game.finished = snapshot.finished.clone();
+
+        if snapshot.tendencies.len() == game.players.len() {
+            game.tendencies = snapshot.tendencies.clone();
+        }
+
+        if !snapshot.seating.is_empty() {
+            game.seating = snapshot.seating.clone();
+            game.seats_shuffled = true;
+        }
+
+        if matches!(game.rng, Shuffler::Seeded(_)) {
+            game.seed = snapshot.seed;
+        }
+
+        Ok(game)
+    }
+
+    /// Rebuilds a seeded game from a log of its events, as spectators or any one of its players
+    /// received them, and seats `players` in it, in the order the log has them. A log that stops
+    /// part way through a turn gives the game as it was before that turn, and one from a later
+    /// [engine](rules::ENGINE_VERSION) diverges at its setup.
+    ///
+    /// Emotes aren't replayed, and neither are any hooks the game had.
+    pub fn from_events(players: Vec<&'a mut dyn Player>, seed: u64, events: &[GameEvent]) -> Result<GameState<'a>, EventDivergence> {
+        let names = players.iter().map(|player| player.name().to_string()).collect::<Vec<String>>();
+        let snapshot = replay::rebuild(names, seed, events)?;
+
+        Ok(GameState::from_snapshot(players, &snapshot).expect("a rebuilt game restores with the seats it was rebuilt with"))
+    }
+
+    fn with_shuffler(players: Vec<&'a mut dyn Player>, rng: Shuffler<'a>) -> GameState<'a> {
+        let seats = players.len();
+
         GameState {
             deck: Deck::generate(),
             discard: vec![],
-            players: players.into_iter().map(|p| (p, vec![])).collect(),
+            players: players.into_iter().map(|p| (p, Hand::new())).collect(),
+            spectators: vec![],
+            events: None,
+            hooks: vec![],
+            rules: Rules::default(),
+            seating: (0..seats).collect(),
+            seats_shuffled: false,
+            first_player: None,
+            opening: Opening::default(),
+            hand_sizes: vec![],
             current_player: 0,
             direction: Direction::Clockwise,
+            active_color: None,
+            second_color: None,
             to_draw: 0,
+            draw_chain: vec![],
+            pending: VecDeque::new(),
+            tendencies: vec![Tendencies::default(); seats],
+            out: vec![],
+            finished: vec![],
+            turns: 0,
+            seed: None,
+            rng,
+            pacer: Box::new(NoPacing),
+            clock: None,
+            at: None,
+            dealt: 0,
+            actions: vec![],
+            playable: vec![],
+            drawn: vec![],
+            aborted: None,
+            paused: false,
+            emote_limit: Some(EmoteLimit::default()),
+            emotes: vec![],
+            reveal_hands: false,
+            window: None,
+            swapped_with: None,
+            #[cfg(feature = "std")]
+            handle: None,
+            #[cfg(feature = "training")]
+            decisions: None,
         }
     }
 
-    pub fn start(&mut self) -> String {
-        self.deck.shuffle();
+    pub fn add_spectator(&mut self, spectator: &'a mut dyn Spectator) {
+        self.spectators.push(spectator);
+    }
+
+    /// Changes the rules, which should be done before the first turn.
+    pub fn set_rules(&mut self, rules: Rules) {
+        self.rules = rules;
+    }
+
+    pub fn rules(&self) -> Rules {
+        self.rules
+    }
+
+    /// Seats the players in a random order, with the game's own shuffles so that seeded games
+    /// still replay. Like the rules, this should be done once, before the first turn; see
+    /// [`seating`](GameState::seating) for where everyone ended up.
+    pub fn shuffle_seats(&mut self) {
+        let mut order = (0..self.players.len()).collect::<Vec<usize>>();
+        order.shuffle(&mut self.rng);
+
+        let mut players = self.players.drain(..).map(Some).collect::<Vec<_>>();
+        self.players = order.iter().map(|&from| players[from].take().unwrap()).collect();
+        self.seating = order.iter().map(|&from| self.seating[from]).collect();
+        self.seats_shuffled = true;
+    }
+
+    /// The next `count` seats to play, in order, for showing who's coming up. The skips and
+    /// reverses played so far are accounted for; cards not yet played can change it.
+    pub fn upcoming_turn_order(&self, count: usize) -> Vec<usize> {
+        self.turn_order().upcoming().take(count).collect()
+    }
+
+    /// For each seat, the index its player had in the list the game was made with. In order
+    /// unless the seats were shuffled.
+    pub fn seating(&self) -> &[usize] {
+        &self.seating
+    }
+
+    /// Who takes the first turn, e.g. the winner of the last round. The player before them deals,
+    /// so any action card turned up by the deal works against them. Defaults to seat 1, after
+    /// seat 0 deals; set it before the first turn.
+    pub fn set_first_player(&mut self, seat: usize) {
+        self.first_player = Some(seat);
+    }
 
-        for (_, hand) in self.players.iter_mut() {
+    /// What the game starts with from the one before it, such as a draw left pending when it
+    /// ended. Set it before the first turn.
+    pub fn set_opening(&mut self, opening: Opening) {
+        self.opening = opening;
+    }
 
-            let insert = self.deck.draw_multiple(7);
-            hand.extend(insert);
+    /// Deals each player their own number of cards rather than [`Rules::hand_size`], as a
+    /// handicap: more for stronger players, fewer for newcomers. `sizes` go by each player's
+    /// place in the list the game was made with, so they follow players when the seats are
+    /// shuffled, and there's one for every player or none at all. Set it before the first turn,
+    /// after the rules; sizes the deck can't deal are refused, and the game keeps the ones it had.
+    pub fn set_hand_sizes(&mut self, sizes: Vec<u8>) -> Result<(), DealError> {
+        if !sizes.is_empty() && sizes.len() != self.players.len() {
+            return Err(DealError::HandSizes { expected: self.players.len(), actual: sizes.len() });
         }
 
-        loop {
-            let top_card = self.deck.draw().unwrap();
+        let previous = core::mem::replace(&mut self.hand_sizes, sizes);
 
-            match top_card {
-                Card::Wild { color: _ } => {
-                    self.deck.reinsert_random(top_card);
-                }
-                Card::DrawFour { color: _ } => {
-                    self.deck.reinsert_random(top_card);
-                }
-                Card::Skip { .. } => {
-                    self.deck.reinsert_random(top_card);
-                }
-                _ => {
-                    self.discard.push(top_card);
-                    println!("The top card is: {}", Self::get_colorized_card_name(*self.discard.last().unwrap()));
-                    break;
-                }
-            }
+        if let Err(error) = self.check_deal() {
+            self.hand_sizes = previous;
+            return Err(error);
         }
 
-        loop {
-            std::thread::sleep(std::time::Duration::from_millis(800));
+        Ok(())
+    }
 
-            Self::ensure_drawable_deck(&mut self.deck, &mut self.discard, self.to_draw);
+    /// How many cards `seat` is dealt, after any [handicap](GameState::set_hand_sizes).
+    pub fn hand_size(&self, seat: usize) -> u8 {
+        self.hand_sizes.get(self.seating[seat]).copied().unwrap_or(self.rules.hand_size)
+    }
 
-            self.current_player = self.next_player();
+    /// Whether the deck, with the cards the rules add to it, holds enough to deal every hand and
+    /// turn up a card to start on. A game that doesn't can't be played; check it before the first
+    /// turn.
+    pub fn check_deal(&self) -> Result<(), DealError> {
+        let added = usize::from(self.rules.skip_everyone_cards)
+            + CardColor::ALL.len() * usize::from(self.rules.trade_hands_cards + self.rules.discard_all_cards);
+        let carried = self.opening.wild.filter(Card::is_wild).is_some_and(|wild| self.deck.cards.iter().any(|card| card.is_equivalent(&wild)));
+        let deck = self.deck.cards.len() + added - usize::from(carried);
+        let needed = (0..self.players.len()).map(|seat| usize::from(self.hand_size(seat))).sum::<usize>();
 
-            // Play for the current player
-            let (current_player, player_hand) = self.players.get_mut(self.current_player).unwrap();
+        match needed < deck {
+            true => Ok(()),
+            false => Err(DealError::TooManyCards { needed, deck }),
+        }
+    }
 
-            let playable_player_hand = &mut Self::get_playable_hand(player_hand, self.discard.last().unwrap(), self.to_draw);
+    /// Hooks are called in the order they were added.
+    pub fn add_hook(&mut self, hook: &'a mut dyn Hook) {
+        self.hooks.push(hook);
+    }
 
-            if self.to_draw > 0 && !Self::contains_special_card(playable_player_hand, self.discard.last().unwrap()) {
-                let draw = &self.deck.draw_multiple(self.to_draw);
+    /// The seed, for games made with [`with_seed`](GameState::with_seed).
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
 
-                player_hand.extend(draw);
-                current_player.observe_turn_skip(Some(draw.iter().collect()));
+    /// Keeps a [`Decision`] for every move a player chooses from now on, for
+    /// [`training`](crate::training) data. Forced draws aren't decisions, so they're left out.
+    #[cfg(feature = "training")]
+    pub fn record_decisions(&mut self) {
+        self.decisions.get_or_insert_with(Vec::new);
+    }
 
-                println!("{} drew {} cards", current_player.name(), self.to_draw);
+    /// The decisions recorded since [`record_decisions`](GameState::record_decisions), oldest
+    /// first.
+    #[cfg(feature = "training")]
+    pub fn decisions(&self) -> &[Decision] {
+        self.decisions.as_deref().unwrap_or(&[])
+    }
 
-                self.to_draw = 0;
-                continue;
-            }
+    /// Sends spectators [`GameEvent::DebugHands`] after every turn, to trace what the players
+    /// were holding when they made their moves. Players never get it, so turning it on can't
+    /// change how anyone plays. Off by default, and not kept in snapshots.
+    pub fn set_reveal_hands(&mut self, reveal: bool) {
+        self.reveal_hands = reveal;
+    }
 
-            let turn = Turn {
-                full_hand: &player_hand.iter().copied().collect(),
-                playable_hand: playable_player_hand,
-                to_draw: self.to_draw,
-                last_card: *self.discard.last().unwrap(),
-            };
-
-            match current_player.execute_turn(&turn) {
-                TurnResult::Played(card) => {
-                    player_hand.remove(player_hand.iter().position(|c| *c == card).unwrap());
-                    self.discard.push(card);
-
-                    println!("{} played {}", current_player.name(), Self::get_colorized_card_name(card));
-
-                    match card {
-                        Card::Skip { .. } => {
-
-                            self.current_player = self.next_player();
-                            let next_player = &self.players.get_mut(self.current_player).unwrap().0;
-
-                            next_player.observe_turn_skip(None);
-
-                            println!("{}'s turn was skipped", next_player.name());
-                            continue;
-                        }
-                        Card::Reverse { .. } => {
-                            self.direction = match self.direction {
-                                Direction::Clockwise => Direction::CounterClockwise,
-                                Direction::CounterClockwise => Direction::Clockwise,
-                            };
-                        }
-                        Card::DrawTwo { .. } => {
-                            self.to_draw += 2;
-                        }
-                        Card::DrawFour { .. } => {
-                            self.to_draw += 4;
-                        }
-                        _ => {}
-                    }
-                }
-                TurnResult::Drew => {
-                    if self.to_draw == 0 {
-                        self.to_draw += 1;
-                    }
+    /// Opens a [`Window`] after every card for the other players to act out of turn, held open
+    /// for `timeout` when the game has a [`handle`](GameState::handle) that answers can come
+    /// through. Off by default, and not kept in snapshots or replays: what's heard only reaches
+    /// hooks, so the game plays out the same either way.
+    pub fn set_window(&mut self, timeout: Option<Duration>) {
+        self.window = timeout;
+    }
 
-                    let cards = &self.deck.draw_multiple(self.to_draw);
-                    player_hand.extend(cards);
+    /// Replaces `seat`'s hand with `cards`, to set up an exact situation. The cards come from
+    /// nowhere and the old ones go nowhere, so the game can end up with more or fewer than a
+    /// deck's worth, and its [`replay`](GameState::replay) no longer plays out the same. The
+    /// first [`play_turn`](GameState::play_turn) deals, so call this after it.
+    #[cfg(feature = "scenario")]
+    pub fn set_hand(&mut self, seat: usize, cards: impl IntoIterator<Item = Card>) {
+        self.players[seat].1 = cards.into_iter().collect();
+    }
 
-                    current_player.observe_turn_skip(Some(cards.iter().collect()));
+    /// The deck, to arrange what's drawn next with [`Deck::place_on_top`]. The same goes for it
+    /// as for [`set_hand`](GameState::set_hand).
+    #[cfg(feature = "scenario")]
+    pub fn deck_mut(&mut self) -> &mut Deck {
+        &mut self.deck
+    }
 
-                    println!("{} drew {} card(s)", current_player.name(), cards.len());
+    /// Stamps every event from now on with the time on `clock`, as the views that go with them
+    /// have it in [`GameView::at`]. Games don't keep time without one, and it isn't kept in
+    /// snapshots or replays.
+    pub fn set_clock(&mut self, clock: impl Clock + 'a) {
+        self.clock = Some(Box::new(clock));
+    }
 
-                    self.to_draw = 0;
-                }
-            };
+    /// What [`start`](GameState::start) waits on between turns. Defaults to [`NoPacing`], so a
+    /// game only waits when whoever shows it asks to.
+    pub fn set_pacer(&mut self, pacer: impl Pacer + 'a) {
+        self.pacer = Box::new(pacer);
+    }
 
-            if player_hand.is_empty() {
-                return current_player.name().to_string();
-            }
-        }
+    /// Paces the game with a [`Delay`] of `delay` after each turn.
+    #[cfg(feature = "std")]
+    pub fn set_turn_delay(&mut self, delay: Duration) {
+        self.set_pacer(Delay(delay));
     }
 
-pub fn get_colorized_card_name(card: Card) -> String {
-    use crate::card::CardColor::*;
-    match card {
-        Card::DrawFour { color: Some(color) } =>
-        {
-            let formatted_color = match color {
-                Red => format!("\x1b[31m{color}\x1b[0m"),
-                Blue => format!("\x1b[34m{color}\x1b[0m"),
-                Green => format!("\x1b[32m{color}\x1b[0m"),
-                Yellow => format!("\x1b[33m{color}\x1b[0m"),
-            };
-            format!("\x1b[31mDr\x1b[34maw \x1b[32mFo\x1b[33mur\x1b[0m ({formatted_color})")
-        },
-        Card::DrawTwo { color } =>
-        {
-            match color {
-                Red => format!("\x1b[31m{card}\x1b[0m"),
-                Blue => format!("\x1b[34m{card}\x1b[0m"),
-                Green => format!("\x1b[32m{card}\x1b[0m"),
-                Yellow => format!("\x1b[33m{card}\x1b[0m"),
-            }
-        },
-        Card::Skip { color } =>
-        {
-            match color {
-                Red => format!("\x1b[31m{card}\x1b[0m"),
-                Blue => format!("\x1b[34m{card}\x1b[0m"),
-                Green => format!("\x1b[32m{card}\x1b[0m"),
-                Yellow => format!("\x1b[33m{card}\x1b[0m"),
-            }
-        },
-        Card::Reverse { color } =>
-        {
-            match color {
-                Red => format!("\x1b[31m{card}\x1b[0m"),
-                Blue => format!("\x1b[34m{card}\x1b[0m"),
-                Green => format!("\x1b[32m{card}\x1b[0m"),
-                Yellow => format!("\x1b[33m{card}\x1b[0m"),
+    /// Plays the round until someone wins or it's aborted. While the game is paused it holds,
+    /// leaving a snapshot with its [`handle`](GameState::handle), until it's resumed or aborted
+    /// from there. If no handle has been made, nothing could resume it, so it resumes at once.
+    #[cfg(feature = "std")]
+    pub fn start(&mut self) -> GameOutcome {
+        loop {
+            if let Some(outcome) = self.play_turn() {
+                return outcome;
             }
-        },
-        Card::Numeric { color, .. } =>
-        {
-            match color {
-                Red => format!("\x1b[31m{card}\x1b[0m"),
-                Blue => format!("\x1b[34m{card}\x1b[0m"),
-                Green => format!("\x1b[32m{card}\x1b[0m"),
-                Yellow => format!("\x1b[33m{card}\x1b[0m"),
+
+            if self.paused {
+                self.hold();
+            } else {
+                let view = self.view_with(None, self.fingerprint());
+                self.pacer.pace(&view);
             }
-        },
-        Card::Wild { color: Some(color) } => {
-            let formatted_color = match color {
-                Red => format!("\x1b[31m{color}\x1b[0m"),
-                Blue => format!("\x1b[34m{color}\x1b[0m"),
-                Green => format!("\x1b[32m{color}\x1b[0m"),
-                Yellow => format!("\x1b[33m{color}\x1b[0m"),
-            };
-            format!("\x1b[31mW\x1b[34mi\x1b[32ml\x1b[33md\x1b[0m ({formatted_color})")
-        },
-        Card::Wild { color: None } => "\x1b[31mW\x1b[34mi\x1b[32ml\x1b[33md\x1b[0m".to_string(),
-        Card::DrawFour { color: None } => "\x1b[31mDr\x1b[34maw \x1b[32mFo\x1b[33mur\x1b[0m".to_string(),
+        }
     }
-}
 
-    fn get_playable_hand(hand: &[Card], card: &Card, to_draw: u8) -> Vec<Card> {
+    #[cfg(feature = "std")]
+    fn hold(&mut self) {
+        let Some(handle) = self.handle.clone() else {
+            return self.resume();
+        };
+
+        let mut control = handle.control();
+        control.snapshot = Some(self.snapshot());
 
-        if to_draw > 0 && matches!(card, Card::DrawTwo { .. } | Card::DrawFour { .. }) {
-            return hand.iter().filter(|c| **c == *card).copied().collect::<Vec<Card>>();
+        while control.paused && control.abort.is_none() {
+            control = handle.0.1.wait(control).unwrap_or_else(|poisoned| poisoned.into_inner());
         }
 
-        hand.iter()
-            .filter(|c| c.can_play_on(card))
-            .copied()
-            .collect::<Vec<Card>>()
+        control.snapshot = None;
     }
 
-    fn contains_special_card(hand: &[Card], card: &Card) -> bool {
-        hand.iter().any(|c| *c == *card)
-    }
+    /// Plays a single turn, dealing first if that hasn't happened yet. Returns the outcome once
+    /// someone has won or the game has been aborted; calling it again after that doesn't play
+    /// any further. Nothing is played while the game is paused.
+    pub fn play_turn(&mut self) -> Option<GameOutcome> {
+        if let Some(outcome) = self.outcome() {
+            return Some(outcome);
+        }
 
-    fn ensure_drawable_deck(deck: &mut Deck, discard: &mut Vec<Card>, to_draw: u8) {
-        if discard.len() < 2 && (deck.cards.len() as u8) >= to_draw {
-            return;
+        #[cfg(feature = "std")]
+        if let Some(outcome) = self.poll_handle() {
+            return Some(outcome);
         }
 
-        if (discard.len() as u8) >= to_draw {
-            let from_discard = discard.drain(..discard.len() - 1);
-            deck.cards.extend(from_discard);
-            deck.shuffle();
+        if self.paused {
+            return None;
+        }
 
-        } else {
-            // TODO: Investigate why this is necessary
-            discard.drain(..discard.len() - 1); // Keep the last four cards
+        if self.discard.is_empty() {
+            self.deal();
+            return None;
+        }
+
+        if self.rules.max_turns.is_some_and(|max| self.turns >= max) {
+            return Some(self.abort("turn limit reached"));
+        }
+
+        let actions = self.actions.len();
+        let outcome = self.take_turn();
 
-            // push a supplementary deck
-            let new_deck = Deck::generate();
-            deck.cards.extend(new_deck.cards);
+        if !self.hooks.is_empty() {
+            // A move the player chose is always logged; a forced draw isn't. A skip moves the
+            // current player on, so the seat comes from the log when there is an entry.
+            let action = self.actions.get(actions).copied();
+            let seat = action.map_or(self.current_player, |action| action.seat);
+            self.run_hooks(seat, |hook, view| hook.on_after_turn(seat, action.map(|action| action.result), view));
+        }
 
-            deck.shuffle();
+        if self.reveal_hands {
+            self.emit_debug_hands();
         }
+
+        outcome
     }
 
-    fn next_player(&self) -> usize{
-        let mut index = self.current_player;
-        let direction = self.direction;
+    /// Plays on as the events are taken, a turn at a time, for walking through a game with a
+    /// plain `for` loop. The events are the ones spectators are sent. Iteration ends with the
+    /// game, or once it's paused, when calling this again carries on.
+    pub fn events(&mut self) -> Events<'_, 'a> {
+        self.events = Some(VecDeque::new());
+        Events { game: self, over: false }
+    }
 
-        match direction {
-            Direction::Clockwise => {
-                index = (index + 1) % self.players.len()
-            },
-            Direction::CounterClockwise => {
+    fn take_turn(&mut self) -> Option<GameOutcome> {
+        Self::ensure_drawable_deck(&mut self.deck, &mut self.discard, self.to_draw, &mut self.rng);
+
+        self.current_player = self.next_player();
+        self.turns += 1;
+
+        let seat = self.current_player;
+        self.emit(|game| GameEvent::TurnStarted { player: game.id(seat) });
+        self.run_hooks(seat, |hook, view| hook.on_before_turn(seat, view));
+
+        // Play for the current player. The playable hand and drawn cards go into buffers kept
+        // between turns, so a turn doesn't allocate.
+        let last_card = *self.discard.last().unwrap();
+
+        // The table as it is before the player decides, including everything they can't see.
+        #[cfg(feature = "training")]
+        let table = self.decisions.is_some().then(|| (
+            self.view_with(Some(seat), self.fingerprint()),
+            self.players.iter().map(|(_, hand)| hand.to_vec()).collect::<Vec<Vec<Card>>>(),
+            self.deck.cards.clone(),
+        ));
+
+        let (current_player, player_hand) = &mut self.players[seat];
+
+        rules::fill_playable_either(&mut self.playable, player_hand, &last_card, self.second_color, self.to_draw, &self.rules);
 
-                if index == 0 {
-                    index = self.players.len() - 1;
-                } else {
-                    index -= 1;
+        if rules::must_draw(&self.playable, &last_card, self.to_draw) {
+            return self.draw_for(seat, last_card);
+        }
+
+        let turn = Turn {
+            full_hand: player_hand,
+            playable_hand: &mut self.playable,
+            to_draw: self.to_draw,
+            last_card,
+            drawn: None,
+        };
+
+        let result = current_player.execute_turn(&turn);
+
+        #[cfg(feature = "training")]
+        if let (Some(decisions), Some((view, hands, deck))) = (&mut self.decisions, table) {
+            decisions.push(Decision { seat, turn: self.turns, view, hands, deck, playable: self.playable.clone(), action: result });
+        }
+
+        match result {
+            TurnResult::Played(card) => {
+                if let Some(outcome) = self.play_card(seat, card) {
+                    return Some(outcome);
+                }
+            }
+            TurnResult::Drew => {
+                let draw = Draw::facing(self.to_draw);
+
+                if let Some(outcome) = self.draw_for(seat, last_card) {
+                    self.log(seat, result);
+                    return Some(outcome);
+                }
+
+                // A penalty is the whole turn; only a player's own draw can be played on from.
+                if draw == Draw::Single && self.rules.play_drawn && !self.out.contains(&seat) {
+                    self.log(seat, result);
+                    return self.offer_drawn(seat, last_card);
                 }
             }
         };
 
-        index
+        self.log(seat, result);
+        None
     }
-}
\ No newline at end of file
+
+    /// Puts a card from the player's hand on the discard pile and carries out what it does.
+    /// Returns the outcome if it was their last card.
+    fn play_card(&mut self, seat: usize, card: Card) -> Option<GameOutcome> {
+        let hand = &mut self.players[seat].1;
+        let held = hand.remove(&card);
+        assert!(held, "{card} was played but isn't in the player's hand");
+        self.discard.push(card);
+        self.active_color = card.color();
+        self.second_color = None;
+        self.tendencies[seat].played(&card);
+
+        let won = hand.is_empty();
+
+        let effect = rules::effect_of(&card, self.standing(), &self.rules).and_then(|effect| self.hook_effect(seat, effect));
+
+        if let Some(effect) = effect {
+            self.pending.push_back(PendingEffect { seat, effect });
+        }
+
+        self.emit(|game| GameEvent::Played { player: game.id(seat), card });
+
+        // Playing on, the card still does what it does to the players left.
+        if won && !self.plays_on() {
+            self.pending.clear();
+            return Some(self.win(seat, card));
+        }
+
+        if self.rules.two_color_wilds {
+            self.name_second_color(seat, card);
+        }
+
+        if let Some(timeout) = self.window {
+            self.open_window(seat, card, timeout);
+        }
+
+        self.resolve_pending();
+
+        // A Discard All can take the rest of the hand with it.
+        if !self.players[seat].1.is_empty() {
+            return None;
+        }
+
+        if !self.plays_on() {
+            return Some(self.win(seat, card));
+        }
+
+        self.finish(seat);
+        None
+    }
+
+    /// Asks the player of a wild for a color to name along with the one they chose.
+    fn name_second_color(&mut self, seat: usize, card: Card) {
+        let Some(first) = card.color().filter(|_| card.is_wild()) else {
+            return;
+        };
+
+        let view = self.view_with(Some(seat), self.fingerprint());
+        self.second_color = self.players[seat].0.choose_second_color(&view, first).filter(|second| *second != first);
+
+        if self.second_color.is_some() {
+            self.emit(|game| GameEvent::ColorsNamed { player: game.id(seat), colors: game.active_colors() });
+        }
+    }
+
+    /// Asks everyone else still in the round whether they act on `card`, and tells the hooks
+    /// about whoever is heard.
+    fn open_window(&mut self, seat: usize, card: Card, timeout: Duration) {
+        let window = Window { seat, card, cards_left: self.players[seat].1.len(), timeout };
+
+        #[cfg(feature = "std")]
+        if let Some(handle) = &self.handle {
+            handle.control().interjections.clear();
+        }
+
+        self.emit(|game| GameEvent::WindowOpened { player: game.id(seat), card });
+
+        let fingerprint = self.fingerprint();
+        let mut answers = vec![];
+
+        for other in (0..self.players.len()).filter(|other| *other != seat && !self.out.contains(other)) {
+            let view = self.view_with(Some(other), fingerprint);
+
+            if let Some(interjection) = self.players[other].0.interject(&window, &view) {
+                answers.push((other, interjection));
+            }
+        }
+
+        #[cfg(feature = "std")]
+        if let Some(handle) = &self.handle {
+            answers.extend(handle.wait_for_interjections(timeout).into_iter().filter(|(other, _)| {
+                *other != seat && *other < self.players.len() && !self.out.contains(other)
+            }));
+        }
+
+        let Some((by, interjection)) = window::settle(&answers, &self.turn_order()) else {
+            return;
+        };
+
+        self.emit(|game| GameEvent::Interjected { player: game.id(by), interjection });
+        self.run_hooks(by, |hook, view| hook.on_interjection(by, interjection, &window, view));
+    }
+
+    /// Whether the game carries on if the current player goes out.
+    fn plays_on(&self) -> bool {
+        self.rules.play_to_last && self.standing() > 2
+    }
+
+    fn finish(&mut self, seat: usize) {
+        self.out.push(seat);
+        self.finished.push(seat);
+
+        let place = self.finished.len();
+        self.emit(|game| GameEvent::Finished { player: game.id(seat), place });
+    }
+
+    fn win(&mut self, seat: usize, card: Card) -> GameOutcome {
+        self.log(seat, TurnResult::Played(card));
+
+        if self.rules.play_to_last {
+            self.finish(seat);
+        }
+
+        let winner = self.winner();
+        self.emit(|game| GameEvent::Won { player: game.id(winner) });
+        self.reveal();
+        GameOutcome::Won(self.summary())
+    }
+
+    /// Shows everyone's hand once the round is over.
+    fn reveal(&mut self) {
+        for player in self.player_summaries() {
+            self.emit(|_| GameEvent::Revealed { player: player.id, cards: player.hand, points: player.points });
+        }
+    }
+
+    /// How many players are still in the round.
+    fn standing(&self) -> usize {
+        self.players.len() - self.out.len()
+    }
+
+    /// Whoever went out first, or the current player if nobody has yet.
+    fn winner(&self) -> usize {
+        self.finished.first().copied().unwrap_or(self.current_player)
+    }
+
+    /// Carries out queued effects in order. A draw is added to what the next player faces, and
+    /// only taken when their turn comes, so they still get the chance to stack on it.
+    fn resolve_pending(&mut self) {
+        while let Some(PendingEffect { seat, effect }) = self.pending.pop_front() {
+            match effect {
+                Effect::TradeHands => {
+                    self.trade_hands(seat);
+                    continue;
+                }
+                Effect::SwapHands => {
+                    self.swap_hands(seat);
+                    continue;
+                }
+                Effect::DiscardAll(color) => {
+                    self.discard_all(seat, color);
+                    continue;
+                }
+                _ => {}
+            }
+
+            let mut order = self.turn_order();
+            let skipped = rules::apply_effect(effect, &mut order);
+
+            self.current_player = order.current;
+            self.direction = order.direction;
+            self.to_draw = order.to_draw;
+
+            if let Effect::Draw(count) = effect {
+                let card = *self.discard.last().unwrap();
+                self.draw_chain.push(StackedDraw { seat, card, count });
+
+                let to_draw = self.to_draw;
+                self.emit(|game| GameEvent::Stacked { player: game.id(seat), to_draw });
+            }
+
+            if let Some(skipped) = skipped {
+                self.players[skipped].0.observe_turn_skip(None);
+                self.emit(|game| GameEvent::Skipped { player: game.id(skipped) });
+            }
+        }
+    }
+
+    /// Swaps the player's hand with whoever holds the fewest cards, the first of them in the
+    /// direction of play if several do.
+    fn trade_hands(&mut self, seat: usize) {
+        let Some(with) = self.others(seat).min_by_key(|&other| self.players[other].1.len()) else {
+            return;
+        };
+
+        self.exchange(seat, with);
+    }
+
+    /// Swaps the player's hand with whoever they pick.
+    fn swap_hands(&mut self, seat: usize) {
+        let candidates = self.others(seat).collect::<Vec<usize>>();
+
+        let Some(&first) = candidates.first() else {
+            return;
+        };
+
+        let view = self.view_with(Some(seat), self.fingerprint());
+        let chosen = self.players[seat].0.choose_swap_target(&view, &candidates);
+        let with = if candidates.contains(&chosen) { chosen } else { first };
+
+        self.swapped_with = Some(with);
+        self.exchange(seat, with);
+    }
+
+    /// Everyone still in the round but `seat`, in the direction of play from it.
+    fn others(&self, seat: usize) -> impl Iterator<Item = usize> + use<'_, 'a> {
+        let seats = self.players.len();
+
+        core::iter::successors(Some(seat), move |&other| Some(rules::next_seat(other, seats, self.direction)))
+            .skip(1)
+            .take(seats - 1)
+            .filter(|other| !self.out.contains(other))
+    }
+
+    fn exchange(&mut self, seat: usize, with: usize) {
+        let hand = self.players[seat].1;
+        self.players[seat].1 = self.players[with].1;
+        self.players[with].1 = hand;
+        let avoiding = core::mem::take(&mut self.tendencies[seat].avoiding);
+        self.tendencies[seat].avoiding = core::mem::replace(&mut self.tendencies[with].avoiding, avoiding);
+
+        self.emit(|game| GameEvent::Traded { player: game.id(seat), with: game.id(with) });
+    }
+
+    /// Moves every card of `color` in the player's hand under the top of the discard pile,
+    /// where it's out of play until the deck is next rebuilt. Wild cards stay.
+    fn discard_all(&mut self, seat: usize, color: CardColor) {
+        let cards = self.players[seat].1.take_color(color);
+
+        if cards.is_empty() {
+            return;
+        }
+
+        let top = self.discard.pop();
+        self.discard.extend(cards.iter().copied());
+        self.discard.extend(top);
+        self.tendencies[seat].avoid(color);
+
+        self.emit(|game| GameEvent::Discarded { player: game.id(seat), cards });
+    }
+
+    /// Draws whatever is pending for the player, or their own draw if nothing is. Returns the
+    /// outcome if the draw put the player out and left a single player in.
+    fn draw_for(&mut self, seat: usize, last_card: Card) -> Option<GameOutcome> {
+        match Draw::facing(self.to_draw) {
+            Draw::Single => self.draw_single(seat, last_card),
+            Draw::Penalty(count) => self.draw_penalty(count),
+        }
+
+        self.take_drawn(seat)
+    }
+
+    /// The player's own draw: a card, or cards up to a playable one when the rules go on
+    /// drawing. With a launcher, it's one press of it.
+    fn draw_single(&mut self, seat: usize, last_card: Card) {
+        if let Some(color) = self.active_color {
+            self.tendencies[seat].drew_on(color);
+
+            if let Some(second) = self.second_color {
+                self.tendencies[seat].avoid(second);
+            }
+        }
+
+        if let Some(launcher) = self.rules.launcher {
+            self.drawn.clear();
+
+            for _ in 0..launcher.fire(&mut self.rng) {
+                self.drawn.extend(self.deck.draw());
+            }
+        } else if self.rules.draw_until_playable {
+            self.drawn.clear();
+
+            while let Some(card) = self.deck.draw() {
+                self.drawn.push(card);
+
+                if rules::can_play_either(&card, &last_card, self.second_color, 0, &self.rules) {
+                    break;
+                }
+            }
+        } else {
+            self.deck.draw_into(1, &mut self.drawn);
+        }
+    }
+
+    /// The `count` cards played on the player. With a launcher, each is a press of it.
+    fn draw_penalty(&mut self, count: u8) {
+        if let Some(launcher) = self.rules.launcher {
+            self.drawn.clear();
+
+            for _ in 0..count {
+                for _ in 0..launcher.fire(&mut self.rng) {
+                    self.drawn.extend(self.deck.draw());
+                }
+            }
+        } else {
+            self.deck.draw_into(count, &mut self.drawn);
+        }
+    }
+
+    /// Gives the player what was just drawn for them, as far as mercy lets it, and clears what
+    /// was pending.
+    fn take_drawn(&mut self, seat: usize) -> Option<GameOutcome> {
+        let mut spared = 0;
+
+        if let Some(Mercy::Cap(limit)) = self.rules.mercy {
+            let room = usize::from(limit).saturating_sub(self.players[seat].1.len());
+
+            // Cards that aren't taken go back on the deck as they came off it.
+            while self.drawn.len() > room {
+                self.deck.cards.extend(self.drawn.pop());
+                spared += 1;
+            }
+        }
+
+        let (player, hand) = &mut self.players[seat];
+        hand.extend(self.drawn.iter().copied());
+        player.observe_turn_skip(Some(&self.drawn));
+
+        self.to_draw = 0;
+        self.draw_chain.clear();
+        let count = self.drawn.len();
+        self.emit(|game| GameEvent::Drew { player: game.id(seat), count });
+
+        if spared > 0 {
+            self.emit(|game| GameEvent::Spared { player: game.id(seat), count: spared });
+        }
+
+        match self.rules.mercy {
+            Some(Mercy::Eliminate(limit)) if self.players[seat].1.len() > usize::from(limit) => self.eliminate(seat),
+            _ => None,
+        }
+    }
+
+    /// Takes the player out of the round, putting their hand under the top of the discard pile.
+    /// Returns the outcome if only one player is left, who wins.
+    fn eliminate(&mut self, seat: usize) -> Option<GameOutcome> {
+        let hand = core::mem::take(&mut self.players[seat].1);
+        let top = self.discard.pop();
+        self.discard.extend(hand.iter());
+        self.discard.extend(top);
+
+        self.out.push(seat);
+        self.emit(|game| GameEvent::Eliminated { player: game.id(seat) });
+        self.last_standing()
+    }
+
+    /// Once everyone else is out, makes the one player left the winner.
+    fn last_standing(&mut self) -> Option<GameOutcome> {
+        if self.standing() > 1 {
+            return None;
+        }
+
+        self.current_player = (0..self.players.len()).find(|seat| !self.out.contains(seat))?;
+        let winner = self.winner();
+        self.emit(|game| GameEvent::Won { player: game.id(winner) });
+        self.reveal();
+        Some(GameOutcome::Won(self.summary()))
+    }
+
+    /// Lets a player who has just drawn play the card they drew, last if there were several.
+    /// Keeping it is logged as drawing again.
+    fn offer_drawn(&mut self, seat: usize, last_card: Card) -> Option<GameOutcome> {
+        let drawn = *self.drawn.last()?;
+
+        if !rules::can_play_either(&drawn, &last_card, self.second_color, 0, &self.rules) {
+            return None;
+        }
+
+        self.playable.clear();
+        self.playable.push(drawn);
+
+        let (player, hand) = &mut self.players[seat];
+        let turn = Turn {
+            full_hand: hand,
+            playable_hand: &mut self.playable,
+            to_draw: 0,
+            last_card,
+            drawn: Some(drawn),
+        };
+
+        let result = player.execute_turn(&turn);
+
+        if let TurnResult::Played(card) = result {
+            if let Some(outcome) = self.play_card(seat, card) {
+                return Some(outcome);
+            }
+        }
+
+        self.log(seat, result);
+        None
+    }
+
+    fn deal(&mut self) {
+        self.emit(|game| GameEvent::Setup {
+            rules: game.rules,
+            seating: game.shuffled_seating(),
+            first_player: game.first_player,
+            opening: game.opening,
+            hand_sizes: game.hand_sizes.clone(),
+            engine: ENGINE_VERSION,
+        });
+
+        if let Some(first) = self.first_player {
+            self.current_player = rules::next_seat(first % self.players.len(), self.players.len(), Direction::CounterClockwise);
+        }
+
+        let extra = Card::WildSkipEveryone { color: None };
+        self.deck.cards.extend(core::iter::repeat_n(extra, self.rules.skip_everyone_cards.into()));
+
+        for color in CardColor::ALL {
+            self.deck.cards.extend(core::iter::repeat_n(Card::TradeHands { color }, self.rules.trade_hands_cards.into()));
+            self.deck.cards.extend(core::iter::repeat_n(Card::DiscardAll { color }, self.rules.discard_all_cards.into()));
+        }
+
+        // Taken out before the shuffle, so it's there whatever gets dealt.
+        let carried = self.opening.wild.filter(Card::is_wild).and_then(|wild| {
+            let index = self.deck.cards.iter().rposition(|card| card.is_equivalent(&wild))?;
+            self.deck.cards.remove(index);
+            Some(wild)
+        });
+
+        self.deck.shuffle(&mut self.rng);
+
+        // Dealt through the drawn-card buffer, so a deal doesn't allocate either.
+        for seat in 0..self.players.len() {
+            let size = self.hand_size(seat);
+            self.deck.draw_into(size, &mut self.drawn);
+            self.players[seat].1.extend(self.drawn.iter().copied());
+        }
+
+        match carried {
+            Some(top_card) => {
+                self.discard.push(top_card);
+                self.active_color = top_card.color();
+                self.emit(|_| GameEvent::Started { top_card });
+            }
+            None => self.turn_up(),
+        }
+
+        self.to_draw = self.to_draw.saturating_add(self.opening.draw);
+        self.dealt = self.fingerprint();
+    }
+
+    /// Turns over the first card of the discard pile, putting back any it can't start on.
+    fn turn_up(&mut self) {
+        let reshuffled = |card: &Card, rules: &Rules| card.is_wild() || (matches!(card, Card::Skip { .. }) && !rules.first_card_effects);
+
+        loop {
+            let top_card = self.deck.draw().expect("the deal leaves a card to turn up");
+
+            // A deal that leaves nothing else starts on what's there, rather than going round
+            // forever putting it back.
+            let stuck = self.deck.cards.iter().all(|card| reshuffled(card, &self.rules));
+
+            match top_card {
+                _ if top_card.is_wild() && !stuck => {
+                    self.deck.reinsert_random(top_card, &mut self.rng);
+                }
+                Card::Skip { .. } if !self.rules.first_card_effects && !stuck => {
+                    self.deck.reinsert_random(top_card, &mut self.rng);
+                }
+                _ => {
+                    self.discard.push(top_card);
+                    self.active_color = top_card.color();
+                    self.emit(|_| GameEvent::Started { top_card });
+
+                    if self.rules.first_card_effects {
+                        self.apply_first_card(top_card);
+                    }
+
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Carries out an action card turned up by the deal. The current player dealt, so the next
+    /// one would go first: a Skip passes over them, a Reverse sends play back to the dealer, and
+    /// a Draw Two leaves them to draw before anyone plays. Cards that change hands do nothing here,
+    /// since nobody played them.
+    fn apply_first_card(&mut self, top_card: Card) {
+        let Some(effect) = rules::effect_of(&top_card, self.players.len(), &self.rules) else {
+            return;
+        };
+
+        if matches!(effect, Effect::TradeHands | Effect::SwapHands | Effect::DiscardAll(_)) {
+            return;
+        }
+
+        let dealer = self.current_player;
+
+        if effect == Effect::Reverse {
+            // Turns start by moving on from the current player, so stand in the first player's
+            // seat to face the dealer once the direction changes.
+            self.current_player = self.next_player();
+        }
+
+        self.pending.push_back(PendingEffect { seat: dealer, effect });
+        self.resolve_pending();
+    }
+
+    fn log(&mut self, seat: usize, result: TurnResult) {
+        self.actions.push(ReplayAction {
+            seat,
+            result,
+            fingerprint: self.fingerprint(),
+            target: self.swapped_with.take(),
+            second_color: self.second_color.filter(|_| matches!(result, TurnResult::Played(card) if card.is_wild())),
+        });
+    }
+
+    /// Every card at the table: the deck, the discard pile and each hand.
+    #[cfg(feature = "arbitrary")]
+    pub(crate) fn cards(&self) -> impl Iterator<Item = Card> + use<'_, 'a> {
+        self.deck.cards.iter()
+            .chain(&self.discard)
+            .copied()
+            .chain(self.players.iter().flat_map(|(_, hand)| hand.iter()))
+    }
+
+    pub(crate) fn dealt(&self) -> u64 {
+        self.dealt
+    }
+
+    pub(crate) fn actions(&self) -> &[ReplayAction] {
+        &self.actions
+    }
+
+    /// Everything needed to play this game again: the seed and every move made so far. Only
+    /// seeded games can be replayed.
+    pub fn replay(&self) -> Option<Replay> {
+        Some(Replay {
+            seed: self.seed?,
+            players: self.players.iter().map(|(player, _)| player.name().to_string()).collect(),
+            dealt: self.dealt,
+            actions: self.actions.clone(),
+            rules: self.rules,
+            seating: self.shuffled_seating(),
+            first_player: self.first_player,
+            opening: self.opening,
+            hand_sizes: self.hand_sizes.clone(),
+            engine: ENGINE_VERSION,
+        })
+    }
+
+    /// Stops the game where it is, without a winner. Spectators and players see
+    /// [`GameEvent::Aborted`], and the outcome has everyone's hand as it was left. A game that
+    /// has already ended keeps its outcome.
+    pub fn abort(&mut self, reason: impl Into<String>) -> GameOutcome {
+        if let Some(outcome) = self.outcome() {
+            return outcome;
+        }
+
+        let reason = reason.into();
+        self.aborted = Some(reason.clone());
+        self.emit(|_| GameEvent::Aborted { reason });
+        self.reveal();
+
+        self.outcome().unwrap()
+    }
+
+    /// Takes `seat` out of the game between turns, as when someone leaves a networked game for
+    /// good, and does with their hand what `hand` says. A pending draw they were about to face
+    /// goes with them. Play passes over the seat from then on, so whoever would have played
+    /// after them plays next, and they rank as eliminated.
+    ///
+    /// Leaving isn't a move, so the game's [`replay`](GameState::replay) won't play back past
+    /// it, though [`from_events`](GameState::from_events) will. Returns the outcome if only one
+    /// player is left, who wins, or if the game was already over.
+    pub fn remove_player(&mut self, seat: usize, hand: LeftHand) -> Option<GameOutcome> {
+        if let Some(outcome) = self.outcome() {
+            return Some(outcome);
+        }
+
+        if self.out.contains(&seat) {
+            return None;
+        }
+
+        if self.next_player() == seat {
+            self.to_draw = 0;
+            self.draw_chain.clear();
+        }
+
+        let cards = core::mem::take(&mut self.players[seat].1);
+        self.out.push(seat);
+
+        match hand {
+            LeftHand::ShuffleIn => {
+                self.deck.put_back(cards.iter());
+                self.deck.shuffle(&mut self.rng);
+            }
+            LeftHand::Distribute => {
+                let seats = self.players.len();
+                let standing = core::iter::successors(Some(seat), |&next| Some(rules::next_seat(next, seats, self.direction)))
+                    .skip(1)
+                    .take(seats)
+                    .filter(|next| !self.out.contains(next))
+                    .collect::<Vec<usize>>();
+
+                for (card, to) in cards.iter().zip(standing.iter().cycle()) {
+                    self.players[*to].1.insert(card);
+                }
+            }
+            LeftHand::Discard => {
+                let top = self.discard.pop();
+                self.discard.extend(cards.iter());
+                self.discard.extend(top);
+            }
+        }
+
+        self.emit(|game| GameEvent::PlayerLeft { player: game.id(seat), hand });
+        self.last_standing()
+    }
+
+    /// Stops play after the current turn, until [`resume`](GameState::resume) is called.
+    /// Everyone sees [`GameEvent::Paused`]. Games that have ended can't be paused.
+    pub fn pause(&mut self) {
+        if self.paused || self.outcome().is_some() {
+            return;
+        }
+
+        self.paused = true;
+
+        #[cfg(feature = "std")]
+        if let Some(handle) = &self.handle {
+            handle.pause();
+        }
+
+        self.emit(|_| GameEvent::Paused);
+    }
+
+    pub fn resume(&mut self) {
+        if !self.paused {
+            return;
+        }
+
+        self.paused = false;
+
+        #[cfg(feature = "std")]
+        if let Some(handle) = &self.handle {
+            handle.resume();
+        }
+
+        self.emit(|_| GameEvent::Resumed);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// A handle that controls the game from elsewhere, such as another thread driving
+    /// [`start`](GameState::start) or a task that's been cancelled. Every call returns the same
+    /// handle.
+    #[cfg(feature = "std")]
+    pub fn handle(&mut self) -> GameHandle {
+        let paused = self.paused;
+
+        self.handle
+            .get_or_insert_with(|| {
+                let handle = GameHandle::default();
+                handle.control().paused = paused;
+                handle
+            })
+            .clone()
+    }
+
+    /// Applies whatever the handle has asked for since the last turn.
+    #[cfg(feature = "std")]
+    fn poll_handle(&mut self) -> Option<GameOutcome> {
+        let (abort, paused, emotes) = {
+            let mut control = self.handle.as_ref()?.control();
+            (control.abort.clone(), control.paused, core::mem::take(&mut control.emotes))
+        };
+
+        for (seat, to, emote) in emotes {
+            let _ = self.emote(seat, to, emote);
+        }
+
+        if let Some(reason) = abort {
+            return Some(self.abort(reason));
+        }
+
+        if paused {
+            self.pause();
+        } else {
+            self.resume();
+        }
+
+        None
+    }
+
+    /// How many emotes each seat may send, or `None` to turn them off. Defaults to
+    /// [`EmoteLimit::default`].
+    pub fn set_emote_limit(&mut self, limit: Option<EmoteLimit>) {
+        self.emote_limit = limit;
+    }
+
+    /// Sends an emote from `seat` to everyone, or only to the seat `to`. Everyone else at the
+    /// table sees it as [`GameEvent::Emote`]; spectators only see emotes sent to everyone.
+    pub fn emote(&mut self, seat: usize, to: Option<usize>, emote: Emote) -> Result<(), EmoteError> {
+        let limit = self.emote_limit.ok_or(EmoteError::Disabled)?;
+
+        if let Some(unknown) = [Some(seat), to].into_iter().flatten().find(|seat| *seat >= self.players.len()) {
+            return Err(EmoteError::UnknownSeat(unknown));
+        }
+
+        let turns = self.turns;
+        self.emotes.retain(|(_, turn)| turn + limit.turns > turns);
+
+        if self.emotes.iter().filter(|(sender, _)| *sender == seat).count() >= limit.count {
+            return Err(EmoteError::RateLimited);
+        }
+
+        self.emotes.push((seat, turns));
+
+        let event = GameEvent::Emote { player: self.id(seat), to: to.map(|to| self.id(to)), emote };
+
+        match to {
+            Some(to) => self.emit_to(&[seat, to], &event),
+            None => self.emit(|_| event),
+        }
+
+        Ok(())
+    }
+
+    /// Everything needed to carry on this game later with
+    /// [`from_snapshot`](GameState::from_snapshot), such as after a restart.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            players: self.players.iter().map(|(player, _)| player.name().to_string()).collect(),
+            hands: self.players.iter().map(|(_, hand)| hand.to_vec()).collect(),
+            deck: self.deck.cards.clone(),
+            discard: self.discard.clone(),
+            current_player: self.current_player,
+            direction: self.direction,
+            to_draw: self.to_draw,
+            draw_chain: self.draw_chain.clone(),
+            turns: self.turns,
+            seed: self.seed,
+            stream_position: match &self.rng {
+                Shuffler::Seeded(rng) => Some(rng.get_word_pos()),
+                Shuffler::Custom(_) => None,
+            },
+            dealt: self.dealt,
+            actions: self.actions.clone(),
+            paused: self.paused,
+            aborted: self.aborted.clone(),
+            rules: self.rules,
+            seating: self.shuffled_seating(),
+            first_player: self.first_player,
+            opening: self.opening,
+            hand_sizes: self.hand_sizes.clone(),
+            out: self.out.clone(),
+            finished: self.finished.clone(),
+            tendencies: self.tendencies.clone(),
+            second_color: self.second_color,
+            engine: ENGINE_VERSION,
+            fingerprint: self.fingerprint(),
+        }
+    }
+
+    /// The seating for snapshots and replays, which leave it empty if the seats weren't shuffled.
+    fn shuffled_seating(&self) -> Vec<usize> {
+        match self.seats_shuffled {
+            true => self.seating.clone(),
+            false => vec![],
+        }
+    }
+
+    /// How the game ended, if it has.
+    fn outcome(&self) -> Option<GameOutcome> {
+        if let Some(reason) = &self.aborted {
+            return Some(GameOutcome::Aborted {
+                reason: reason.clone(),
+                players: self.player_summaries(),
+                turns: self.turns,
+            });
+        }
+
+        let current = self.current_player;
+        let won = !self.discard.is_empty() && (self.standing() <= 1 || !self.out.contains(&current) && self.players[current].1.is_empty());
+        won.then(|| GameOutcome::Won(self.summary()))
+    }
+
+    fn player_summaries(&self) -> Vec<PlayerSummary> {
+        self.players
+            .iter()
+            .enumerate()
+            .map(|(seat, (_, hand))| PlayerSummary {
+                id: self.id(seat),
+                name: self.name(seat),
+                hand: hand.to_vec(),
+                points: hand.iter().map(|card| self.rules.points.of(&card)).sum(),
+                dealt: self.hand_size(seat),
+            })
+            .collect()
+    }
+
+    /// Scores the round for its winner, once it's over.
+    ///
+    /// Everyone is ranked by when they went out, then those still holding cards by what their
+    /// hands are worth, the last player in when the rest were eliminated going first, and then
+    /// whoever was eliminated, latest first.
+    fn summary(&self) -> RoundSummary {
+        let players = self.player_summaries();
+        let winner = self.winner();
+
+        let mut holding = (0..players.len()).filter(|seat| !self.out.contains(seat)).collect::<Vec<usize>>();
+        holding.sort_by_key(|&seat| (seat != winner, players[seat].points));
+
+        let mut ranking = self.finished.clone();
+        ranking.extend(holding);
+        ranking.extend(self.out.iter().rev().filter(|seat| !self.finished.contains(seat)));
+
+        RoundSummary {
+            winner: players[winner].name.clone(),
+            winner_id: players[winner].id,
+            // The last player left when the rest were eliminated still holds cards of their own.
+            points: players.iter().enumerate().filter(|(seat, _)| *seat != winner).map(|(_, player)| player.points).sum(),
+            players,
+            turns: self.turns,
+            ranking,
+            last_card: self.discard.last().copied(),
+            left_to_draw: (self.to_draw > 0).then(|| (self.next_player(), self.to_draw)),
+        }
+    }
+
+    /// The color the next card has to match: the top card's, or the one chosen for it if it's a
+    /// wild. `None` before the deal, or after a wild played without a color, which only another
+    /// wild can cover unless the rules have [`open_wilds`](Rules::open_wilds).
+    pub fn active_color(&self) -> Option<CardColor> {
+        self.active_color
+    }
+
+    /// Every color the next card can match: the [active color](GameState::active_color), and
+    /// the second one named for a wild under [`Rules::two_color_wilds`].
+    pub fn active_colors(&self) -> ColorSet {
+        self.active_color.into_iter().chain(self.second_color).collect()
+    }
+
+    /// Builds the view of the table for `seat`, or the public view when `seat` is `None`.
+    pub fn view(&self, seat: Option<usize>) -> GameView {
+        self.view_with(seat, self.fingerprint())
+    }
+
+    fn view_with(&self, seat: Option<usize>, fingerprint: u64) -> GameView {
+        GameView {
+            seat,
+            hand: seat.map_or(vec![], |seat| self.players[seat].1.to_vec()),
+            top_card: self.discard.last().copied(),
+            active_color: self.active_color,
+            second_color: self.second_color,
+            players: self.players
+                .iter()
+                .enumerate()
+                .map(|(seat, (_, hand))| PlayerView {
+                    id: self.id(seat),
+                    name: self.name(seat),
+                    cards: hand.len(),
+                    out: self.out.contains(&seat),
+                    tendencies: self.tendencies[seat].clone(),
+                })
+                .collect(),
+            current_player: self.current_player,
+            direction: self.direction,
+            to_draw: self.to_draw,
+            draw_chain: self.draw_chain.clone(),
+            draw_pile: self.rules.pile_sizes.show(self.deck.cards.len()),
+            discard_pile: self.rules.pile_sizes.show(self.discard.len()),
+            fingerprint,
+            at: self.at,
+        }
+    }
+
+    /// `seat`'s name, numbered from the second player who has it on, so a table of bots that
+    /// all go by the same name can still be told apart.
+    fn name(&self, seat: usize) -> String {
+        let name = self.players[seat].0.name();
+        let same = |(player, _): &&(&mut dyn Player, Hand)| player.name() == name;
+
+        match self.players.iter().filter(same).count() {
+            1 => name.to_string(),
+            _ => format!("{name} ({})", self.players[..seat].iter().filter(same).count() + 1),
+        }
+    }
+
+    fn id(&self, seat: usize) -> PlayerId {
+        PlayerId(self.seating[seat] as u32)
+    }
+
+    /// The [`PlayerId`] of whoever is in `seat`.
+    pub fn player_id(&self, seat: usize) -> PlayerId {
+        self.id(seat)
+    }
+
+    /// Where the player with `id` sits, if they're at the table.
+    pub fn seat_of(&self, id: PlayerId) -> Option<usize> {
+        self.seating.iter().position(|&from| from as u32 == id.0)
+    }
+
+    /// A hash of the whole table: deck order, hands, discard pile, whose turn it is, direction
+    /// and pending draws. Two games in the same state always have the same fingerprint, on any
+    /// platform and build, so clients and servers can compare them to spot a desync.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hash = Fnv::new();
+
+        hash.write_cards(&self.deck.cards);
+        hash.write_cards(&self.discard);
+
+        for (_, hand) in &self.players {
+            hash.write_hand(hand);
+        }
+
+        hash.write(&(self.current_player as u32).to_le_bytes());
+        hash.write(&[self.direction as u8, self.to_draw]);
+        hash.write(&self.turns.to_le_bytes());
+
+        for seat in &self.out {
+            hash.write(&(*seat as u32).to_le_bytes());
+        }
+
+        // Only hashed when there is one, so games without it keep their fingerprints.
+        if let Some(color) = self.second_color {
+            hash.write(&[color as u8]);
+        }
+
+        hash.0
+    }
+
+    /// Sends an event to everyone watching. The event and views are only built when someone
+    /// is, so games between bots don't pay for them.
+    fn emit(&mut self, event: impl FnOnce(&Self) -> GameEvent) {
+        let watched = self.players.iter().any(|(player, _)| player.wants_events());
+
+        if !watched && self.spectators.is_empty() && self.events.is_none() {
+            return;
+        }
+
+        let event = event(self);
+        let fingerprint = self.fingerprint();
+
+        if let Some(clock) = &mut self.clock {
+            self.at = Some(clock.now());
+        }
+
+        let views = (0..self.players.len())
+            .map(|seat| self.players[seat].0.wants_events().then(|| self.view_with(Some(seat), fingerprint)))
+            .collect::<Vec<Option<GameView>>>();
+
+        for ((player, _), view) in self.players.iter_mut().zip(&views) {
+            if let Some(view) = view {
+                player.observe_event(&event, view);
+            }
+        }
+
+        if !self.spectators.is_empty() {
+            let view = self.view_with(None, fingerprint);
+
+            for spectator in self.spectators.iter_mut() {
+                spectator.observe_event(&event, &view);
+            }
+        }
+
+        if let Some(events) = &mut self.events {
+            events.push_back(event);
+        }
+    }
+
+    fn run_hooks(&mut self, seat: usize, mut call: impl FnMut(&mut dyn Hook, &GameView)) {
+        if self.hooks.is_empty() {
+            return;
+        }
+
+        let view = self.view_with(Some(seat), self.fingerprint());
+
+        for hook in self.hooks.iter_mut() {
+            call(&mut **hook, &view);
+        }
+    }
+
+    fn hook_effect(&mut self, seat: usize, effect: Effect) -> Option<Effect> {
+        if self.hooks.is_empty() {
+            return Some(effect);
+        }
+
+        let view = self.view_with(Some(seat), self.fingerprint());
+        self.hooks.iter_mut().try_fold(effect, |effect, hook| hook.on_effect_applied(effect, &view))
+    }
+
+    fn emit_debug_hands(&mut self) {
+        if self.spectators.is_empty() && self.events.is_none() {
+            return;
+        }
+
+        let event = GameEvent::DebugHands {
+            hands: self.players.iter().map(|(_, hand)| hand.iter().collect()).collect(),
+        };
+        let view = self.view_with(None, self.fingerprint());
+
+        for spectator in self.spectators.iter_mut() {
+            spectator.observe_event(&event, &view);
+        }
+
+        if let Some(events) = &mut self.events {
+            events.push_back(event);
+        }
+    }
+
+    /// Sends an event to some of the players only, and to none of the spectators.
+    fn emit_to(&mut self, seats: &[usize], event: &GameEvent) {
+        let fingerprint = self.fingerprint();
+
+        for seat in 0..self.players.len() {
+            if seats.contains(&seat) && self.players[seat].0.wants_events() {
+                let view = self.view_with(Some(seat), fingerprint);
+                self.players[seat].0.observe_event(event, &view);
+            }
+        }
+    }
+
+    fn ensure_drawable_deck(deck: &mut Deck, discard: &mut Vec<Card>, to_draw: u8, rng: &mut dyn RngCore) {
+        if discard.len() < 2 && deck.cards.len() >= to_draw as usize {
+            return;
+        }
+
+        let from_discard = discard.drain(..discard.len() - 1);
+        deck.put_back(from_discard);
+
+        if deck.cards.len() < to_draw as usize {
+            // Not enough cards left in play to cover the draw, so bring in a supplementary deck.
+            deck.put_back(Deck::new_deck());
+        }
+
+        deck.shuffle(rng);
+    }
+
+    fn next_player(&self) -> usize {
+        self.turn_order().next()
+    }
+
+    fn turn_order(&self) -> TurnOrder {
+        TurnOrder {
+            current: self.current_player,
+            seats: self.players.len(),
+            direction: self.direction,
+            to_draw: self.to_draw,
+            out: self.out.clone(),
+        }
+    }
+}
+
+/// Puts seats recorded after [`GameState::shuffle_seats`] back where they were before it, so the
+/// same shuffle puts them where they were recorded. A damaged seating can leave some out; they go
+/// at the end, so the game comes out different instead of short of a seat.
+pub(crate) fn unshuffle<T>(seats: Vec<T>, seating: &[usize]) -> Vec<T> {
+    let mut recorded = seats.into_iter().map(Some).collect::<Vec<Option<T>>>();
+    let mut unshuffled = (0..recorded.len())
+        .filter_map(|from| seating.iter().position(|&seat| seat == from))
+        .filter_map(|seat| recorded.get_mut(seat)?.take())
+        .collect::<Vec<T>>();
+
+    unshuffled.extend(recorded.into_iter().flatten());
+    unshuffled
+}
+
+/// FNV-1a, since std's hashers aren't guaranteed to be stable between releases.
+struct Fnv(u64);
+
+impl Fnv {
+    fn new() -> Fnv {
+        Fnv(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_hand(&mut self, hand: &Hand) {
+        self.write(&(hand.len() as u32).to_le_bytes());
+
+        for (card, count) in hand.counts() {
+            self.write(&[card.code(), count]);
+        }
+    }
+
+    fn write_cards(&mut self, cards: &[Card]) {
+        self.write(&(cards.len() as u32).to_le_bytes());
+
+        for card in cards {
+            self.write(&[card_code(card)]);
+        }
+    }
+}
+
+/// A distinct byte for every card, wild cards' chosen colors included.
+pub(crate) fn card_code(card: &Card) -> u8 {
+    let color = |color: &CardColor| match color {
+        CardColor::Red => 0,
+        CardColor::Green => 1,
+        CardColor::Blue => 2,
+        CardColor::Yellow => 3,
+    };
+
+    match card {
+        Card::Numeric { color: c, value } => color(c) << 4 | value.number(),
+        Card::Skip { color: c } => color(c) << 4 | 10,
+        Card::Reverse { color: c } => color(c) << 4 | 11,
+        Card::DrawTwo { color: c } => color(c) << 4 | 12,
+        Card::Wild { color: c } => 0x40 | c.as_ref().map_or(4, color),
+        Card::DrawFour { color: c } => 0x50 | c.as_ref().map_or(4, color),
+        Card::WildSkipEveryone { color: c } => 0x60 | c.as_ref().map_or(4, color),
+        Card::TradeHands { color: c } => 0x70 | color(c),
+        Card::DiscardAll { color: c } => 0x80 | color(c),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::CardValue;
+
+    const RED_SEVEN: Card = Card::Numeric { color: CardColor::Red, value: CardValue::Seven };
+    const RED_TWO: Card = Card::Numeric { color: CardColor::Red, value: CardValue::Two };
+    const BLUE_THREE: Card = Card::Numeric { color: CardColor::Blue, value: CardValue::Three };
+    const GREEN_FIVE: Card = Card::Numeric { color: CardColor::Green, value: CardValue::Five };
+
+    /// Plays the first card it's offered, and draws when it's offered none.
+    struct FirstOffered;
+
+    impl Player for FirstOffered {
+        fn name(&self) -> &str {
+            "First offered"
+        }
+
+        fn execute_turn(&mut self, turn: &Turn) -> TurnResult {
+            turn.playable_hand.first().map_or(TurnResult::Drew, |card| TurnResult::Played(*card))
+        }
+
+        fn observe_turn(&self, _other: &dyn Player, _card: &Card) {}
+
+        fn observe_turn_skip(&self, _observed_cards: Option<&[Card]>) {}
+    }
+
+    #[derive(Default)]
+    struct Heard(Vec<GameEvent>);
+
+    impl Spectator for Heard {
+        fn observe_event(&mut self, event: &GameEvent, _view: &GameView) {
+            if matches!(event, GameEvent::Drew { .. } | GameEvent::Played { .. }) {
+                self.0.push(event.clone());
+            }
+        }
+    }
+
+    /// Seat 0 to play on `top` with `to_draw` pending, holding nothing that goes on a red seven,
+    /// with a red two to be drawn next.
+    fn position(top: Card, to_draw: u8) -> Position {
+        Position {
+            hands: vec![vec![BLUE_THREE, GREEN_FIVE], vec![GREEN_FIVE]],
+            deck: vec![GREEN_FIVE, BLUE_THREE, RED_TWO],
+            discard: vec![top],
+            to_play: 0,
+            direction: Direction::Clockwise,
+            to_draw,
+            rules: Rules::official(),
+            out: vec![],
+            seed: 0,
+        }
+    }
+
+    #[test]
+    fn an_own_draw_is_one_card_that_can_be_played_straight_away() {
+        let mut players = [FirstOffered, FirstOffered];
+        let mut heard = Heard::default();
+        let mut game = GameState::from_position(players.iter_mut().map(|player| player as &mut dyn Player).collect(), &position(RED_SEVEN, 0)).unwrap();
+        game.add_spectator(&mut heard);
+
+        assert_eq!(game.play_turn(), None);
+        drop(game);
+
+        let me = PlayerId(0);
+        assert_eq!(heard.0, [GameEvent::Drew { player: me, count: 1 }, GameEvent::Played { player: me, card: RED_TWO }]);
+    }
+
+    #[test]
+    fn a_penalty_is_the_whole_turn() {
+        let mut players = [FirstOffered, FirstOffered];
+        let mut heard = Heard::default();
+        let mut game = GameState::from_position(players.iter_mut().map(|player| player as &mut dyn Player).collect(), &position(Card::DrawTwo { color: CardColor::Red }, 2)).unwrap();
+        game.add_spectator(&mut heard);
+
+        assert_eq!(game.play_turn(), None);
+
+        // The red two it drew would go on the Draw Two, but taking a penalty ends the turn.
+        let view = game.view(Some(0));
+        assert_eq!((view.hand.len(), view.to_draw, view.current_player), (4, 0, 0));
+        assert_eq!(game.upcoming_turn_order(1), [1]);
+        drop(game);
+
+        assert_eq!(heard.0, [GameEvent::Drew { player: PlayerId(0), count: 2 }]);
+    }
+
+    #[test]
+    fn the_last_player_left_scores_nothing_for_their_own_hand() {
+        let mut players = [FirstOffered, FirstOffered];
+        let mut game = GameState::from_position(players.iter_mut().map(|player| player as &mut dyn Player).collect(), &position(RED_SEVEN, 0)).unwrap();
+
+        let Some(GameOutcome::Won(summary)) = game.remove_player(1, LeftHand::Discard) else {
+            panic!("the last player left wins");
+        };
+
+        assert_eq!(summary.winner_id, PlayerId(0));
+        assert_eq!(summary.players[0].points, 8);
+        assert_eq!(summary.points, 0);
+    }
+}
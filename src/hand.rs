@@ -0,0 +1,134 @@
+use alloc::vec::Vec;
+
+use crate::card::{Card, CardColor};
+use crate::packed::{PackedCard, PackedHand};
+
+/// A player's cards, stored as a count of each kind so lookups and removals don't scan, and
+/// so a hand never allocates however many cards end up in it.
+///
+/// Cards iterate grouped by color and then by rank, which is also how they're best shown.
+/// Wild cards lose any color chosen for them when they're added.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct Hand {
+    counts: PackedHand,
+    /// A bit for every kind held at least once.
+    present: u64,
+}
+
+impl Hand {
+    pub fn new() -> Hand {
+        Hand::default()
+    }
+
+    pub fn insert(&mut self, card: Card) {
+        let card = PackedCard::from(card);
+
+        self.counts.insert(card);
+        self.present |= 1 << card.kind();
+    }
+
+    /// Removes one copy of `card`, of any chosen color if it's wild. False if there wasn't one.
+    pub fn remove(&mut self, card: &Card) -> bool {
+        let card = PackedCard::from(*card);
+        let removed = self.counts.remove(card);
+
+        if !self.counts.contains(card) {
+            self.present &= !(1 << card.kind());
+        }
+
+        removed
+    }
+
+    /// Removes and returns every card of `color`, leaving wild cards whatever color was chosen
+    /// for them.
+    pub fn take_color(&mut self, color: CardColor) -> Vec<Card> {
+        let cards = self.iter().filter(|card| !card.is_wild() && card.color() == Some(color)).collect::<Vec<Card>>();
+
+        for card in &cards {
+            self.remove(card);
+        }
+
+        cards
+    }
+
+    pub fn contains(&self, card: &Card) -> bool {
+        self.present & (1 << PackedCard::from(*card).kind()) != 0
+    }
+
+    pub fn count(&self, card: &Card) -> u8 {
+        self.counts.count(PackedCard::from(*card))
+    }
+
+    /// A bit for every kind of card in the hand that can be played on `top`, indexed by
+    /// [`PackedCard::kind`].
+    pub fn playable_mask(&self, top: &Card) -> u64 {
+        self.present & PackedCard::from(*top).playable_kinds()
+    }
+
+    /// The cards selected by a mask from [`playable_mask`](Hand::playable_mask), every copy
+    /// included.
+    pub fn masked(&self, mask: u64) -> impl Iterator<Item = Card> + '_ {
+        self.kinds(mask).flat_map(|(card, count)| core::iter::repeat_n(Card::from(card), count as usize))
+    }
+
+    /// Each kind of card in the hand, with how many copies of it there are.
+    pub fn counts(&self) -> impl Iterator<Item = (PackedCard, u8)> + '_ {
+        self.kinds(u64::MAX)
+    }
+
+    fn kinds(&self, mask: u64) -> impl Iterator<Item = (PackedCard, u8)> + '_ {
+        Kinds(self.present & mask).map(|kind| {
+            let card = PackedCard::from_kind(kind);
+            (card, self.counts.count(card))
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Card> + '_ {
+        self.masked(u64::MAX)
+    }
+
+    pub fn to_vec(&self) -> Vec<Card> {
+        self.iter().collect()
+    }
+}
+
+impl Extend<Card> for Hand {
+    fn extend<I: IntoIterator<Item = Card>>(&mut self, cards: I) {
+        for card in cards {
+            self.insert(card);
+        }
+    }
+}
+
+impl FromIterator<Card> for Hand {
+    fn from_iter<I: IntoIterator<Item = Card>>(cards: I) -> Hand {
+        let mut hand = Hand::new();
+        hand.extend(cards);
+        hand
+    }
+}
+
+/// The kinds set in a mask, lowest first.
+struct Kinds(u64);
+
+impl Iterator for Kinds {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.0 == 0 {
+            return None;
+        }
+
+        let kind = self.0.trailing_zeros() as usize;
+        self.0 &= self.0 - 1;
+        Some(kind)
+    }
+}
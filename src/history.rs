@@ -0,0 +1,87 @@
+use alloc::{string::String, vec::Vec};
+use core::time::Duration;
+
+use crate::game::{GameEvent, GameView, Spectator};
+use crate::player::PlayerId;
+
+/// Records every event of a game in order, tagged with the turn it happened on.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct History {
+    /// The players' names in seating order, taken from the first event's view.
+    pub players: Vec<String>,
+    /// Their ids, in the same order.
+    pub ids: Vec<PlayerId>,
+    pub entries: Vec<HistoryEntry>,
+    turn: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    /// 0 for events before the first turn, such as the initial flip.
+    pub turn: u32,
+    pub event: GameEvent,
+    /// The game's fingerprint just after the event.
+    pub fingerprint: u64,
+    /// See [`GameView::at`].
+    pub at: Option<Duration>,
+}
+
+/// How long a player took to decide on their move, from [`History::think_times`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThinkTime {
+    pub turn: u32,
+    pub player: PlayerId,
+    pub took: Duration,
+}
+
+impl History {
+    pub fn new() -> History {
+        History::default()
+    }
+
+    pub fn turns(&self) -> u32 {
+        self.turn
+    }
+
+    /// The name of the player with `id`, as the game's views had it.
+    pub fn name_of(&self, id: PlayerId) -> Option<&str> {
+        self.ids.iter().position(|player| *player == id).map(|seat| self.players[seat].as_str())
+    }
+
+    /// For every turn, the time from its start to the player's first play or draw, going by
+    /// the times the events were stamped with. Empty for a game without a
+    /// [`Clock`](crate::clock::Clock). A forced draw counts too, taking no time at all.
+    pub fn think_times(&self) -> Vec<ThinkTime> {
+        let mut times = Vec::new();
+        let mut started = None;
+
+        for entry in &self.entries {
+            match (&entry.event, started.take()) {
+                (GameEvent::TurnStarted { player }, _) => started = entry.at.map(|at| (player, at)),
+                (GameEvent::Played { player, .. } | GameEvent::Drew { player, .. }, Some((starter, since))) if player == starter => {
+                    if let Some(at) = entry.at {
+                        times.push(ThinkTime { turn: entry.turn, player: *player, took: at.saturating_sub(since) });
+                    }
+                }
+                (_, still) => started = still,
+            }
+        }
+
+        times
+    }
+}
+
+impl Spectator for History {
+    fn observe_event(&mut self, event: &GameEvent, view: &GameView) {
+        if self.players.is_empty() {
+            self.players = view.players.iter().map(|player| player.name.clone()).collect();
+            self.ids = view.players.iter().map(|player| player.id).collect();
+        }
+
+        if let GameEvent::TurnStarted { .. } = event {
+            self.turn += 1;
+        }
+
+        self.entries.push(HistoryEntry { turn: self.turn, event: event.clone(), fingerprint: view.fingerprint, at: view.at });
+    }
+}
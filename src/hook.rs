@@ -0,0 +1,69 @@
+//! Extension points in the turn loop, for achievements, house penalties or experimental rules
+//! that shouldn't need changes to the engine itself.
+
+use crate::card::{Card, CardColor};
+use crate::game::{GameView, TurnResult};
+use crate::window::{Interjection, Window};
+
+/// What a played card does besides landing on the discard pile.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Effect {
+    /// Play changes direction.
+    Reverse,
+    /// The next player loses their turn.
+    Skip,
+    /// The next player draws this many cards on top of any already pending, unless they stack.
+    Draw(u8),
+    /// Everyone else loses their turn, so whoever played the card goes again.
+    SkipEveryone,
+    /// The player swaps hands with whoever holds the fewest cards.
+    TradeHands,
+    /// The player swaps hands with someone they pick, asked with
+    /// [`Player::choose_swap_target`](crate::player::Player::choose_swap_target).
+    SwapHands,
+    /// The player discards every card of the color they hold, all at once. A hook can put this
+    /// in place of any other effect to clear part of a hand.
+    DiscardAll(CardColor),
+}
+
+impl Effect {
+    /// The effect a card has under the standard rules, if any.
+    pub fn of(card: &Card) -> Option<Effect> {
+        match card {
+            Card::Reverse { .. } => Some(Effect::Reverse),
+            Card::Skip { .. } => Some(Effect::Skip),
+            Card::DrawTwo { .. } => Some(Effect::Draw(2)),
+            Card::DrawFour { .. } => Some(Effect::Draw(4)),
+            Card::WildSkipEveryone { .. } => Some(Effect::SkipEveryone),
+            Card::TradeHands { .. } => Some(Effect::TradeHands),
+            Card::DiscardAll { color } => Some(Effect::DiscardAll(*color)),
+            Card::Numeric { .. } | Card::Wild { .. } => None,
+        }
+    }
+}
+
+/// Called by the game as each turn is played; add one with
+/// [`GameState::add_hook`](crate::game::GameState::add_hook). Every method gets the view of the
+/// seat whose turn it is.
+///
+/// A hook that changes effects changes the game, so replays of it won't
+/// [`verify`](crate::replay::Replay::verify) without the same hook in place.
+pub trait Hook: Send {
+    /// Before the player acts, or is made to draw.
+    fn on_before_turn(&mut self, _seat: usize, _view: &GameView) {}
+
+    /// Once the turn is over. `result` is `None` when the player had to draw a pending penalty
+    /// instead of choosing a move.
+    fn on_after_turn(&mut self, _seat: usize, _result: Option<TurnResult>, _view: &GameView) {}
+
+    /// When a played card's effect is about to take hold. Return a different effect to apply
+    /// that instead, or `None` to cancel it; later hooks only see what earlier ones returned.
+    fn on_effect_applied(&mut self, effect: Effect, _view: &GameView) -> Option<Effect> {
+        Some(effect)
+    }
+
+    /// When `seat` is heard in a [`Window`], after the game has settled who was. The view is
+    /// `seat`'s.
+    fn on_interjection(&mut self, _seat: usize, _interjection: Interjection, _window: &Window, _view: &GameView) {}
+}
@@ -0,0 +1,74 @@
+//! An UNO engine, with terminal, Discord and server frontends.
+//!
+//! The rules engine ([`card`], [`game`], [`player`] and the modules they use) only needs `core`
+//! and `alloc`. Building without the default `std` feature leaves everything else out, along
+//! with [`GameState::new`](game::GameState::new) and [`GameState::start`](game::GameState::start),
+//! which need an OS for entropy and for holding a paused game.
+//!
+//! The rest of the default build is split up so embedders can leave out what they don't use:
+//! `bots` for the computer players, `cli` for the terminal frontend and `server` for hosting
+//! games, including on Discord. None of the engine is async, so there's nothing it needs to
+//! pull in for an async host.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod achievements;
+#[cfg(feature = "archive")]
+pub mod archive;
+pub mod audit;
+pub mod card;
+pub mod clock;
+#[cfg(feature = "archive")]
+pub mod collusion;
+#[cfg(all(feature = "std", feature = "bots"))]
+pub mod daily;
+#[cfg(feature = "conformance")]
+pub mod conformance;
+#[cfg(feature = "cli")]
+pub mod console;
+pub mod delayed;
+pub mod diff;
+#[cfg(feature = "server")]
+pub mod discord;
+pub mod emote;
+pub mod eval;
+#[cfg(feature = "fair")]
+pub mod fair;
+#[cfg(feature = "arbitrary")]
+pub mod fuzz;
+pub mod game;
+pub mod hand;
+pub mod history;
+pub mod hook;
+#[cfg(feature = "std")]
+pub mod locale;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod notation;
+pub mod odds;
+pub mod pacer;
+pub mod packed;
+pub mod player;
+#[cfg(feature = "persistence")]
+pub mod profile;
+#[cfg(feature = "std")]
+pub mod render;
+pub mod replay;
+pub mod rules;
+pub mod series;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod sim;
+#[cfg(feature = "persistence")]
+pub mod slots;
+pub mod snapshot;
+pub mod summary;
+#[cfg(feature = "training")]
+pub mod training;
+#[cfg(feature = "std")]
+pub mod tutorial;
+#[cfg(feature = "tui")]
+pub mod tui;
+pub mod window;
@@ -0,0 +1,423 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use crate::emote::Emote;
+use crate::game::LeftHand;
+
+/// Every user-facing string the crate prints. Templates refer to their arguments as `{name}`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Message {
+    Welcome,
+    AskName,
+    AskDifficulty,
+    InvalidDifficulty,
+    InvalidInput,
+    PlayAgain,
+    AskPlayerCount,
+    /// `{number}`
+    AskPlayerName,
+    /// `{player}`
+    PassTo,
+    EndTurn,
+    /// `{card}`
+    TopCard,
+    /// `{player}`, `{card}`
+    Played,
+    /// `{player}`, `{count}`
+    Drew,
+    /// `{player}`
+    Skipped,
+    /// `{player}`, `{with}`
+    Traded,
+    /// `{player}`, `{count}`
+    Discarded,
+    /// `{player}`, `{count}`
+    Stacked,
+    /// `{player}`, `{count}`
+    Spared,
+    /// `{player}`
+    Eliminated,
+    /// `{player}`
+    PlayerLeft,
+    /// `{player}`, `{place}`
+    Finished,
+    /// `{player}`
+    CalledOut,
+    /// `{player}`
+    Challenged,
+    /// `{player}`, `{card}`
+    JumpedIn,
+    /// `{player}`
+    Won,
+    /// `{reason}`
+    Aborted,
+    /// `{player}`, `{cards}`, `{points}`
+    Revealed,
+    /// `{player}`, `{colors}`
+    ColorsNamed,
+    Paused,
+    Resumed,
+    /// `{player}`, `{emote}`
+    Emote,
+    /// `{player}`, `{emote}`
+    PrivateEmote,
+    EmoteHello,
+    EmoteGoodLuck,
+    EmoteWellPlayed,
+    EmoteOops,
+    EmoteThinking,
+    EmoteTaunt,
+    YouWon,
+    YourTurn,
+    ChooseAction,
+    MustDraw,
+    PlayableCards,
+    SelectCard,
+    ChooseColor,
+    /// `{input}`
+    InvalidColor,
+    /// `{card}`
+    YouDrewCard,
+    /// `{count}`, `{cards}`
+    YouDrewCards,
+    YouWereSkipped,
+    /// `{player}`
+    WaitingFor,
+    /// `{card}`
+    CannotPlay,
+    /// `{count}`
+    PendingDraw,
+    TuiPlayers,
+    TuiDiscard,
+    TuiLog,
+    TuiHand,
+    TuiMustDraw,
+    TuiHelpCard,
+    TuiHelpColor,
+    TuiHelpWaiting,
+    WildCardName,
+    DrawFourName,
+    SkipEveryoneName,
+    /// `{card}`, `{color}`
+    ChosenColor,
+    Clockwise,
+    CounterClockwise,
+    /// `{count}`, `{card}`, `{direction}`
+    VerboseStarted,
+    /// `{player}`, `{count}`
+    VerboseTurn,
+    /// `{player}`, `{card}`, `{count}`
+    VerbosePlayed,
+    /// `{player}`, `{count}`, `{total}`
+    VerboseDrew,
+    /// `{player}`
+    VerboseSkipped,
+    /// `{player}`, `{with}`, `{total}`
+    VerboseTraded,
+    /// `{player}`, `{count}`, `{total}`
+    VerboseDiscarded,
+    /// `{player}`, `{count}`, `{total}`
+    VerboseSpared,
+    /// `{player}`
+    VerboseEliminated,
+    /// `{player}`, `{hand}`, one of the three below
+    VerbosePlayerLeft,
+    VerboseHandShuffledIn,
+    VerboseHandDistributed,
+    VerboseHandDiscarded,
+    /// `{player}`, `{place}`
+    VerboseFinished,
+    /// `{direction}`
+    VerboseReversed,
+    /// `{player}`
+    VerboseGoesAgain,
+    /// `{count}`
+    VerbosePendingDraw,
+    SummaryPlayer,
+    SummaryCards,
+    SummaryPoints,
+    SummaryHand,
+    /// `{player}`, `{points}`, `{turns}`
+    RoundResult,
+    /// `{number}`, `{count}`
+    ExhibitionGame,
+    /// `{turns}`
+    ExhibitionStopped,
+    /// `{number}`, `{divergence}`
+    ExhibitionDiverged,
+    /// `{games}`, `{turns}`
+    ExhibitionResult,
+    /// `{count}`
+    ExhibitionUnfinished,
+    StatsSeat,
+    StatsDifficulty,
+    StatsWins,
+    StatsWinRate,
+    StatsAverageCards,
+    /// `{player}`, `{achievement}`
+    AchievementUnlocked,
+    AchievementDrawFourFinish,
+    AchievementComeback,
+    AchievementNoDraws,
+    AchievementStacker,
+    /// `{error}`
+    ReportError,
+    /// `{player}`, `{cards}`
+    DebugHand,
+    /// `{player}`, `{rank}`, `{count}`, `{rating}`
+    LeaderboardRank,
+    /// `{error}`
+    ProfilesError,
+    /// `{error}`
+    ArchiveError,
+    /// `{cards}`, `{opponents}`
+    DiscordHand,
+    /// `{card}`
+    DiscordTurn,
+    DiscordDraw,
+    DiscordSelect,
+    DiscordIllegal,
+    DiscordPrevious,
+    DiscordNext,
+    ChooseSwapTarget,
+    /// `{index}`, `{player}`, `{count}`
+    SwapCandidate,
+    ChooseSecondColor,
+    TutorialMatchPrompt,
+    TutorialMatchHint,
+    TutorialMatchedColor,
+    TutorialMatchedNumber,
+    TutorialDrawTwoPrompt,
+    TutorialDrawTwoHint,
+    TutorialStacked,
+    TutorialTookDraw,
+    TutorialWildPrompt,
+    TutorialWildHint,
+    TutorialPlayedWild,
+    DailyWon,
+    DailyPlaced,
+    DailyUnfinished,
+}
+
+impl Message {
+    /// The text of an emote.
+    pub fn emote(emote: Emote) -> Message {
+        match emote {
+            Emote::Hello => Message::EmoteHello,
+            Emote::GoodLuck => Message::EmoteGoodLuck,
+            Emote::WellPlayed => Message::EmoteWellPlayed,
+            Emote::Oops => Message::EmoteOops,
+            Emote::Thinking => Message::EmoteThinking,
+            Emote::Taunt => Message::EmoteTaunt,
+        }
+    }
+
+    /// What became of the hand of a player who left, for [`Message::VerbosePlayerLeft`].
+    pub fn left_hand(hand: LeftHand) -> Message {
+        match hand {
+            LeftHand::ShuffleIn => Message::VerboseHandShuffledIn,
+            LeftHand::Distribute => Message::VerboseHandDistributed,
+            LeftHand::Discard => Message::VerboseHandDiscarded,
+        }
+    }
+
+    /// The name of one of the [built-in achievements](crate::achievements::Achievements::builtin).
+    pub fn achievement(id: &str) -> Option<Message> {
+        match id {
+            "draw-four-finish" => Some(Message::AchievementDrawFourFinish),
+            "comeback" => Some(Message::AchievementComeback),
+            "no-draws" => Some(Message::AchievementNoDraws),
+            "stacker" => Some(Message::AchievementStacker),
+            _ => None,
+        }
+    }
+}
+
+const ENGLISH: [(Message, &str); 132] = [
+    (Message::Welcome, "Welcome to Uno (CLI Edition!). The game will start shortly."),
+    (Message::AskName, "Lets start with your name: "),
+    (Message::AskDifficulty, "Choose a difficulty: [E]asy, [M]edium, [H]ard"),
+    (Message::InvalidDifficulty, "Invalid difficulty. Defaulting to Medium."),
+    (Message::InvalidInput, "Invalid input. Please try again."),
+    (Message::PlayAgain, "[P]lay again or [Q]uit?"),
+    (Message::AskPlayerCount, "How many players are sharing this terminal? (2-6)"),
+    (Message::AskPlayerName, "Name for player {number}: "),
+    (Message::PassTo, "Pass the terminal to {player}, then press Enter."),
+    (Message::EndTurn, "Press Enter to hide your cards and end your turn."),
+    (Message::TopCard, "The top card is: {card}"),
+    (Message::Played, "{player} played {card}"),
+    (Message::Drew, "{player} drew {count} card(s)"),
+    (Message::Skipped, "{player}'s turn was skipped"),
+    (Message::Traded, "{player} traded hands with {with}"),
+    (Message::Stacked, "{player} raised the draw to {count}"),
+    (Message::Discarded, "{player} discarded {count} card(s)"),
+    (Message::Spared, "{player} was spared {count} card(s)"),
+    (Message::Eliminated, "{player} has too many cards and is out"),
+    (Message::PlayerLeft, "{player} has left the game"),
+    (Message::Finished, "{player} went out and finishes #{place}"),
+    (Message::CalledOut, "{player} called out a missed UNO"),
+    (Message::Challenged, "{player} challenged the Draw Four"),
+    (Message::JumpedIn, "{player} jumped in with {card}"),
+    (Message::Won, "{player} won!"),
+    (Message::Aborted, "The game was stopped: {reason}"),
+    (Message::Revealed, "{player} was left holding {cards}, worth {points} points"),
+    (Message::ColorsNamed, "{player} named two colors: {colors} can go on next"),
+    (Message::Paused, "The game is paused."),
+    (Message::Resumed, "The game has resumed."),
+    (Message::Emote, "{player}: {emote}"),
+    (Message::PrivateEmote, "{player} (to you): {emote}"),
+    (Message::EmoteHello, "Hello!"),
+    (Message::EmoteGoodLuck, "Good luck!"),
+    (Message::EmoteWellPlayed, "Well played!"),
+    (Message::EmoteOops, "Oops!"),
+    (Message::EmoteThinking, "Hmm..."),
+    (Message::EmoteTaunt, "Is that all you've got?"),
+    (Message::YouWon, "You won!"),
+    (Message::YourTurn, "Its your turn."),
+    (Message::ChooseAction, "What would you like to do? [Play], [Draw]"),
+    (Message::MustDraw, "You have no cards to play. You must draw."),
+    (Message::PlayableCards, "You can play the following cards:"),
+    (Message::SelectCard, "Enter a number to select a card, or type 'back' to go back to the decision screen."),
+    (Message::ChooseColor, "Enter a color to choose, or type 'back' to go back to the decision screen."),
+    (Message::InvalidColor, "{input} is not a valid color!"),
+    (Message::YouDrewCard, "You drew a {card}."),
+    (Message::YouDrewCards, "You drew {count} cards. [{cards}]"),
+    (Message::YouWereSkipped, "You have been skipped!"),
+    (Message::WaitingFor, "Waiting for {player}..."),
+    (Message::CannotPlay, "You can't play {card} right now."),
+    (Message::PendingDraw, "+{count} to draw"),
+    (Message::TuiPlayers, "Players"),
+    (Message::TuiDiscard, "Discard"),
+    (Message::TuiLog, "Log"),
+    (Message::TuiHand, "Your hand"),
+    (Message::TuiMustDraw, "You have no cards to play. Press [D] to draw."),
+    (Message::TuiHelpCard, "←/→ select  [Enter] play  [D]raw  [Q]uit"),
+    (Message::TuiHelpColor, "Choose a color: [R]ed [G]reen [B]lue [Y]ellow  [Esc] back"),
+    (Message::TuiHelpWaiting, "[Q]uit"),
+    (Message::WildCardName, "Wild card"),
+    (Message::DrawFourName, "Wild Draw Four"),
+    (Message::SkipEveryoneName, "Wild Skip Everyone"),
+    (Message::ChosenColor, "{card}, chosen color {color}"),
+    (Message::Clockwise, "clockwise"),
+    (Message::CounterClockwise, "counterclockwise"),
+    (Message::VerboseStarted, "A new game has started with {count} players. The top card is {card}. Play goes {direction}."),
+    (Message::VerboseTurn, "It is now {player}'s turn. {player} holds {count} cards."),
+    (Message::VerbosePlayed, "{player} played {card}. {player} has {count} cards left."),
+    (Message::VerboseDrew, "{player} drew {count} card(s) and now holds {total} cards."),
+    (Message::VerboseSkipped, "{player}'s turn was skipped."),
+    (Message::VerboseTraded, "{player} traded hands with {with} and now holds {total} cards."),
+    (Message::VerboseDiscarded, "{player} discarded {count} more card(s) of that color and now holds {total} cards."),
+    (Message::VerboseSpared, "{player} was spared {count} card(s) by the hand limit and holds {total} cards."),
+    (Message::VerboseEliminated, "{player} went over the hand limit and is out of the round."),
+    (Message::VerbosePlayerLeft, "{player} has left the game. {hand}"),
+    (Message::VerboseHandShuffledIn, "Their cards were shuffled into the deck."),
+    (Message::VerboseHandDistributed, "Their cards were dealt out to the rest of the table."),
+    (Message::VerboseHandDiscarded, "Their cards went under the top of the discard pile."),
+    (Message::VerboseFinished, "{player} has no cards left and finishes in place {place}. Play goes on without them."),
+    (Message::VerboseReversed, "The direction of play is now {direction}."),
+    (Message::VerboseGoesAgain, "Everyone else is skipped, so {player} goes again."),
+    (Message::VerbosePendingDraw, "The next player must draw {count} cards unless they can stack a matching draw card."),
+    (Message::SummaryPlayer, "Player"),
+    (Message::SummaryCards, "Cards"),
+    (Message::SummaryPoints, "Points"),
+    (Message::SummaryHand, "Hand"),
+    (Message::RoundResult, "{player} won the round, scoring {points} points in {turns} turns."),
+    (Message::ExhibitionGame, "Game {number} of {count}"),
+    (Message::ExhibitionStopped, "Nobody had won after {turns} turns, so the game was stopped."),
+    (Message::ExhibitionDiverged, "Game {number} didn't replay the same way: {divergence}"),
+    (Message::ExhibitionResult, "{games} games, {turns} turns each on average."),
+    (Message::ExhibitionUnfinished, "{count} were stopped before anyone won."),
+    (Message::StatsSeat, "Seat"),
+    (Message::StatsDifficulty, "Difficulty"),
+    (Message::StatsWins, "Wins"),
+    (Message::StatsWinRate, "Win rate"),
+    (Message::StatsAverageCards, "Cards left"),
+    (Message::AchievementUnlocked, "{player} unlocked an achievement: {achievement}"),
+    (Message::AchievementDrawFourFinish, "Going Out With a Bang (won with a Draw Four)"),
+    (Message::AchievementComeback, "Comeback (held 20 or more cards and still won)"),
+    (Message::AchievementNoDraws, "Clean Sweep (won without drawing a card)"),
+    (Message::AchievementStacker, "Not My Problem (stacked a draw card)"),
+    (Message::ReportError, "Couldn't write the report: {error}"),
+    (Message::DebugHand, "[debug] {player} holds {cards}"),
+    (Message::LeaderboardRank, "{player} is ranked #{rank} of {count} with a rating of {rating}."),
+    (Message::ProfilesError, "Couldn't update player profiles: {error}"),
+    (Message::ArchiveError, "Couldn't archive the game: {error}"),
+    (Message::DiscordHand, "Your hand: {cards}\nOpponents: {opponents}"),
+    (Message::DiscordTurn, "It's your turn! The top card is {card}."),
+    (Message::DiscordDraw, "Draw"),
+    (Message::DiscordSelect, "Choose a card to play"),
+    (Message::DiscordIllegal, "That move isn't available right now."),
+    (Message::DiscordPrevious, "Previous cards"),
+    (Message::DiscordNext, "More cards"),
+    (Message::ChooseSwapTarget, "Enter a number to choose who to swap hands with:"),
+    (Message::SwapCandidate, "{index}: {player} ({count} cards)"),
+    (Message::ChooseSecondColor, "Enter a second color to name along with it, or leave it empty to name just the one."),
+    (Message::TutorialMatchPrompt, "The top card is a red 7. Play a card of the same color or the same number."),
+    (Message::TutorialMatchHint, "Look for another red card, or any 7."),
+    (Message::TutorialMatchedColor, "Red on red: a card can always go on one of its own color."),
+    (Message::TutorialMatchedNumber, "7 on 7: a card can always go on one with the same number, whatever its color."),
+    (Message::TutorialDrawTwoPrompt, "You've been hit with a Draw Two. Stack one of your own to pass it on, or take the two cards."),
+    (Message::TutorialDrawTwoHint, "With a draw coming your way, only a Draw Two just like it can be played. Otherwise, draw."),
+    (Message::TutorialStacked, "Stacked! Now the next player has to draw four, unless they can stack too."),
+    (Message::TutorialTookDraw, "You drew two cards, and that was your turn. Stacking would have passed them on."),
+    (Message::TutorialWildPrompt, "Nothing in your hand matches the green 4, but a wild goes on anything. Play it and choose a color."),
+    (Message::TutorialWildHint, "Your wild is the only card that goes on the green 4. Don't forget to choose its color."),
+    (Message::TutorialPlayedWild, "A wild goes on any card, and the color you chose is the one to match next."),
+    (Message::DailyWon, "Uno daily {date}: won in {turns} turns, scoring {points}."),
+    (Message::DailyPlaced, "Uno daily {date}: finished {place} of {players} after {turns} turns."),
+    (Message::DailyUnfinished, "Uno daily {date}: unfinished after {turns} turns."),
+];
+
+/// A message catalog for one language.
+///
+/// Catalogs start out with the English text, so a translation only needs to supply the
+/// messages it has translated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Locale {
+    code: String,
+    messages: HashMap<Message, String>,
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Locale::english()
+    }
+}
+
+impl Locale {
+    pub fn english() -> Locale {
+        Locale::new("en")
+    }
+
+    pub fn new(code: &str) -> Locale {
+        Locale {
+            code: code.to_string(),
+            messages: ENGLISH.iter().map(|(message, text)| (*message, text.to_string())).collect(),
+        }
+    }
+
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    pub fn with(mut self, message: Message, template: &str) -> Locale {
+        self.set(message, template);
+        self
+    }
+
+    pub fn set(&mut self, message: Message, template: &str) {
+        self.messages.insert(message, template.to_string());
+    }
+
+    /// The raw template for `message`.
+    pub fn get(&self, message: Message) -> &str {
+        &self.messages[&message]
+    }
+
+    /// Fills in the template for `message`. Placeholders without a matching argument are left as-is.
+    pub fn format(&self, message: Message, args: &[(&str, &dyn Display)]) -> String {
+        args.iter().fold(self.get(message).to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{name}}}"), &value.to_string())
+        })
+    }
+}
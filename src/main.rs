@@ -1,76 +1,253 @@
-#![feature(slice_group_by)]
+use std::time::Duration;
 
-use std::io::{stdout, Write};
-use rand::thread_rng;
-use crate::game::GameState;
-use crate::player::{AIDifficulty, Player};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use uno_cli::achievements::Achievements;
+use uno_cli::console::Console;
+use uno_cli::game::GameState;
+use uno_cli::player;
+use uno_cli::player::{AIDifficulty, Player, PlayerId};
+use uno_cli::locale::{Locale, Message};
+use uno_cli::render::{RenderConfig, RenderMode};
+use uno_cli::summary::GameOutcome;
 
-mod card;
-mod player;
-mod game;
+const FLAGS: [&str; 5] = ["--tui", "--hotseat", "--plain", "--unicode", "--accessible"];
+
+/// How long to wait after each turn, so people can follow the computer players.
+const TURN_DELAY: Duration = Duration::from_millis(800);
 
 fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<String>>();
 
-    let difficulty = get_difficulty();
+    let render = if args.iter().any(|arg| arg == "--plain") {
+        RenderConfig::new(RenderMode::Plain)
+    } else if args.iter().any(|arg| arg == "--unicode") {
+        RenderConfig::new(RenderMode::Unicode)
+    } else if args.iter().any(|arg| arg == "--accessible") {
+        RenderConfig::new(RenderMode::Accessible)
+    } else {
+        RenderConfig::default()
+    };
 
     enable_ansi_support::enable_ansi_support().unwrap();
 
-    println!("Welcome to Uno (CLI Edition!). The game will start shortly.");
+    if args.iter().any(|arg| arg == "--hotseat") {
+        println!("{}", render.locale.get(Message::Welcome));
+        return play_hotseat(render);
+    }
+
+    let difficulty = get_difficulty(args.iter().find(|arg| !FLAGS.contains(&arg.as_str())), &render.locale);
+
+    println!("{}", render.locale.get(Message::Welcome));
 
     std::thread::sleep(std::time::Duration::from_millis(1500));
 
-    let mut rng = &mut thread_rng();
-    let mut rng2 = &mut thread_rng();
-    let mut rng3 = &mut thread_rng();
+    let mut rng = StdRng::from_entropy();
+    let mut rng2 = StdRng::from_entropy();
+    let mut rng3 = StdRng::from_entropy();
 
     let ai_one = &mut player::Ai::new(&mut rng, difficulty);
     let ai_two = &mut player::Ai::new(&mut rng2, difficulty);
     let ai_three = &mut player::Ai::new(&mut rng3, difficulty);
 
-    println!("Lets start with your name: ");
+    println!("{}", render.locale.get(Message::AskName));
     let mut name = String::new();
     std::io::stdin().read_line(&mut name).unwrap();
 
-    let human = &mut player::Human::new(name.trim().to_string());
+    if args.iter().any(|arg| arg == "--tui") {
+        #[cfg(feature = "tui")]
+        return play_tui(name.trim().to_string(), render, [ai_one, ai_two, ai_three]);
+
+        #[cfg(not(feature = "tui"))]
+        println!("This build doesn't include the terminal interface (the `tui` feature). Using the plain CLI instead.");
+    }
+
+    let human = &mut player::Human::new(name.trim().to_string(), render.clone());
+    let console = &mut Console::new(render.clone());
+    let achievements = &mut Achievements::builtin();
 
     loop {
-        let players: Vec<&mut dyn Player> = vec![ai_one, human, ai_two, ai_three];
-        let mut game = GameState::new(players);
+        #[cfg(feature = "archive")]
+        let mut history = uno_cli::history::History::new();
+
+        let outcome = {
+            let players: Vec<&mut dyn Player> = vec![ai_one, human, ai_two, ai_three];
+            let mut game = GameState::new(players);
+            game.set_turn_delay(TURN_DELAY);
+            game.add_spectator(console);
+            game.add_spectator(achievements);
 
-        let winner  = game.start();
+            #[cfg(feature = "archive")]
+            game.add_spectator(&mut history);
 
-        if winner == human.name() {
-            println!("You won!");
+            game.start()
+        };
+
+        // Nothing aborts the game here, but if something did, the console has said why.
+        let GameOutcome::Won(summary) = outcome else {
+            break;
+        };
+
+        // Players are given their place in the list as their id.
+        if summary.winner_id == PlayerId(1) {
+            println!("{}", render.locale.get(Message::YouWon));
         } else {
-            println!("{winner} won!");
+            println!("{}", render.locale.format(Message::Won, &[("player", &summary.winner)]));
         }
 
-        std::thread::sleep(std::time::Duration::from_millis(1500));
+        println!("\n{}\n", summary.table(&render));
+        print_achievements(achievements, &render.locale);
 
-        let mut input = String::new();
+        #[cfg(feature = "persistence")]
+        record_profiles(&summary, human.name(), &render.locale);
 
-        println!("[P]lay again or [Q]uit?");
+        #[cfg(feature = "archive")]
+        archive_game(&history, &summary, &render.locale);
 
-        std::io::stdin().read_line(&mut input).unwrap();
+        if !play_again(&render.locale) {
+            break;
+        }
+    }
+}
+
+/// Several people taking turns at one terminal, with no computer players.
+fn play_hotseat(render: RenderConfig) {
+    let count = loop {
+        println!("{}", render.locale.get(Message::AskPlayerCount));
+
+        match read_line().parse::<usize>() {
+            Ok(count) if (2..=6).contains(&count) => break count,
+            _ => println!("{}", render.locale.get(Message::InvalidInput)),
+        }
+    };
+
+    let mut seats = (1..=count)
+        .map(|number| {
+            println!("{}", render.locale.format(Message::AskPlayerName, &[("number", &number)]));
+            player::HotSeat::new(read_line(), render.clone())
+        })
+        .collect::<Vec<player::HotSeat>>();
+
+    let console = &mut Console::new(render.clone());
+    let achievements = &mut Achievements::builtin();
+
+    loop {
+        let outcome = {
+            let players = seats.iter_mut().map(|seat| seat as &mut dyn Player).collect();
+            let mut game = GameState::new(players);
+            game.set_turn_delay(TURN_DELAY);
+            game.add_spectator(console);
+            game.add_spectator(achievements);
+            game.start()
+        };
+
+        let GameOutcome::Won(summary) = outcome else {
+            break;
+        };
+
+        println!("{}", render.locale.format(Message::Won, &[("player", &summary.winner)]));
+        println!("\n{}\n", summary.table(&render));
+        print_achievements(achievements, &render.locale);
 
-        if matches!(input.trim().to_lowercase().as_str(), "q" | "quit") {
+        if !play_again(&render.locale) {
             break;
         }
+    }
+}
 
-        print!("\x1B[2J\x1B[1;1H");
+fn print_achievements(achievements: &mut Achievements, locale: &Locale) {
+    for unlocked in achievements.take_new() {
+        let name = Message::achievement(unlocked.achievement).map_or(unlocked.achievement, |message| locale.get(message));
+        println!("{}", locale.format(Message::AchievementUnlocked, &[("player", &unlocked.player), ("achievement", &name)]));
     }
 }
 
-fn get_difficulty() -> AIDifficulty {
-    if std::env::args().len() > 1 {
-        let arg = std::env::args().nth(1).unwrap();
+/// Asks whether to play another round, clearing the screen for it if so.
+fn play_again(locale: &Locale) -> bool {
+    std::thread::sleep(std::time::Duration::from_millis(1500));
+
+    println!("{}", locale.get(Message::PlayAgain));
+
+    if matches!(read_line().to_lowercase().as_str(), "q" | "quit") {
+        return false;
+    }
+
+    print!("\x1B[2J\x1B[1;1H");
+    true
+}
+
+fn read_line() -> String {
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input).unwrap();
+    input.trim().to_string()
+}
+
+#[cfg(feature = "tui")]
+fn play_tui(name: String, render: RenderConfig, opponents: [&mut dyn Player; 3]) {
+    let [ai_one, ai_two, ai_three] = opponents;
+    let tui = &mut uno_cli::tui::TuiPlayer::new(name, render.locale);
+
+    loop {
+        let players: Vec<&mut dyn Player> = vec![&mut *ai_one, &mut *tui, &mut *ai_two, &mut *ai_three];
+        let outcome = {
+            let mut game = GameState::new(players);
+            game.set_turn_delay(TURN_DELAY);
+            game.start()
+        };
+
+        match outcome.won() {
+            Some(summary) if tui.play_again(summary) => {}
+            _ => break,
+        }
+    }
+}
+
+#[cfg(feature = "persistence")]
+fn record_profiles(summary: &uno_cli::summary::RoundSummary, name: &str, locale: &Locale) {
+    use uno_cli::profile::{ProfileStore, Ranking};
+
+    const PROFILES: &str = "uno_profiles.json";
+
+    let result = ProfileStore::load(PROFILES).and_then(|mut store| {
+        store.record(summary);
+        store.save(PROFILES)?;
+        Ok(store)
+    });
+
+    match result {
+        Ok(store) => {
+            let leaderboard = store.leaderboard(Ranking::Rating);
+
+            if let (Some(rank), Some(profile)) = (leaderboard.rank_of(name), store.get(name)) {
+                println!("{}", locale.format(Message::LeaderboardRank, &[
+                    ("player", &name),
+                    ("rank", &rank),
+                    ("count", &leaderboard.len()),
+                    ("rating", &format!("{:.0}", profile.rating)),
+                ]));
+            }
+        }
+        Err(error) => println!("{}", locale.format(Message::ProfilesError, &[("error", &error)])),
+    }
+}
+
+#[cfg(feature = "archive")]
+fn archive_game(history: &uno_cli::history::History, summary: &uno_cli::summary::RoundSummary, locale: &Locale) {
+    let result = uno_cli::archive::Archive::open("uno_games.db").and_then(|mut archive| archive.store(history, summary));
+
+    if let Err(error) = result {
+        println!("{}", locale.format(Message::ArchiveError, &[("error", &error)]));
+    }
+}
 
+fn get_difficulty(arg: Option<&String>, locale: &Locale) -> AIDifficulty {
+    if let Some(arg) = arg {
         return match arg.to_lowercase().as_str() {
             "-e" | "--easy" => AIDifficulty::Easy,
             "-m" | "--medium" => AIDifficulty::Medium,
             "-h" | "--hard" => AIDifficulty::Hard,
             _ => {
-                println!("Invalid difficulty. Defaulting to Medium.");
+                println!("{}", locale.get(Message::InvalidDifficulty));
                 AIDifficulty::Medium
             }
         }
@@ -79,7 +256,7 @@ fn get_difficulty() -> AIDifficulty {
     let mut input = String::new();
 
     loop {
-        println!("Choose a difficulty: [E]asy, [M]edium, [H]ard");
+        println!("{}", locale.get(Message::AskDifficulty));
 
         std::io::stdin().read_line(&mut input).unwrap();
 
@@ -88,7 +265,7 @@ fn get_difficulty() -> AIDifficulty {
             "m" | "medium" => return AIDifficulty::Medium,
             "h" | "hard" => return AIDifficulty::Hard,
             _ => {
-                println!("Invalid input. Please try again.");
+                println!("{}", locale.get(Message::InvalidInput));
                 input.clear();
             }
         }
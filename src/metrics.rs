@@ -0,0 +1,140 @@
+use std::time::Instant;
+
+use prometheus::{Histogram, HistogramOpts, IntCounter, IntGauge, Registry, TextEncoder};
+
+use crate::game::{GameEvent, GameView, Spectator};
+
+/// Prometheus metrics shared by every game a process runs.
+///
+/// Each game reports through its own [`GameMetrics`] spectator, made with [`Metrics::observer`];
+/// [`Metrics::render`] produces the text exposition format for a `/metrics` endpoint.
+#[derive(Clone)]
+pub struct Metrics {
+    registry: Registry,
+    games_started: IntCounter,
+    games_finished: IntCounter,
+    active_tables: IntGauge,
+    cards_played: IntCounter,
+    cards_drawn: IntCounter,
+    turn_duration: Histogram,
+    actions_per_game: Histogram,
+}
+
+/// Reports one game to its [`Metrics`].
+pub struct GameMetrics {
+    metrics: Metrics,
+    active: bool,
+    turn_started: Option<Instant>,
+    actions: u32,
+}
+
+impl Metrics {
+    pub fn new() -> prometheus::Result<Metrics> {
+        Metrics::with_registry(Registry::new())
+    }
+
+    /// Registers the metrics with an existing registry, for processes that export others too.
+    pub fn with_registry(registry: Registry) -> prometheus::Result<Metrics> {
+        let metrics = Metrics {
+            games_started: IntCounter::new("uno_games_started_total", "Games that have been dealt")?,
+            games_finished: IntCounter::new("uno_games_finished_total", "Games that ended with a winner")?,
+            active_tables: IntGauge::new("uno_active_tables", "Games currently in progress")?,
+            cards_played: IntCounter::new("uno_cards_played_total", "Cards played across all games")?,
+            cards_drawn: IntCounter::new("uno_cards_drawn_total", "Cards drawn across all games")?,
+            turn_duration: Histogram::with_opts(
+                HistogramOpts::new("uno_turn_duration_seconds", "Time from the start of a turn to the start of the next")
+                    .buckets(vec![0.1, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0]),
+            )?,
+            actions_per_game: Histogram::with_opts(
+                HistogramOpts::new("uno_actions_per_game", "Plays and draws made in a finished game")
+                    .buckets(vec![10.0, 25.0, 50.0, 75.0, 100.0, 150.0, 250.0, 500.0]),
+            )?,
+            registry,
+        };
+
+        metrics.registry.register(Box::new(metrics.games_started.clone()))?;
+        metrics.registry.register(Box::new(metrics.games_finished.clone()))?;
+        metrics.registry.register(Box::new(metrics.active_tables.clone()))?;
+        metrics.registry.register(Box::new(metrics.cards_played.clone()))?;
+        metrics.registry.register(Box::new(metrics.cards_drawn.clone()))?;
+        metrics.registry.register(Box::new(metrics.turn_duration.clone()))?;
+        metrics.registry.register(Box::new(metrics.actions_per_game.clone()))?;
+
+        Ok(metrics)
+    }
+
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    /// A spectator to add to a new game.
+    pub fn observer(&self) -> GameMetrics {
+        GameMetrics {
+            metrics: self.clone(),
+            active: false,
+            turn_started: None,
+            actions: 0,
+        }
+    }
+
+    /// Everything in the registry, in the Prometheus text format.
+    pub fn render(&self) -> String {
+        TextEncoder::new().encode_to_string(&self.registry.gather()).unwrap_or_default()
+    }
+}
+
+impl GameMetrics {
+    fn end_turn(&mut self) {
+        if let Some(started) = self.turn_started.take() {
+            self.metrics.turn_duration.observe(started.elapsed().as_secs_f64());
+        }
+    }
+}
+
+impl Spectator for GameMetrics {
+    fn observe_event(&mut self, event: &GameEvent, _view: &GameView) {
+        match event {
+            GameEvent::Started { .. } => {
+                self.active = true;
+                self.metrics.games_started.inc();
+                self.metrics.active_tables.inc();
+            }
+            GameEvent::TurnStarted { .. } => {
+                self.end_turn();
+                self.turn_started = Some(Instant::now());
+            }
+            GameEvent::Played { .. } => {
+                self.actions += 1;
+                self.metrics.cards_played.inc();
+            }
+            GameEvent::Drew { count, .. } => {
+                self.actions += 1;
+                self.metrics.cards_drawn.inc_by(*count as u64);
+            }
+            // Time spent paused isn't part of anyone's turn.
+            GameEvent::Paused => self.end_turn(),
+            GameEvent::Setup { .. } | GameEvent::Skipped { .. } | GameEvent::Traded { .. } | GameEvent::Discarded { .. } | GameEvent::Spared { .. } | GameEvent::Eliminated { .. } | GameEvent::Finished { .. } | GameEvent::WindowOpened { .. } | GameEvent::Interjected { .. } | GameEvent::Stacked { .. } | GameEvent::PlayerLeft { .. } | GameEvent::Resumed | GameEvent::Emote { .. } | GameEvent::DebugHands { .. } | GameEvent::Revealed { .. } | GameEvent::ColorsNamed { .. } => {}
+            GameEvent::Won { .. } => {
+                self.end_turn();
+                self.active = false;
+                self.metrics.games_finished.inc();
+                self.metrics.active_tables.dec();
+                self.metrics.actions_per_game.observe(self.actions as f64);
+            }
+            GameEvent::Aborted { .. } => {
+                self.end_turn();
+                self.active = false;
+                self.metrics.active_tables.dec();
+            }
+        }
+    }
+}
+
+impl Drop for GameMetrics {
+    fn drop(&mut self) {
+        // A game that's dropped without a winner is no longer being played either.
+        if self.active {
+            self.metrics.active_tables.dec();
+        }
+    }
+}
@@ -0,0 +1,225 @@
+//! A compact text notation for games, in the spirit of chess's PGN:
+//!
+//! ```text
+//! [Players "Kirito, Asuna, Kazuto, Levi"]
+//! [Seed "42"]
+//!
+//! 1: P R7; 2: P RS; 4: D; 5: P W(blue); 6: D4; 7: P B+2
+//! ```
+//!
+//! Tags come first, one per line. Each move is the turn it happened on, then `P` and a card for a
+//! play or `D` for a draw, with the number of cards when it's more than one. Cards are a color
+//! letter followed by a number, `S` (skip), `R` (reverse), `+2`, `T` (trade hands) or `A`
+//! (discard all); wilds are `W`, `+4` and `WS` (skip everyone), with the chosen color in
+//! parentheses.
+
+use alloc::{format, string::{String, ToString}, vec, vec::Vec};
+use core::fmt::{self, Display, Formatter};
+use core::str::FromStr;
+
+use crate::card::{Card, CardColor, CardValue};
+use crate::game::GameEvent;
+use crate::history::History;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Notation {
+    pub tags: Vec<(String, String)>,
+    pub moves: Vec<NotatedMove>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NotatedMove {
+    pub turn: u32,
+    pub action: NotatedAction,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum NotatedAction {
+    Play(Card),
+    Draw(usize),
+}
+
+impl Notation {
+    /// The plays and draws of a recorded game, tagged with its players.
+    pub fn from_history(history: &History) -> Notation {
+        let moves = history.entries
+            .iter()
+            .filter_map(|entry| {
+                let action = match entry.event {
+                    GameEvent::Played { card, .. } => NotatedAction::Play(card),
+                    GameEvent::Drew { count, .. } => NotatedAction::Draw(count),
+                    _ => return None,
+                };
+
+                Some(NotatedMove { turn: entry.turn, action })
+            })
+            .collect();
+
+        Notation {
+            tags: vec![("Players".to_string(), history.players.join(", "))],
+            moves,
+        }
+    }
+
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags.iter().find(|(tag, _)| tag == name).map(|(_, value)| value.as_str())
+    }
+}
+
+impl Display for Notation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (name, value) in &self.tags {
+            writeln!(f, "[{name} \"{value}\"]")?;
+        }
+
+        if !self.tags.is_empty() {
+            writeln!(f)?;
+        }
+
+        let moves = self.moves.iter().map(NotatedMove::to_string).collect::<Vec<String>>();
+        write!(f, "{}", moves.join("; "))
+    }
+}
+
+impl FromStr for Notation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut notation = Notation::default();
+        let mut moves = String::new();
+
+        for line in s.lines().map(str::trim) {
+            if let Some(tag) = line.strip_prefix('[') {
+                let (name, value) = tag
+                    .strip_suffix(']')
+                    .and_then(|tag| tag.split_once(' '))
+                    .and_then(|(name, value)| Some((name, value.trim().strip_prefix('"')?.strip_suffix('"')?)))
+                    .ok_or_else(|| format!("{line} is not a valid tag"))?;
+
+                notation.tags.push((name.to_string(), value.to_string()));
+            } else {
+                moves.push_str(line);
+                moves.push(' ');
+            }
+        }
+
+        for entry in moves.split(';').map(str::trim).filter(|entry| !entry.is_empty()) {
+            notation.moves.push(entry.parse()?);
+        }
+
+        Ok(notation)
+    }
+}
+
+impl Display for NotatedMove {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.action {
+            NotatedAction::Play(card) => write!(f, "{}: P {}", self.turn, notate_card(&card)),
+            NotatedAction::Draw(1) => write!(f, "{}: D", self.turn),
+            NotatedAction::Draw(count) => write!(f, "{}: D{count}", self.turn),
+        }
+    }
+}
+
+impl FromStr for NotatedMove {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (turn, action) = s.split_once(':').ok_or_else(|| format!("{s} is missing its turn number"))?;
+        let turn = turn.trim().parse().map_err(|_| format!("{turn} is not a valid turn number"))?;
+        let action = action.trim();
+
+        let action = if let Some(card) = action.strip_prefix("P ") {
+            NotatedAction::Play(parse_card(card.trim())?)
+        } else if let Some(count) = action.strip_prefix('D') {
+            match count {
+                "" => NotatedAction::Draw(1),
+                count => NotatedAction::Draw(count.parse().map_err(|_| format!("{action} is not a valid draw"))?),
+            }
+        } else {
+            return Err(format!("{action} is not a valid move"));
+        };
+
+        Ok(NotatedMove { turn, action })
+    }
+}
+
+/// A card in the notation, such as `R7`, `GS` or `W(blue)`.
+pub fn notate_card(card: &Card) -> String {
+    let color = |color: &CardColor| match color {
+        CardColor::Red => 'R',
+        CardColor::Green => 'G',
+        CardColor::Blue => 'B',
+        CardColor::Yellow => 'Y',
+    };
+    let chosen = |color: &Option<CardColor>| color.map_or(String::new(), |color| format!("({})", color.to_string().to_lowercase()));
+
+    match card {
+        Card::Numeric { color: c, value } => format!("{}{}", color(c), value.number()),
+        Card::Skip { color: c } => format!("{}S", color(c)),
+        Card::Reverse { color: c } => format!("{}R", color(c)),
+        Card::DrawTwo { color: c } => format!("{}+2", color(c)),
+        Card::TradeHands { color: c } => format!("{}T", color(c)),
+        Card::DiscardAll { color: c } => format!("{}A", color(c)),
+        Card::Wild { color: c } => format!("W{}", chosen(c)),
+        Card::DrawFour { color: c } => format!("+4{}", chosen(c)),
+        Card::WildSkipEveryone { color: c } => format!("WS{}", chosen(c)),
+    }
+}
+
+pub fn parse_card(s: &str) -> Result<Card, String> {
+    let invalid = || format!("{s} is not a valid card");
+
+    let wild = |rest: &str| match rest {
+        "" => Ok(None),
+        rest => rest
+            .strip_prefix('(')
+            .and_then(|rest| rest.strip_suffix(')'))
+            .ok_or_else(invalid)?
+            .parse()
+            .map(Some),
+    };
+
+    if let Some(rest) = s.strip_prefix("WS") {
+        return Ok(Card::WildSkipEveryone { color: wild(rest)? });
+    }
+
+    if let Some(rest) = s.strip_prefix('W') {
+        return Ok(Card::Wild { color: wild(rest)? });
+    }
+
+    if let Some(rest) = s.strip_prefix("+4") {
+        return Ok(Card::DrawFour { color: wild(rest)? });
+    }
+
+    let mut chars = s.chars();
+
+    let color = match chars.next() {
+        Some('R') => CardColor::Red,
+        Some('G') => CardColor::Green,
+        Some('B') => CardColor::Blue,
+        Some('Y') => CardColor::Yellow,
+        _ => return Err(invalid()),
+    };
+
+    let value = match chars.as_str() {
+        "S" => return Ok(Card::Skip { color }),
+        "R" => return Ok(Card::Reverse { color }),
+        "+2" => return Ok(Card::DrawTwo { color }),
+        "T" => return Ok(Card::TradeHands { color }),
+        "A" => return Ok(Card::DiscardAll { color }),
+        "0" => CardValue::Zero,
+        "1" => CardValue::One,
+        "2" => CardValue::Two,
+        "3" => CardValue::Three,
+        "4" => CardValue::Four,
+        "5" => CardValue::Five,
+        "6" => CardValue::Six,
+        "7" => CardValue::Seven,
+        "8" => CardValue::Eight,
+        "9" => CardValue::Nine,
+        _ => return Err(invalid()),
+    };
+
+    Ok(Card::Numeric { color, value })
+}
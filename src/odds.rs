@@ -0,0 +1,132 @@
+//! Estimates of what's in the cards a player can't see, worked out from the ones they can.
+//!
+//! The discard pile is shuffled back into the deck before every turn, so apart from the top card
+//! nothing that has been played stays out of the game. Every card a seat can't see is as likely
+//! to be in the deck as in any other hand, which makes the odds a matter of counting.
+
+use crate::card::{Card, Deck};
+use crate::game::{Direction, GameView};
+use crate::packed::{PackedCard, KINDS};
+
+/// How many of each card one seat hasn't seen, out of a full deck.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardCounter {
+    unseen: [u8; KINDS],
+    total: usize,
+}
+
+impl CardCounter {
+    /// For someone holding `hand`, with `top` face up on the discard pile.
+    pub fn new<'a>(hand: impl IntoIterator<Item = &'a Card>, top: Option<&'a Card>) -> CardCounter {
+        let mut counter = CardCounter { unseen: [0; KINDS], total: 0 };
+
+        for card in Deck::new_deck() {
+            counter.unseen[kind(&card)] += 1;
+            counter.total += 1;
+        }
+
+        for card in hand.into_iter().chain(top) {
+            counter.see(card);
+        }
+
+        counter
+    }
+
+    /// For the seat a view was made for, or for a spectator, who only sees the top card.
+    pub fn from_view(view: &GameView) -> CardCounter {
+        CardCounter::new(&view.hand, view.top_card.as_ref())
+    }
+
+    /// Takes a card out of the unseen ones. When a supplementary deck has brought in more copies
+    /// of a card than one deck holds, the extra copies are ignored.
+    pub fn see(&mut self, card: &Card) {
+        let count = &mut self.unseen[kind(card)];
+
+        if *count > 0 {
+            *count -= 1;
+            self.total -= 1;
+        }
+    }
+
+    pub fn unseen(&self, card: &Card) -> u8 {
+        self.unseen[kind(card)]
+    }
+
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// How many of the unseen cards `matches` accepts.
+    pub fn count(&self, matches: impl Fn(Card) -> bool) -> usize {
+        (0..KINDS)
+            .filter(|&kind| self.unseen[kind] > 0 && matches(Card::from(PackedCard::from_kind(kind))))
+            .map(|kind| self.unseen[kind] as usize)
+            .sum()
+    }
+
+    /// The chance that `cards` cards dealt from the unseen ones include at least one that
+    /// `matches` accepts.
+    pub fn chance_of_any(&self, cards: usize, matches: impl Fn(Card) -> bool) -> f64 {
+        let matching = self.count(matches);
+        let others = self.total - matching;
+        let cards = cards.min(self.total);
+
+        if matching == 0 {
+            return 0.0;
+        }
+
+        if cards > others {
+            return 1.0;
+        }
+
+        // Drawing without replacement: the chance of missing every match, one card at a time.
+        let missed = (0..cards).fold(1.0, |missed, drawn| missed * (others - drawn) as f64 / (self.total - drawn) as f64);
+
+        1.0 - missed
+    }
+}
+
+/// The chance that whoever plays after the view's seat can answer `card` if it's played now:
+/// by stacking the same card on a draw card, or by playing anything at all on the rest.
+pub fn next_can_respond(view: &GameView, card: Card) -> f64 {
+    let seat = view.seat.unwrap_or(view.current_player);
+    let count = view.players.len();
+
+    let direction = match (card, view.direction) {
+        (Card::Reverse { .. }, Direction::Clockwise) => Direction::CounterClockwise,
+        (Card::Reverse { .. }, Direction::CounterClockwise) => Direction::Clockwise,
+        (_, direction) => direction,
+    };
+
+    let step = |seat: usize| match direction {
+        Direction::Clockwise => (seat + 1) % count,
+        Direction::CounterClockwise => (seat + count - 1) % count,
+    };
+
+    let next = match card {
+        Card::Skip { .. } => step(step(seat)),
+        _ => step(seat),
+    };
+
+    let stacking = matches!(card, Card::DrawTwo { .. } | Card::DrawFour { .. });
+    let counter = CardCounter::from_view(view);
+
+    counter.chance_of_any(view.players[next].cards, |held| if stacking { held == card } else { held.can_play_on(&card) })
+}
+
+/// The chance that at least one player other than the view's seat holds a card `matches`
+/// accepts.
+pub fn any_opponent_holds(view: &GameView, matches: impl Fn(Card) -> bool) -> f64 {
+    let cards = view.players
+        .iter()
+        .enumerate()
+        .filter(|(seat, _)| Some(*seat) != view.seat)
+        .map(|(_, player)| player.cards)
+        .sum();
+
+    CardCounter::from_view(view).chance_of_any(cards, matches)
+}
+
+fn kind(card: &Card) -> usize {
+    PackedCard::from(*card).kind()
+}
@@ -0,0 +1,74 @@
+//! How long a game waits between turns. The engine never looks at the clock itself: whoever
+//! shows the game decides, so a bot match can run flat out while a GUI waits for its animations.
+
+#[cfg(feature = "std")]
+use std::sync::mpsc::{self, Receiver, Sender};
+#[cfg(feature = "std")]
+use std::time::Duration;
+
+use crate::game::GameView;
+
+/// Called by [`GameState::start`](crate::game::GameState::start) after each turn, and returns
+/// once the next one should be played. Set one with
+/// [`GameState::set_pacer`](crate::game::GameState::set_pacer).
+pub trait Pacer: Send {
+    /// `view` is the public view of the table as the turn left it.
+    fn pace(&mut self, view: &GameView);
+}
+
+/// Plays the next turn straight away, for games nobody is watching.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct NoPacing;
+
+impl Pacer for NoPacing {
+    fn pace(&mut self, _view: &GameView) {}
+}
+
+/// Waits the same time after every turn, so people can follow the computer players.
+#[cfg(feature = "std")]
+#[derive(Debug, Copy, Clone)]
+pub struct Delay(pub Duration);
+
+#[cfg(feature = "std")]
+impl Pacer for Delay {
+    fn pace(&mut self, _view: &GameView) {
+        if !self.0.is_zero() {
+            std::thread::sleep(self.0);
+        }
+    }
+}
+
+/// Waits for the frontend to say it's done showing the turn, e.g. once an animation completes.
+/// Each call to [`Signal::done`] lets one more turn through; if the frontend goes away, the game
+/// carries on without waiting.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct Signalled(Receiver<()>);
+
+/// The frontend's end of a [`Signalled`] pacer.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct Signal(Sender<()>);
+
+#[cfg(feature = "std")]
+impl Signalled {
+    pub fn new() -> (Signalled, Signal) {
+        let (sender, receiver) = mpsc::channel();
+        (Signalled(receiver), Signal(sender))
+    }
+}
+
+#[cfg(feature = "std")]
+impl Signal {
+    pub fn done(&self) {
+        // A game that has finished has dropped its end, and has nothing left to wait for.
+        let _ = self.0.send(());
+    }
+}
+
+#[cfg(feature = "std")]
+impl Pacer for Signalled {
+    fn pace(&mut self, _view: &GameView) {
+        let _ = self.0.recv();
+    }
+}
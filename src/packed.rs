@@ -0,0 +1,290 @@
+//! A one-byte card and a count-array hand, for simulations that play millions of games.
+//!
+//! Convert at the edges with `From`; everything else in the crate works with [`Card`].
+
+use alloc::vec::Vec;
+
+use crate::card::{Card, CardColor, CardValue};
+
+const COLORS: [CardColor; 4] = [CardColor::Red, CardColor::Green, CardColor::Blue, CardColor::Yellow];
+const VALUES: [CardValue; 10] = [
+    CardValue::Zero, CardValue::One, CardValue::Two, CardValue::Three, CardValue::Four,
+    CardValue::Five, CardValue::Six, CardValue::Seven, CardValue::Eight, CardValue::Nine,
+];
+
+/// The number of distinct cards, ignoring the colors chosen for wild cards.
+pub const KINDS: usize = 63;
+
+const WILD: u8 = 52;
+const DRAW_FOUR: u8 = 53;
+/// The kind of a Wild Skip Everyone, whose codes come after the rest.
+const SKIP_EVERYONE_KIND: u8 = 54;
+const SKIP_EVERYONE: u8 = 63;
+/// The first code of the UNO Attack cards, four Trade Hands and then four Discard All.
+const ATTACK: u8 = 68;
+/// How far an UNO Attack card's kind is below its code.
+const ATTACK_OFFSET: u8 = ATTACK - (SKIP_EVERYONE_KIND + 1);
+const CODES: usize = 76;
+
+/// A card in one byte.
+///
+/// Colored cards are `color * 13 + rank`, where ranks 0-9 are the numbers followed by skip,
+/// reverse and draw two. 52 and 53 are wild and draw four cards without a color, and 54-61
+/// are the same two with one chosen. Cards added later come after, so the older codes kept
+/// their meaning: the Wild Skip Everyone is 63 without a color and 64-67 with one, then Trade
+/// Hands and Discard All take 68-75, a color at a time. 62 is never a card.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PackedCard(u8);
+
+impl PackedCard {
+    /// The card with any chosen color dropped, from `0` to [`KINDS`], for indexing tables.
+    pub fn kind(self) -> usize {
+        kind_of(self.0) as usize
+    }
+
+    /// The card whose [`kind`](PackedCard::kind) is `kind`. Panics if it's out of range.
+    pub fn from_kind(kind: usize) -> PackedCard {
+        assert!(kind < KINDS, "{kind} is not a card kind");
+        PackedCard(code_of(kind as u8))
+    }
+
+    pub fn code(self) -> u8 {
+        self.0
+    }
+
+    pub fn from_code(code: u8) -> Option<PackedCard> {
+        ((code as usize) < CODES && code != 62).then_some(PackedCard(code))
+    }
+
+    pub fn is_wild(self) -> bool {
+        is_wild(self.0)
+    }
+
+    /// Whether this can be played on `top`, as [`Card::can_play_on`] decides.
+    pub fn can_play_on(self, top: PackedCard) -> bool {
+        PLAYABLE[top.0 as usize] & (1 << self.kind()) != 0
+    }
+
+    /// A bit for every [`kind`](PackedCard::kind) of card that can be played on this one.
+    pub fn playable_kinds(self) -> u64 {
+        PLAYABLE[self.0 as usize]
+    }
+}
+
+/// [`PackedCard::playable_kinds`] for every code, worked out at compile time.
+const PLAYABLE: [u64; CODES] = {
+    let mut table = [0; CODES];
+    let mut top = 0;
+
+    while top < CODES as u8 {
+        let mut kind = 0;
+
+        while kind < KINDS as u8 {
+            if playable(code_of(kind), top) {
+                table[top as usize] |= 1 << kind;
+            }
+
+            kind += 1;
+        }
+
+        top += 1;
+    }
+
+    table
+};
+
+const fn kind_of(code: u8) -> u8 {
+    match code {
+        code if code < 54 => code,
+        code if code < SKIP_EVERYONE => WILD + (code - 54) / 4,
+        code if code < ATTACK => SKIP_EVERYONE_KIND,
+        code => code - ATTACK_OFFSET,
+    }
+}
+
+/// The code of a kind of card, without a color if it's wild.
+const fn code_of(kind: u8) -> u8 {
+    match kind {
+        SKIP_EVERYONE_KIND => SKIP_EVERYONE,
+        kind if kind > SKIP_EVERYONE_KIND => kind + ATTACK_OFFSET,
+        kind => kind,
+    }
+}
+
+const fn is_wild(code: u8) -> bool {
+    matches!(kind_of(code), WILD | DRAW_FOUR | SKIP_EVERYONE_KIND)
+}
+
+/// The printed or chosen color of a code, as an index into red, green, blue and yellow.
+const fn color_index(code: u8) -> Option<u8> {
+    match code {
+        code if code < WILD => Some(code / 13),
+        WILD | DRAW_FOUR | 62 | SKIP_EVERYONE => None,
+        code if code < SKIP_EVERYONE => Some((code - 54) % 4),
+        code if code < ATTACK => Some(code - SKIP_EVERYONE - 1),
+        code => Some((code - ATTACK) % 4),
+    }
+}
+
+/// What a colored card is besides its color, so that two of the same can go on each other:
+/// 0-12 as in the colored codes, then 13 for Trade Hands and 14 for Discard All.
+const fn rank(code: u8) -> Option<u8> {
+    match code {
+        code if code < WILD => Some(code % 13),
+        code if code >= ATTACK => Some(13 + (code - ATTACK) / 4),
+        _ => None,
+    }
+}
+
+const fn playable(card: u8, top: u8) -> bool {
+    if is_wild(card) {
+        return true;
+    }
+
+    let same_rank = match (rank(card), rank(top)) {
+        (Some(a), Some(b)) => a == b,
+        _ => false,
+    };
+
+    match (color_index(card), color_index(top)) {
+        (Some(a), Some(b)) => same_rank || a == b,
+        _ => same_rank,
+    }
+}
+
+impl From<Card> for PackedCard {
+    fn from(card: Card) -> PackedCard {
+        let color = |color: CardColor| COLORS.iter().position(|c| *c == color).unwrap() as u8;
+        let chosen = |base: u8, chosen: Option<CardColor>| match base {
+            SKIP_EVERYONE => chosen.map_or(base, |c| SKIP_EVERYONE + 1 + color(c)),
+            base => chosen.map_or(base, |c| 54 + (base - WILD) * 4 + color(c)),
+        };
+
+        PackedCard(match card {
+            Card::Numeric { color: c, value } => color(c) * 13 + value.number(),
+            Card::Skip { color: c } => color(c) * 13 + 10,
+            Card::Reverse { color: c } => color(c) * 13 + 11,
+            Card::DrawTwo { color: c } => color(c) * 13 + 12,
+            Card::Wild { color: c } => chosen(WILD, c),
+            Card::DrawFour { color: c } => chosen(DRAW_FOUR, c),
+            Card::WildSkipEveryone { color: c } => chosen(SKIP_EVERYONE, c),
+            Card::TradeHands { color: c } => ATTACK + color(c),
+            Card::DiscardAll { color: c } => ATTACK + 4 + color(c),
+        })
+    }
+}
+
+impl From<PackedCard> for Card {
+    fn from(card: PackedCard) -> Card {
+        let chosen = color_index(card.0).map(|c| COLORS[c as usize]);
+
+        match card.kind() as u8 {
+            WILD => Card::Wild { color: chosen },
+            DRAW_FOUR => Card::DrawFour { color: chosen },
+            SKIP_EVERYONE_KIND => Card::WildSkipEveryone { color: chosen },
+            _ if card.0 >= ATTACK => {
+                let color = COLORS[(card.0 - ATTACK) as usize % 4];
+
+                match card.0 < ATTACK + 4 {
+                    true => Card::TradeHands { color },
+                    false => Card::DiscardAll { color },
+                }
+            }
+            code => {
+                let color = COLORS[(code / 13) as usize];
+
+                match code % 13 {
+                    10 => Card::Skip { color },
+                    11 => Card::Reverse { color },
+                    12 => Card::DrawTwo { color },
+                    rank => Card::Numeric { color, value: VALUES[rank as usize] },
+                }
+            }
+        }
+    }
+}
+
+/// A hand as a count of each card kind, in a fixed 65 bytes. Chosen wild colors aren't kept,
+/// since they only matter once a card is played.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PackedHand {
+    counts: [u8; KINDS],
+    len: u16,
+}
+
+impl PackedHand {
+    pub fn new() -> PackedHand {
+        PackedHand { counts: [0; KINDS], len: 0 }
+    }
+
+    pub fn insert(&mut self, card: PackedCard) {
+        self.counts[card.kind()] += 1;
+        self.len += 1;
+    }
+
+    /// Removes one copy of `card`; false if there wasn't one.
+    pub fn remove(&mut self, card: PackedCard) -> bool {
+        match &mut self.counts[card.kind()] {
+            0 => false,
+            count => {
+                *count -= 1;
+                self.len -= 1;
+                true
+            }
+        }
+    }
+
+    pub fn contains(&self, card: PackedCard) -> bool {
+        self.counts[card.kind()] > 0
+    }
+
+    pub fn count(&self, card: PackedCard) -> u8 {
+        self.counts[card.kind()]
+    }
+
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Every card, in kind order, with wild cards uncolored.
+    pub fn iter(&self) -> impl Iterator<Item = PackedCard> + '_ {
+        self.counts
+            .iter()
+            .enumerate()
+            .flat_map(|(kind, count)| core::iter::repeat_n(PackedCard::from_kind(kind), *count as usize))
+    }
+}
+
+impl Default for PackedHand {
+    fn default() -> PackedHand {
+        PackedHand::new()
+    }
+}
+
+impl FromIterator<PackedCard> for PackedHand {
+    fn from_iter<I: IntoIterator<Item = PackedCard>>(cards: I) -> PackedHand {
+        let mut hand = PackedHand::new();
+
+        for card in cards {
+            hand.insert(card);
+        }
+
+        hand
+    }
+}
+
+impl From<&[Card]> for PackedHand {
+    fn from(cards: &[Card]) -> PackedHand {
+        cards.iter().map(|card| PackedCard::from(*card)).collect()
+    }
+}
+
+impl From<&PackedHand> for Vec<Card> {
+    fn from(hand: &PackedHand) -> Vec<Card> {
+        hand.iter().map(Card::from).collect()
+    }
+}
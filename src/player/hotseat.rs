@@ -0,0 +1,110 @@
+use std::cell::Cell;
+use std::io::stdin;
+
+use crate::card::{Card, CardColor};
+use crate::game::{GameEvent, GameView, Turn, TurnResult};
+use crate::locale::Message;
+use crate::render::RenderConfig;
+
+use super::{Human, HumanPlayer, Player};
+
+/// A [`Human`] for pass-and-play, where several people share one terminal.
+///
+/// Play waits until the terminal has been passed to the player before showing them anything,
+/// and their cards are cleared off the screen once they're done, so the table only ever sees
+/// what the console narrates.
+pub struct HotSeat {
+    human: Human,
+    render: RenderConfig,
+    /// Set between the gate at the start of a turn and hiding the hand at the end of it.
+    in_turn: Cell<bool>,
+}
+
+impl HotSeat {
+    pub fn new(name: String, render: RenderConfig) -> HotSeat {
+        HotSeat {
+            human: Human::new(name, render.clone()),
+            render,
+            in_turn: Cell::new(false),
+        }
+    }
+
+    /// Waits for the player to take the terminal.
+    fn gate(&self) {
+        println!("\n{}", self.render.locale.format(Message::PassTo, &[("player", &self.name())]));
+        wait_for_enter();
+        self.in_turn.set(true);
+    }
+
+    /// Waits for the player to finish looking at their cards, then clears them away.
+    fn hide(&self) {
+        println!("{}", self.render.locale.get(Message::EndTurn));
+        wait_for_enter();
+        print!("\x1B[2J\x1B[1;1H");
+        self.in_turn.set(false);
+    }
+}
+
+fn wait_for_enter() {
+    let mut input = String::new();
+    stdin().read_line(&mut input).unwrap();
+}
+
+impl Player for HotSeat {
+    fn name(&self) -> &str {
+        self.human.name()
+    }
+
+    fn execute_turn(&mut self, turn: &Turn) -> TurnResult {
+        self.gate();
+        println!("{}", self.render.locale.format(Message::TopCard, &[("card", &turn.last_card.display(&self.render))]));
+
+        let result = self.human.execute_turn(turn);
+
+        // Drawing shows the new cards first, in observe_turn_skip.
+        if let TurnResult::Played(_) = result {
+            self.hide();
+        }
+
+        result
+    }
+
+    fn observe_turn(&self, _other: &dyn Player, _card: &Card) {
+        // Everyone sharing the terminal would see it once per seat; the console narrates it.
+    }
+
+    fn observe_turn_skip(&self, observed_cards: Option<&[Card]>) {
+        if observed_cards.is_none() {
+            return;
+        }
+
+        // Forced draws happen without a turn, so the terminal has to be passed over first.
+        if !self.in_turn.get() {
+            self.gate();
+        }
+
+        self.human.observe_turn_skip(observed_cards);
+        self.hide();
+    }
+
+    fn choose_swap_target(&mut self, view: &GameView, candidates: &[usize]) -> usize {
+        // The terminal was passed on once the card was down, so it has to come back.
+        self.gate();
+        let seat = self.human.choose_swap_target(view, candidates);
+        self.hide();
+        seat
+    }
+
+    fn choose_second_color(&mut self, view: &GameView, first: CardColor) -> Option<CardColor> {
+        self.gate();
+        let second = self.human.choose_second_color(view, first);
+        self.hide();
+        second
+    }
+
+    fn observe_event(&mut self, event: &GameEvent, view: &GameView) {
+        self.human.observe_event(event, view);
+    }
+}
+
+impl HumanPlayer for HotSeat {}
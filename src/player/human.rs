@@ -0,0 +1,232 @@
+use std::io::stdin;
+use std::str::FromStr;
+
+use crate::card::{Card, CardColor};
+use crate::game::{GameEvent, GameView, Turn, TurnResult};
+use crate::locale::Message;
+use crate::render::RenderConfig;
+
+use super::{HumanPlayer, Player};
+
+/// Plays from the terminal, reading moves from standard input.
+pub struct Human {
+    name: String,
+    render: RenderConfig,
+}
+
+impl Human {
+    pub fn new(name: String, render: RenderConfig) -> Human {
+        Human {
+            name,
+            render,
+        }
+    }
+
+fn get_action(&mut self, turn: &Turn) -> Option<TurnResult> {
+    let mut input = String::new();
+
+    loop {
+        stdin().read_line(&mut input).unwrap();
+        let cur_input = input.trim().to_lowercase();
+
+        match cur_input.as_str() {
+            "play" => {
+                break self.get_card(turn)
+            },
+            "draw" => {
+                return Some(TurnResult::Drew);
+            },
+            _ => {
+                input.clear();
+                println!("{}", self.render.locale.get(Message::InvalidInput));
+            }
+        }
+    }
+}
+
+    fn get_card(&mut self, turn: &Turn) -> Option<TurnResult> {
+        let mut input = String::new();
+
+        println!("{}", self.render.locale.get(Message::PlayableCards));
+
+        for (i, card) in turn.playable_hand.iter().enumerate() {
+            println!("{i}: {}", card.display(&self.render));
+        }
+
+        println!("{}", self.render.locale.get(Message::SelectCard));
+
+        let index = loop {
+            stdin().read_line(&mut input).unwrap();
+            let cur_input = input.trim().to_lowercase();
+
+            if cur_input == "back" {
+                return None;
+            }
+
+            // TODO: Clear?
+            match cur_input.parse::<usize>() {
+                Ok(index) => {
+                    if index >= turn.playable_hand.len() {
+                        input.clear();
+                        println!("{}", self.render.locale.get(Message::InvalidInput));
+                        continue;
+                    }
+
+                    break index;
+                },
+                Err(_) => {
+                    input.clear();
+                    println!("{}", self.render.locale.get(Message::InvalidInput));
+                    continue;
+                }
+            }
+        };
+
+        let mut card = turn.playable_hand[index];
+
+        match card {
+            _ if card.is_wild() => {
+                let color = self.get_color();
+
+                color.map(|color| TurnResult::Played(*card.with_color(color).unwrap()))
+            },
+            _ => {
+                Some(TurnResult::Played(card))
+            }
+        }
+    }
+
+    fn get_color(&self) -> Option<CardColor> {
+        let mut input = String::new();
+        loop {
+            println!("{}", self.render.locale.get(Message::ChooseColor));
+
+            stdin().read_line(&mut input).unwrap();
+            let cur_input = input.trim().to_lowercase();
+
+            if cur_input == "back" {
+                return None;
+            }
+
+            if let Ok(color) = CardColor::from_str(&cur_input) {
+                return Some(color);
+            } else {
+                println!("{}", self.render.locale.format(Message::InvalidColor, &[("input", &cur_input)]));
+                continue;
+            }
+        }
+    }
+}
+
+impl Player for Human {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn execute_turn(&mut self, turn: &Turn) -> TurnResult {
+        println!("{}", self.render.locale.get(Message::YourTurn));
+
+        let can_play = !turn.playable_hand.is_empty();
+
+        loop {
+            if can_play {
+                println!("{}", self.render.locale.get(Message::ChooseAction));
+            }
+            else {
+                println!("{}", self.render.locale.get(Message::MustDraw));
+            }
+
+            if can_play {
+                let action = self.get_action(turn);
+
+                if let Some(action) = action {
+                    break action;
+                }
+            }
+            else {
+                break TurnResult::Drew
+            }
+        }
+
+    }
+
+    fn observe_turn(&self, other: &dyn Player, card: &Card) {
+        println!("{}", self.render.locale.format(Message::Played, &[("player", &other.name()), ("card", &card.display(&self.render))]));
+    }
+
+    fn observe_turn_skip(&self, observed_cards: Option<&[Card]>) {
+        if let Some(observed_cards) = observed_cards {
+            if observed_cards.len() == 1 {
+                println!("{}", self.render.locale.format(Message::YouDrewCard, &[("card", &observed_cards[0].display(&self.render))]));
+            }
+            else {
+                let cards = observed_cards
+                    .iter()
+                    .skip(1)
+                    .fold(observed_cards[0].display(&self.render).to_string(), |acc, card| acc + &format!(", {}", card.display(&self.render)));
+
+                println!("{}", self.render.locale.format(Message::YouDrewCards, &[("count", &observed_cards.len()), ("cards", &cards)]));
+            }
+        }
+        else {
+            println!("{}", self.render.locale.get(Message::YouWereSkipped));
+        }
+    }
+
+    fn choose_swap_target(&mut self, view: &GameView, candidates: &[usize]) -> usize {
+        let locale = &self.render.locale;
+        println!("{}", locale.get(Message::ChooseSwapTarget));
+
+        for (index, &seat) in candidates.iter().enumerate() {
+            let player = &view.players[seat];
+            println!("{}", locale.format(Message::SwapCandidate, &[("index", &index), ("player", &player.name), ("count", &player.cards)]));
+        }
+
+        let mut input = String::new();
+
+        loop {
+            input.clear();
+            stdin().read_line(&mut input).unwrap();
+
+            match input.trim().parse::<usize>().ok().and_then(|index| candidates.get(index)) {
+                Some(&seat) => return seat,
+                None => println!("{}", locale.get(Message::InvalidInput)),
+            }
+        }
+    }
+
+    fn choose_second_color(&mut self, _view: &GameView, first: CardColor) -> Option<CardColor> {
+        let locale = &self.render.locale;
+        println!("{}", locale.get(Message::ChooseSecondColor));
+
+        let mut input = String::new();
+
+        loop {
+            input.clear();
+            stdin().read_line(&mut input).unwrap();
+            let cur_input = input.trim();
+
+            if cur_input.is_empty() {
+                return None;
+            }
+
+            match CardColor::from_str(cur_input) {
+                Ok(color) if color != first => return Some(color),
+                _ => println!("{}", locale.format(Message::InvalidColor, &[("input", &cur_input)])),
+            }
+        }
+    }
+
+    fn observe_event(&mut self, event: &GameEvent, view: &GameView) {
+        // The console shows everything else; emotes sent to one player only reach that player.
+        match event {
+            GameEvent::Emote { player, to: Some(to), emote } if view.player_id() == Some(*to) => {
+                let locale = &self.render.locale;
+                println!("{}", locale.format(Message::PrivateEmote, &[("player", &view.name_of(*player)), ("emote", &locale.get(Message::emote(*emote)))]));
+            }
+            _ => {}
+        }
+    }
+}
+
+impl HumanPlayer for Human {}
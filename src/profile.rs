@@ -0,0 +1,160 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::summary::RoundSummary;
+
+const STARTING_RATING: f64 = 1000.0;
+const K_FACTOR: f64 = 32.0;
+
+/// A player's standing across every recorded round.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    pub games_played: u32,
+    pub wins: u32,
+    pub points: u32,
+    pub rating: f64,
+}
+
+impl Profile {
+    pub fn new(name: &str) -> Profile {
+        Profile {
+            name: name.to_string(),
+            games_played: 0,
+            wins: 0,
+            points: 0,
+            rating: STARTING_RATING,
+        }
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        if self.games_played == 0 {
+            return 0.0;
+        }
+
+        self.wins as f64 / self.games_played as f64
+    }
+}
+
+/// Player profiles, kept in a JSON file between sessions.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProfileStore {
+    profiles: Vec<Profile>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Ranking {
+    Rating,
+    Wins,
+    Points,
+    WinRate,
+}
+
+/// Profiles ordered best-first by a [`Ranking`].
+pub struct Leaderboard<'a> {
+    entries: Vec<&'a Profile>,
+}
+
+impl ProfileStore {
+    /// Reads the store at `path`, or starts an empty one if the file doesn't exist yet.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<ProfileStore> {
+        match fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(ProfileStore::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, json)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Profile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    /// The profile for `name`, created on first use.
+    pub fn profile_mut(&mut self, name: &str) -> &mut Profile {
+        let index = match self.profiles.iter().position(|profile| profile.name == name) {
+            Some(index) => index,
+            None => {
+                self.profiles.push(Profile::new(name));
+                self.profiles.len() - 1
+            }
+        };
+
+        &mut self.profiles[index]
+    }
+
+    /// Updates everyone who took part in the round. Ratings move Elo-style, with the winner
+    /// having beaten each other player once; the adjustment is split so a round is worth the
+    /// same regardless of table size.
+    pub fn record(&mut self, summary: &RoundSummary) {
+        let losers = summary.players
+            .iter()
+            .filter(|player| player.name != summary.winner)
+            .map(|player| (player.name.as_str(), self.get(&player.name).map_or(STARTING_RATING, |p| p.rating)))
+            .collect::<Vec<(&str, f64)>>();
+
+        let winner_rating = self.get(&summary.winner).map_or(STARTING_RATING, |p| p.rating);
+        let k = K_FACTOR / losers.len().max(1) as f64;
+        let mut winner_gain = 0.0;
+
+        for (name, rating) in &losers {
+            let expected = 1.0 / (1.0 + 10f64.powf((rating - winner_rating) / 400.0));
+            let delta = k * (1.0 - expected);
+
+            winner_gain += delta;
+
+            let loser = self.profile_mut(name);
+            loser.games_played += 1;
+            loser.rating -= delta;
+        }
+
+        let winner = self.profile_mut(&summary.winner);
+        winner.games_played += 1;
+        winner.wins += 1;
+        winner.points += summary.points;
+        winner.rating += winner_gain;
+    }
+
+    pub fn leaderboard(&self, ranking: Ranking) -> Leaderboard<'_> {
+        let mut entries = self.profiles.iter().collect::<Vec<&Profile>>();
+
+        entries.sort_by(|a, b| match ranking {
+            Ranking::Rating => b.rating.total_cmp(&a.rating),
+            Ranking::Wins => b.wins.cmp(&a.wins),
+            Ranking::Points => b.points.cmp(&a.points),
+            Ranking::WinRate => b.win_rate().total_cmp(&a.win_rate()),
+        });
+
+        Leaderboard { entries }
+    }
+}
+
+impl<'a> Leaderboard<'a> {
+    pub fn top(&self, count: usize) -> &[&'a Profile] {
+        &self.entries[..count.min(self.entries.len())]
+    }
+
+    /// The 1-based rank of `name`, if they have a profile.
+    pub fn rank_of(&self, name: &str) -> Option<usize> {
+        self.entries.iter().position(|profile| profile.name == name).map(|index| index + 1)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a Profile> + '_ {
+        self.entries.iter().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use crate::card::{Card, CardColor};
+use crate::locale::{Locale, Message};
+
+/// How cards are written to the terminal.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum RenderMode {
+    /// The plain `Display` text, e.g. "Red Seven".
+    Plain,
+    /// The plain text on a background of the card's color.
+    #[default]
+    Ansi,
+    /// Compact symbols, e.g. "🟥7", "🟦⏭", "+4".
+    Unicode,
+    /// Unambiguous full names and descriptive narration of every state change, for screen readers.
+    Accessible,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RenderConfig {
+    pub mode: RenderMode,
+    pub locale: Locale,
+    pub names: CardNames,
+}
+
+impl RenderConfig {
+    pub fn new(mode: RenderMode) -> RenderConfig {
+        RenderConfig { mode, locale: Locale::default(), names: CardNames::default() }
+    }
+}
+
+/// What a card shows, whatever its color, for naming it in [`CardNames`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Face {
+    Number(u8),
+    Skip,
+    Reverse,
+    DrawTwo,
+    TradeHands,
+    DiscardAll,
+    Wild,
+    DrawFour,
+    SkipEveryone,
+}
+
+impl Face {
+    pub fn of(card: &Card) -> Face {
+        match card {
+            Card::Numeric { value, .. } => Face::Number(value.number()),
+            Card::Skip { .. } => Face::Skip,
+            Card::Reverse { .. } => Face::Reverse,
+            Card::DrawTwo { .. } => Face::DrawTwo,
+            Card::TradeHands { .. } => Face::TradeHands,
+            Card::DiscardAll { .. } => Face::DiscardAll,
+            Card::Wild { .. } => Face::Wild,
+            Card::DrawFour { .. } => Face::DrawFour,
+            Card::WildSkipEveryone { .. } => Face::SkipEveryone,
+        }
+    }
+}
+
+/// House names for faces and colors, for themed decks. Names are used wherever cards are
+/// written out in words, and symbols in [`RenderMode::Unicode`]; anything not given one is
+/// written as usual.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CardNames {
+    faces: HashMap<Face, String>,
+    colors: HashMap<CardColor, String>,
+    face_symbols: HashMap<Face, String>,
+    color_symbols: HashMap<CardColor, String>,
+}
+
+impl CardNames {
+    pub fn name(&mut self, face: Face, name: impl Into<String>) -> &mut CardNames {
+        self.faces.insert(face, name.into());
+        self
+    }
+
+    pub fn name_color(&mut self, color: CardColor, name: impl Into<String>) -> &mut CardNames {
+        self.colors.insert(color, name.into());
+        self
+    }
+
+    pub fn symbol(&mut self, face: Face, symbol: impl Into<String>) -> &mut CardNames {
+        self.face_symbols.insert(face, symbol.into());
+        self
+    }
+
+    pub fn color_symbol(&mut self, color: CardColor, symbol: impl Into<String>) -> &mut CardNames {
+        self.color_symbols.insert(color, symbol.into());
+        self
+    }
+
+    /// Whether `card` is written differently from usual in words.
+    fn renames(&self, card: &Card) -> bool {
+        self.faces.contains_key(&Face::of(card)) || card.color().is_some_and(|color| self.colors.contains_key(&color))
+    }
+
+    fn face(&self, card: &Card) -> String {
+        if let Some(name) = self.faces.get(&Face::of(card)) {
+            return name.clone();
+        }
+
+        match card {
+            Card::Numeric { value, .. } => value.to_string(),
+            Card::Skip { .. } => "Skip".to_string(),
+            Card::Reverse { .. } => "Reverse".to_string(),
+            Card::DrawTwo { .. } => "Draw Two".to_string(),
+            Card::TradeHands { .. } => "Trade Hands".to_string(),
+            Card::DiscardAll { .. } => "Discard All".to_string(),
+            Card::Wild { .. } => "Wild Card".to_string(),
+            Card::DrawFour { .. } => "Draw Four".to_string(),
+            Card::WildSkipEveryone { .. } => "Wild Skip Everyone".to_string(),
+        }
+    }
+
+    fn color(&self, color: CardColor) -> String {
+        self.colors.get(&color).cloned().unwrap_or_else(|| color.to_string())
+    }
+
+    /// `card` written out in words as its [`Display`] would, with the house names in.
+    pub fn text(&self, card: &Card) -> String {
+        match card.color() {
+            Some(color) if card.is_wild() => format!("{} ({})", self.face(card), self.color(color)),
+            Some(color) => format!("{} {}", self.color(color), self.face(card)),
+            None => self.face(card),
+        }
+    }
+}
+
+/// A card formatted according to a [`RenderConfig`]; see [`Card::display`].
+pub struct CardDisplay<'a> {
+    card: Card,
+    config: &'a RenderConfig,
+}
+
+impl Card {
+    pub fn display<'a>(&self, config: &'a RenderConfig) -> CardDisplay<'a> {
+        CardDisplay { card: *self, config }
+    }
+}
+
+const RESET: &str = "\x1b[0m";
+const WILD: &str = "\x1b[41;97mW\x1b[44mi\x1b[42ml\x1b[43;30md\x1b[0m";
+const DRAW_FOUR: &str = "\x1b[41;97mDr\x1b[44maw \x1b[42mFo\x1b[43;30mur\x1b[0m";
+const SKIP_EVERYONE: &str = "\x1b[41;97mSkip \x1b[44mEv\x1b[42mery\x1b[43;30mone\x1b[0m";
+
+fn background(color: CardColor) -> &'static str {
+    match color {
+        CardColor::Red => "\x1b[41;97m",
+        CardColor::Green => "\x1b[42;30m",
+        CardColor::Blue => "\x1b[44;97m",
+        CardColor::Yellow => "\x1b[43;30m",
+    }
+}
+
+fn square(color: CardColor) -> &'static str {
+    match color {
+        CardColor::Red => "🟥",
+        CardColor::Green => "🟩",
+        CardColor::Blue => "🟦",
+        CardColor::Yellow => "🟨",
+    }
+}
+
+impl Display for CardDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let card = self.card;
+        let names = &self.config.names;
+
+        match self.config.mode {
+            RenderMode::Plain if names.renames(&card) => write!(f, "{}", names.text(&card)),
+            RenderMode::Plain => write!(f, "{card}"),
+            RenderMode::Accessible => match card {
+                _ if card.is_wild() => {
+                    let name = match names.faces.get(&Face::of(&card)) {
+                        Some(name) => name.clone(),
+                        None => self.config.locale.get(match card {
+                            Card::Wild { .. } => Message::WildCardName,
+                            Card::DrawFour { .. } => Message::DrawFourName,
+                            _ => Message::SkipEveryoneName,
+                        }).to_string(),
+                    };
+
+                    match card.color() {
+                        Some(color) => write!(f, "{}", self.config.locale.format(Message::ChosenColor, &[("card", &name), ("color", &names.color(color))])),
+                        None => write!(f, "{name}"),
+                    }
+                }
+                _ => write!(f, "{}", names.text(&card)),
+            },
+            // Renamed wilds lose their rainbow, which is only for the usual name.
+            RenderMode::Ansi if names.renames(&card) => match card.color() {
+                Some(color) if card.is_wild() => write!(f, "{} ({}{}{RESET})", names.face(&card), background(color), names.color(color)),
+                Some(color) => write!(f, "{}{}{RESET}", background(color), names.text(&card)),
+                None => write!(f, "{}", names.face(&card)),
+            },
+            RenderMode::Ansi => match card {
+                Card::Wild { color: Some(color) } => write!(f, "{WILD} ({}{color}{RESET})", background(color)),
+                Card::DrawFour { color: Some(color) } => write!(f, "{DRAW_FOUR} ({}{color}{RESET})", background(color)),
+                Card::Wild { color: None } => write!(f, "{WILD}"),
+                Card::DrawFour { color: None } => write!(f, "{DRAW_FOUR}"),
+                Card::WildSkipEveryone { color: Some(color) } => write!(f, "{SKIP_EVERYONE} ({}{color}{RESET})", background(color)),
+                Card::WildSkipEveryone { color: None } => write!(f, "{SKIP_EVERYONE}"),
+                _ => write!(f, "{}{card}{RESET}", background(card.color().unwrap())),
+            },
+            RenderMode::Unicode => {
+                if let Some(color) = card.color() {
+                    match names.color_symbols.get(&color) {
+                        Some(symbol) => write!(f, "{symbol}")?,
+                        None => write!(f, "{}", square(color))?,
+                    }
+                }
+
+                if let Some(symbol) = names.face_symbols.get(&Face::of(&card)) {
+                    return write!(f, "{symbol}");
+                }
+
+                match card {
+                    Card::Numeric { value, .. } => write!(f, "{}", value.number()),
+                    Card::Skip { .. } => write!(f, "⏭"),
+                    Card::Reverse { .. } => write!(f, "🔄"),
+                    Card::DrawTwo { .. } => write!(f, "+2"),
+                    Card::Wild { .. } => write!(f, "🌈"),
+                    Card::DrawFour { .. } => write!(f, "+4"),
+                    Card::WildSkipEveryone { .. } => write!(f, "⏩"),
+                    Card::TradeHands { .. } => write!(f, "⇄"),
+                    Card::DiscardAll { .. } => write!(f, "🗑"),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,463 @@
+use alloc::{string::String, vec::Vec};
+use alloc::collections::VecDeque;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use crate::card::{Card, CardColor};
+use crate::game::{self, GameEvent, GameState, GameView, Opening, Spectator, Turn, TurnResult};
+use crate::player::Player;
+use crate::rules::{Rules, ENGINE_VERSION};
+use crate::snapshot::Snapshot;
+
+/// A finished or ongoing game reduced to its seed and the moves made, from
+/// [`GameState::replay`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Replay {
+    pub seed: u64,
+    /// In seating order.
+    pub players: Vec<String>,
+    /// The fingerprint once the cards were dealt.
+    pub dealt: u64,
+    pub actions: Vec<ReplayAction>,
+    /// Recordings from before rules could be changed were all played by the defaults.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rules: Rules,
+    /// See [`GameState::seating`]. Empty when the seats weren't shuffled, as in recordings from
+    /// before they could be.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub seating: Vec<usize>,
+    /// See [`GameState::set_first_player`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub first_player: Option<usize>,
+    /// See [`GameState::set_opening`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub opening: Opening,
+    /// See [`GameState::set_hand_sizes`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub hand_sizes: Vec<u8>,
+    /// See [`ENGINE_VERSION`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub engine: u32,
+}
+
+/// A move a player chose, and the fingerprint of the game once it had been carried out.
+/// Forced draws aren't choices, so they're only reflected in the fingerprints.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReplayAction {
+    pub seat: usize,
+    pub result: TurnResult,
+    pub fingerprint: u64,
+    /// Who the player swapped hands with, when their move let them choose.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub target: Option<usize>,
+    /// The color named along with a wild's, under
+    /// [`Rules::two_color_wilds`](crate::rules::Rules::two_color_wilds).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub second_color: Option<CardColor>,
+}
+
+/// Where a re-run stopped matching the recording.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Divergence {
+    Deal { expected: u64, actual: u64 },
+    /// `actual` is `None` when the game ended before the action could be made.
+    Action { index: usize, expected: u64, actual: Option<u64> },
+    /// The game was recorded by a later engine than this one, at version `found`, so it isn't
+    /// played again at all.
+    Version { found: u32 },
+}
+
+/// A game as one seat saw it, from [`Replay::perspective`]: every event it was sent with the
+/// view that came along. There's no seed, so it can be shared without giving away anyone
+/// else's hand.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Perspective {
+    /// In [`Replay::players`].
+    pub seat: usize,
+    pub players: Vec<String>,
+    pub rules: Rules,
+    pub frames: Vec<Frame>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Frame {
+    pub event: GameEvent,
+    /// With its fingerprint cleared. It hashes the whole table, so with one hand known the
+    /// others could be worked out from it by trying them.
+    pub view: GameView,
+}
+
+impl Replay {
+    /// Plays the game again from the seed with the recorded moves, checking every fingerprint
+    /// along the way, and reports the first point where the engine's result differs.
+    pub fn verify(&self) -> Result<(), Divergence> {
+        self.play(None).0
+    }
+
+    /// The game as `seat` saw it: their own hand throughout, and everyone else's cards only as
+    /// they were played. `None` if there's no such seat or the replay doesn't
+    /// [`verify`](Replay::verify), since what it shows would then be a different game.
+    pub fn perspective(&self, seat: usize) -> Option<Perspective> {
+        if seat >= self.players.len() {
+            return None;
+        }
+
+        let (verified, frames) = self.play(Some(seat));
+        verified.ok()?;
+
+        Some(Perspective { seat, players: self.players.clone(), rules: self.rules, frames })
+    }
+
+    /// The game as it stood at the end of the turn its first `moves` recorded moves were made
+    /// by, for comparing two points in it with [`diff`](crate::diff::diff). A turn can hold two
+    /// moves, such as a draw and then playing the card drawn, so the snapshot can have one more
+    /// than asked for. No moves gives the table as it was dealt. `None` if the recording doesn't
+    /// have that many, stops matching the engine before it gets there, or was recorded by a
+    /// later engine.
+    pub fn snapshot_after(&self, moves: usize) -> Option<Snapshot> {
+        if moves > self.actions.len() || self.engine > ENGINE_VERSION {
+            return None;
+        }
+
+        let mut seats = self.seats(None);
+        let mut game = self.game(&mut seats);
+        game.play_turn();
+
+        while game.actions().len() < moves {
+            if game.play_turn().is_some() && game.actions().len() < moves {
+                return None;
+            }
+        }
+
+        (game.actions()[..moves] == self.actions[..moves]).then(|| game.snapshot())
+    }
+
+    /// Plays the recording through, keeping what the `watched` seat is sent if there is one.
+    fn play(&self, watched: Option<usize>) -> (Result<(), Divergence>, Vec<Frame>) {
+        let mut seats = self.seats(watched);
+        let mut game = self.game(&mut seats);
+
+        let verified = self.check(&mut game);
+        drop(game);
+
+        let frames = seats.into_iter().find_map(|seat| seat.frames).unwrap_or_default();
+        (verified, frames)
+    }
+
+    /// A seat for each player, in the order they were given, to make the recorded moves.
+    fn seats(&self, watched: Option<usize>) -> Vec<ScriptedSeat> {
+        let seats = self.players
+            .iter()
+            .enumerate()
+            .map(|(seat, name)| ScriptedSeat {
+                name: name.clone(),
+                moves: self.actions.iter().filter(|a| a.seat == seat).map(|a| a.result).collect(),
+                targets: self.actions.iter().filter(|a| a.seat == seat).filter_map(|a| a.target).collect(),
+                second_colors: self.actions.iter().filter(|a| a.seat == seat && matches!(a.result, TurnResult::Played(card) if card.is_wild())).map(|a| a.second_color).collect(),
+                frames: (watched == Some(seat)).then(Vec::new),
+            })
+            .collect::<Vec<ScriptedSeat>>();
+
+        match self.seating.is_empty() {
+            true => seats,
+            false => game::unshuffle(seats, &self.seating),
+        }
+    }
+
+    /// The game set up as it was recorded, ready for its first turn.
+    fn game<'s>(&self, seats: &'s mut [ScriptedSeat]) -> GameState<'s> {
+        let mut game = GameState::with_seed(seats.iter_mut().map(|seat| seat as &mut dyn Player).collect(), self.seed);
+        game.set_rules(self.rules);
+
+        if !self.seating.is_empty() {
+            game.shuffle_seats();
+        }
+
+        if let Some(first) = self.first_player {
+            game.set_first_player(first);
+        }
+
+        game.set_opening(self.opening);
+        // Sizes a damaged replay couldn't have been dealt with are refused, and the deal then
+        // comes out different from the one recorded.
+        game.set_hand_sizes(self.hand_sizes.clone()).ok();
+
+        game
+    }
+
+    fn check(&self, game: &mut GameState) -> Result<(), Divergence> {
+        if self.engine > ENGINE_VERSION {
+            return Err(Divergence::Version { found: self.engine });
+        }
+
+        game.play_turn();
+
+        let dealt = game.dealt();
+        if dealt != self.dealt {
+            return Err(Divergence::Deal { expected: self.dealt, actual: dealt });
+        }
+
+        for (index, expected) in self.actions.iter().enumerate() {
+            // Forced draws take a turn without adding an action.
+            while game.actions().len() <= index {
+                if game.play_turn().is_some() && game.actions().len() <= index {
+                    return Err(Divergence::Action { index, expected: expected.fingerprint, actual: None });
+                }
+            }
+
+            let actual = game.actions()[index];
+
+            if actual.seat != expected.seat || actual.result != expected.result || actual.target != expected.target || actual.second_color != expected.second_color || actual.fingerprint != expected.fingerprint {
+                return Err(Divergence::Action { index, expected: expected.fingerprint, actual: Some(actual.fingerprint) });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Where a log of events stopped matching what the engine does with it, from
+/// [`GameState::from_events`]. `index` is the position in the log of the first event that
+/// didn't happen, or that something other than it happened in place of.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct EventDivergence {
+    pub index: usize,
+}
+
+/// Plays a game through again from its events, with every choice taken from the log, and
+/// returns it as of the last turn the log covers completely. Keeping a playable card just drawn
+/// isn't an event, so a log ending on such a draw leaves that turn out.
+pub(crate) fn rebuild(names: Vec<String>, seed: u64, events: &[GameEvent]) -> Result<Snapshot, EventDivergence> {
+    let follow = Follow {
+        log: events,
+        position: AtomicUsize::new(0),
+        diverged: AtomicBool::new(false),
+        exhausted: AtomicBool::new(false),
+    };
+
+    let (rules, seating, first_player, opening, hand_sizes) = match events.iter().enumerate().find(|(_, event)| !ignored(event)) {
+        Some((index, GameEvent::Setup { engine, .. })) if *engine > ENGINE_VERSION => return Err(EventDivergence { index }),
+        Some((_, GameEvent::Setup { rules, seating, first_player, opening, hand_sizes, .. })) => (*rules, seating.as_slice(), *first_player, *opening, hand_sizes.clone()),
+        // Logs from before games announced their setup were all played by the defaults.
+        _ => (Rules::default(), &[][..], None, Opening::default(), Vec::new()),
+    };
+
+    let mut seats = names.into_iter().map(|name| LoggedSeat { name, follow: &follow }).collect::<Vec<LoggedSeat>>();
+
+    if !seating.is_empty() {
+        seats = game::unshuffle(seats, seating);
+    }
+
+    let mut follower = Follower(&follow);
+    let mut game = GameState::with_seed(seats.iter_mut().map(|seat| seat as &mut dyn Player).collect(), seed);
+    game.set_rules(rules);
+
+    if !seating.is_empty() {
+        game.shuffle_seats();
+    }
+
+    if let Some(first) = first_player {
+        game.set_first_player(first);
+    }
+
+    game.set_opening(opening);
+
+    if game.set_hand_sizes(hand_sizes).is_err() {
+        return Err(EventDivergence { index: events.iter().position(|event| !ignored(event)).unwrap_or(0) });
+    }
+
+    game.add_spectator(&mut follower);
+
+    let mut last = game.snapshot();
+
+    while let Some(next) = follow.next() {
+        let before = follow.position();
+
+        match next {
+            GameEvent::Paused => game.pause(),
+            GameEvent::Resumed => game.resume(),
+            GameEvent::Aborted { reason } => {
+                game.abort(reason.clone());
+            }
+            GameEvent::PlayerLeft { player, hand } => {
+                if let Some(seat) = game.seat_of(*player) {
+                    game.remove_player(seat, *hand);
+                }
+            }
+            _ => {
+                game.play_turn();
+            }
+        }
+
+        if follow.exhausted.load(Ordering::Relaxed) {
+            break;
+        }
+
+        if follow.diverged.load(Ordering::Relaxed) || follow.position() == before {
+            return Err(EventDivergence { index: follow.position() });
+        }
+
+        last = game.snapshot();
+    }
+
+    Ok(last)
+}
+
+/// Events that don't change the game, so they're skipped both in logs and when comparing.
+fn ignored(event: &GameEvent) -> bool {
+    matches!(
+        event,
+        GameEvent::Emote { .. } | GameEvent::DebugHands { .. } | GameEvent::WindowOpened { .. } | GameEvent::Interjected { .. },
+    )
+}
+
+/// How far through the log a rebuild has got, shared by its seats and the spectator checking
+/// what the engine does against the log.
+struct Follow<'e> {
+    log: &'e [GameEvent],
+    position: AtomicUsize,
+    diverged: AtomicBool,
+    /// The log ended part way through a turn.
+    exhausted: AtomicBool,
+}
+
+impl<'e> Follow<'e> {
+    fn position(&self) -> usize {
+        self.position.load(Ordering::Relaxed)
+    }
+
+    /// The next event in the log that changes the game, moving past any that don't.
+    fn next(&self) -> Option<&'e GameEvent> {
+        let mut position = self.position();
+
+        while self.log.get(position).is_some_and(ignored) {
+            position += 1;
+        }
+
+        self.position.store(position, Ordering::Relaxed);
+        self.log.get(position)
+    }
+}
+
+/// Checks each event the engine sends against the next one in the log.
+struct Follower<'e>(&'e Follow<'e>);
+
+impl Spectator for Follower<'_> {
+    fn observe_event(&mut self, event: &GameEvent, _view: &GameView) {
+        let follow = self.0;
+
+        if follow.exhausted.load(Ordering::Relaxed) || follow.diverged.load(Ordering::Relaxed) || ignored(event) {
+            return;
+        }
+
+        match follow.next() {
+            Some(expected) if expected == event => {
+                follow.position.fetch_add(1, Ordering::Relaxed);
+            }
+            // Logs from before games announced their setup, how draws stacked up, or the hands
+            // left at the end, just don't have those events.
+            _ if matches!(event, GameEvent::Setup { .. } | GameEvent::Stacked { .. } | GameEvent::Revealed { .. }) => {}
+            Some(_) => follow.diverged.store(true, Ordering::Relaxed),
+            None => follow.exhausted.store(true, Ordering::Relaxed),
+        }
+    }
+}
+
+/// Makes whatever move the log has next: the card it plays, or a draw.
+struct LoggedSeat<'e> {
+    name: String,
+    follow: &'e Follow<'e>,
+}
+
+impl Player for LoggedSeat<'_> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn execute_turn(&mut self, turn: &Turn) -> TurnResult {
+        match self.follow.next() {
+            Some(GameEvent::Played { card, .. }) if turn.playable_hand.contains(card) => TurnResult::Played(*card),
+            Some(_) => TurnResult::Drew,
+            None => {
+                self.follow.exhausted.store(true, Ordering::Relaxed);
+                TurnResult::Drew
+            }
+        }
+    }
+
+    /// Whoever the log has the hands traded with next.
+    fn choose_swap_target(&mut self, view: &GameView, candidates: &[usize]) -> usize {
+        let with = match self.follow.next() {
+            Some(GameEvent::Traded { with, .. }) => candidates.iter().copied().find(|&seat| view.players[seat].id == *with),
+            _ => None,
+        };
+
+        with.unwrap_or(candidates[0])
+    }
+
+    /// The other of the colors the log has named next, if it names any.
+    fn choose_second_color(&mut self, _view: &GameView, first: CardColor) -> Option<CardColor> {
+        match self.follow.next() {
+            Some(GameEvent::ColorsNamed { colors, .. }) => colors.iter().find(|color| *color != first),
+            _ => None,
+        }
+    }
+
+    fn observe_turn(&self, _other: &dyn Player, _card: &Card) {}
+
+    fn observe_turn_skip(&self, _observed_cards: Option<&[Card]>) {}
+
+    fn wants_events(&self) -> bool {
+        false
+    }
+}
+
+/// Replays one seat's recorded moves. A move that's no longer legal is swapped for a draw, which
+/// then shows up as a divergence.
+struct ScriptedSeat {
+    name: String,
+    moves: VecDeque<TurnResult>,
+    targets: VecDeque<usize>,
+    /// What was named with each wild played, in turn.
+    second_colors: VecDeque<Option<CardColor>>,
+    /// Everything the seat is sent, when it's the one being watched.
+    frames: Option<Vec<Frame>>,
+}
+
+impl Player for ScriptedSeat {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn execute_turn(&mut self, turn: &Turn) -> TurnResult {
+        match self.moves.pop_front() {
+            Some(TurnResult::Played(card)) if turn.playable_hand.contains(&card) => TurnResult::Played(card),
+            _ => TurnResult::Drew,
+        }
+    }
+
+    fn choose_swap_target(&mut self, _view: &GameView, candidates: &[usize]) -> usize {
+        self.targets.pop_front().unwrap_or(candidates[0])
+    }
+
+    fn choose_second_color(&mut self, _view: &GameView, _first: CardColor) -> Option<CardColor> {
+        self.second_colors.pop_front().flatten()
+    }
+
+    fn observe_turn(&self, _other: &dyn Player, _card: &Card) {}
+
+    fn observe_turn_skip(&self, _observed_cards: Option<&[Card]>) {}
+
+    fn observe_event(&mut self, event: &GameEvent, view: &GameView) {
+        if let Some(frames) = &mut self.frames {
+            frames.push(Frame { event: event.clone(), view: GameView { fingerprint: 0, ..view.clone() } });
+        }
+    }
+
+    fn wants_events(&self) -> bool {
+        self.frames.is_some()
+    }
+}
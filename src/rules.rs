@@ -0,0 +1,859 @@
+//! Which version of the rules a game plays by, and the rules themselves as plain functions of
+//! the table, which the engine plays by and clients can use to check moves for themselves.
+
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+
+use rand::Rng;
+
+use crate::card::{Card, CardColor, CardValue, PointValues};
+use crate::game::Direction;
+use crate::hand::Hand;
+use crate::hook::Effect;
+use crate::packed::PackedCard;
+
+/// The version of the engine, stamped on every [`Snapshot`](crate::snapshot::Snapshot),
+/// [`Replay`](crate::replay::Replay) and log of events. It goes up whenever the same seed and
+/// moves would play out differently, or a recorded field changes meaning, so that a game
+/// recorded by a later engine is refused rather than played out wrong. Games recorded before
+/// it was stamped have `0`, and play as version 1 does; fields added since take their defaults.
+pub const ENGINE_VERSION: u32 = 1;
+
+/// The house rules a game plays by, set with [`GameState::set_rules`](crate::game::GameState::set_rules).
+///
+/// Start from one of the presets and change what's needed, e.g.
+/// `Rules { hand_size: 5, ..Rules::party() }`. The default is
+/// [`classic_stacking`](Rules::classic_stacking), which is how the engine has always played.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rules {
+    /// A player facing a Draw Two or Draw Four can pass it on by playing the same card, adding
+    /// to what the next player has to draw.
+    pub stacking: bool,
+    /// After drawing instead of playing, a player can play the card they drew straight away if
+    /// it fits.
+    pub play_drawn: bool,
+    /// Players who draw instead of playing keep drawing until they get a card they can play.
+    pub draw_until_playable: bool,
+    /// With two players, a Reverse works like a Skip, so whoever played it goes again.
+    pub two_player_reverse_skips: bool,
+    /// A Skip, Reverse or Draw Two turned up by the deal takes effect on the first player, rather
+    /// than a Skip being shuffled back and the others counting as plain cards. A wild is always
+    /// shuffled back.
+    pub first_card_effects: bool,
+    /// How many cards each player is dealt.
+    pub hand_size: u8,
+    /// Games still going after this many turns are aborted.
+    pub max_turns: Option<u32>,
+    /// What cards left in the losers' hands score for the winner.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub points: PointValues,
+    /// A wild played without a color counts as every color until it's covered, as some older
+    /// house decks play it. Otherwise only another wild can go on it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub open_wilds: bool,
+    /// How many [Wild Skip Everyone](Card::WildSkipEveryone) cards are added to the deck.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub skip_everyone_cards: u8,
+    /// How many [Trade Hands](Card::TradeHands) cards of each color are added to the deck.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub trade_hands_cards: u8,
+    /// How many [Discard All](Card::DiscardAll) cards of each color are added to the deck.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub discard_all_cards: u8,
+    /// Draws come from a card launcher, as in UNO Attack: each card a player would draw is a
+    /// press instead, which fires however many cards the launcher decides, none included.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub launcher: Option<Launcher>,
+    /// A limit on how many cards a hand can hold, for long stacking games where hands would
+    /// otherwise keep growing.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub mercy: Option<Mercy>,
+    /// Play goes on after someone goes out, among the players still holding cards, until only
+    /// one is left. Everyone is ranked by when they went out.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub play_to_last: bool,
+    /// Whoever plays a 0 swaps hands with another player of their choosing. This is the 0 of
+    /// Seven-O on its own, without the 7.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub zero_swap: bool,
+    /// How much views say about the size of the draw and discard piles.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pile_sizes: PileVisibility,
+    /// Experimental: whoever plays a wild can name a second color along with the first, and the
+    /// next card can match either. The player picks it with
+    /// [`Player::choose_second_color`](crate::player::Player::choose_second_color), and it lasts
+    /// until the wild is covered.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub two_color_wilds: bool,
+}
+
+/// What happens to a player made to draw past a hand size, for [`Rules::mercy`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Mercy {
+    /// Draws that would take a hand past this many cards aren't taken.
+    Cap(u8),
+    /// A player left holding more than this many cards is out of the round, and their hand
+    /// goes under the top of the discard pile. The last player still in wins.
+    Eliminate(u8),
+}
+
+/// How much a [`GameView`](crate::game::GameView) says about the size of the draw and discard
+/// piles, for [`Rules::pile_sizes`]. Some tables count how thick the deck is as something to
+/// guess at, like the cards in it.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PileVisibility {
+    #[default]
+    Exact,
+    /// Rounded down to a multiple of [`PILE_BUCKET`], as near as anyone could judge by eye.
+    Bucketed,
+    Hidden,
+}
+
+/// How many cards each bucket of [`PileVisibility::Bucketed`] spans.
+pub const PILE_BUCKET: usize = 10;
+
+/// The size of a pile, as far as the rules let a view say.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PileSize {
+    Exact(usize),
+    /// At least this many, and fewer than [`PILE_BUCKET`] more.
+    AtLeast(usize),
+}
+
+impl PileVisibility {
+    /// What a view shows of a pile of `cards`.
+    pub fn show(self, cards: usize) -> Option<PileSize> {
+        match self {
+            PileVisibility::Exact => Some(PileSize::Exact(cards)),
+            PileVisibility::Bucketed => Some(PileSize::AtLeast(cards / PILE_BUCKET * PILE_BUCKET)),
+            PileVisibility::Hidden => None,
+        }
+    }
+}
+
+impl Display for PileSize {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PileSize::Exact(cards) => write!(f, "{cards}"),
+            PileSize::AtLeast(cards) => write!(f, "{cards}-{}", cards + PILE_BUCKET - 1),
+        }
+    }
+}
+
+/// How likely an UNO Attack launcher is to fire each number of cards, for
+/// [`Rules::launcher`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Launcher {
+    /// The weight of firing each number of cards, from none up. A launcher with no weight
+    /// anywhere never fires.
+    pub weights: [u8; 8],
+}
+
+impl Launcher {
+    /// Usually nothing or a card or two, and now and then a handful.
+    pub fn standard() -> Launcher {
+        Launcher { weights: [40, 25, 14, 9, 6, 3, 2, 1] }
+    }
+
+    /// How many cards one press fires.
+    pub fn fire<R: Rng + ?Sized>(&self, rng: &mut R) -> u8 {
+        let total = self.weights.iter().map(|&weight| u32::from(weight)).sum::<u32>();
+
+        if total == 0 {
+            return 0;
+        }
+
+        let mut roll = rng.gen_range(0..total);
+
+        for (count, &weight) in self.weights.iter().enumerate() {
+            match roll.checked_sub(u32::from(weight)) {
+                Some(rest) => roll = rest,
+                None => return count as u8,
+            }
+        }
+
+        unreachable!("the roll is below the total weight")
+    }
+}
+
+impl Rules {
+    /// Mattel's published rules: no stacking, one card drawn and playable at once, and a
+    /// Reverse skipping the other player when there are only two.
+    pub fn official() -> Rules {
+        Rules {
+            stacking: false,
+            play_drawn: true,
+            draw_until_playable: false,
+            two_player_reverse_skips: true,
+            first_card_effects: true,
+            hand_size: 7,
+            max_turns: None,
+            points: PointValues::standard(),
+            open_wilds: false,
+            skip_everyone_cards: 0,
+            trade_hands_cards: 0,
+            discard_all_cards: 0,
+            launcher: None,
+            mercy: None,
+            play_to_last: false,
+            zero_swap: false,
+            pile_sizes: PileVisibility::Exact,
+            two_color_wilds: false,
+        }
+    }
+
+    /// The common house rules, where draw cards stack and drawing ends the turn.
+    pub fn classic_stacking() -> Rules {
+        Rules {
+            stacking: true,
+            play_drawn: false,
+            draw_until_playable: false,
+            two_player_reverse_skips: false,
+            first_card_effects: false,
+            hand_size: 7,
+            max_turns: None,
+            points: PointValues::standard(),
+            open_wilds: false,
+            skip_everyone_cards: 0,
+            trade_hands_cards: 0,
+            discard_all_cards: 0,
+            launcher: None,
+            mercy: None,
+            play_to_last: false,
+            zero_swap: false,
+            pile_sizes: PileVisibility::Exact,
+            two_color_wilds: false,
+        }
+    }
+
+    /// Long, swingy games: draw cards stack and players draw until they can play.
+    pub fn party() -> Rules {
+        Rules {
+            stacking: true,
+            play_drawn: true,
+            draw_until_playable: true,
+            two_player_reverse_skips: false,
+            first_card_effects: false,
+            hand_size: 7,
+            max_turns: None,
+            points: PointValues::standard(),
+            open_wilds: false,
+            skip_everyone_cards: 0,
+            trade_hands_cards: 0,
+            discard_all_cards: 0,
+            launcher: None,
+            mercy: None,
+            play_to_last: false,
+            zero_swap: false,
+            pile_sizes: PileVisibility::Exact,
+            two_color_wilds: false,
+        }
+    }
+
+    /// A preset by name: `official`, `classic-stacking`, `party`, `tournament` or `attack`.
+    pub fn preset(name: &str) -> Option<Rules> {
+        match name {
+            "attack" => Some(Rules::attack()),
+            "official" => Some(Rules::official()),
+            "classic-stacking" => Some(Rules::classic_stacking()),
+            "party" => Some(Rules::party()),
+            "tournament" => Some(Rules::tournament()),
+            _ => None,
+        }
+    }
+
+    /// The official rules with a cap on how long a game can run, so a schedule of matches always
+    /// finishes.
+    pub fn tournament() -> Rules {
+        Rules { max_turns: Some(500), ..Rules::official() }
+    }
+
+    /// UNO Attack: the official rules with draws from a launcher, and a Trade Hands and two
+    /// Discard All of each color in the deck.
+    pub fn attack() -> Rules {
+        Rules {
+            trade_hands_cards: 1,
+            discard_all_cards: 2,
+            launcher: Some(Launcher::standard()),
+            ..Rules::official()
+        }
+    }
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules::classic_stacking()
+    }
+}
+
+/// Why a move isn't allowed, so a frontend can say more than that it isn't.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IllegalBecause {
+    NotYourTurn,
+    /// The player doesn't hold the card, or there's no card where they said.
+    CardNotInHand,
+    /// A draw is pending, which can only be taken or, where the rules allow it, stacked on.
+    MustRespondToDraw,
+    /// The card matches neither the color nor the rank of the top card.
+    WrongColor,
+    /// A wild card was played without a color chosen for it.
+    NoColorChosen,
+    /// The player has just drawn a card they can play, and may play only that one or keep it.
+    OnlyDrawnCardPlayable,
+}
+
+impl Display for IllegalBecause {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            IllegalBecause::NotYourTurn => write!(f, "it isn't that player's turn"),
+            IllegalBecause::CardNotInHand => write!(f, "that card isn't in the player's hand"),
+            IllegalBecause::MustRespondToDraw => write!(f, "there's a draw to take or stack on first"),
+            IllegalBecause::WrongColor => write!(f, "that card matches neither the color nor the rank on the pile"),
+            IllegalBecause::NoColorChosen => write!(f, "a wild card needs a color chosen for it"),
+            IllegalBecause::OnlyDrawnCardPlayable => write!(f, "only the card just drawn can be played"),
+        }
+    }
+}
+
+impl core::error::Error for IllegalBecause {}
+
+/// [`can_play`], with the reason when the answer is no.
+pub fn check_play(card: &Card, top: &Card, to_draw: u8, rules: &Rules) -> Result<(), IllegalBecause> {
+    match can_play(card, top, to_draw, rules) {
+        true => Ok(()),
+        false => Err(refusal(top, to_draw)),
+    }
+}
+
+/// Why a card that isn't playable on `top` was turned down.
+pub(crate) fn refusal(top: &Card, to_draw: u8) -> IllegalBecause {
+    match pending_on(top, to_draw) {
+        true => IllegalBecause::MustRespondToDraw,
+        false => IllegalBecause::WrongColor,
+    }
+}
+
+/// Whether `card` can go on `top` with `to_draw` cards pending. A pending draw can only be
+/// passed on by stacking the same card, where the rules allow it.
+pub fn can_play(card: &Card, top: &Card, to_draw: u8, rules: &Rules) -> bool {
+    if pending_on(top, to_draw) {
+        rules.stacking && card == top
+    } else {
+        open_wild(top, rules) || card.can_play_on(top)
+    }
+}
+
+/// [`can_play`] after a wild that was declared as two colors under [`Rules::two_color_wilds`],
+/// with `second` the one named along with the color on `top`: the card can match either.
+pub fn can_play_either(card: &Card, top: &Card, second: Option<CardColor>, to_draw: u8, rules: &Rules) -> bool {
+    can_play(card, top, to_draw, rules) || second_top(top, second).is_some_and(|top| can_play(card, &top, to_draw, rules))
+}
+
+/// A bit for every kind in `hand` that [`can_play`] allows, indexed by [`PackedCard::kind`].
+pub fn playable_mask(hand: &Hand, top: &Card, to_draw: u8, rules: &Rules) -> u64 {
+    if open_wild(top, rules) && !pending_on(top, to_draw) {
+        return hand.counts().fold(0, |mask, (card, _)| mask | 1 << card.kind());
+    }
+
+    if !pending_on(top, to_draw) {
+        return hand.playable_mask(top);
+    }
+
+    match rules.stacking {
+        true => hand.playable_mask(top) & (1 << PackedCard::from(*top).kind()),
+        false => 0,
+    }
+}
+
+/// Replaces what's in `playable` with every card in `hand` that can be played, copies included.
+pub fn fill_playable(playable: &mut Vec<Card>, hand: &Hand, top: &Card, to_draw: u8, rules: &Rules) {
+    playable.clear();
+    playable.extend(hand.masked(playable_mask(hand, top, to_draw, rules)));
+}
+
+/// [`fill_playable`], with `second` the color named along with the wild on `top` as for
+/// [`can_play_either`].
+pub fn fill_playable_either(playable: &mut Vec<Card>, hand: &Hand, top: &Card, second: Option<CardColor>, to_draw: u8, rules: &Rules) {
+    let either = second_top(top, second).map_or(0, |top| playable_mask(hand, &top, to_draw, rules));
+    playable.clear();
+    playable.extend(hand.masked(playable_mask(hand, top, to_draw, rules) | either));
+}
+
+/// Whether a player with `playable` has to take the pending draw instead of choosing a move.
+pub fn must_draw(playable: &[Card], top: &Card, to_draw: u8) -> bool {
+    to_draw > 0 && !playable.contains(top)
+}
+
+/// Why a player takes cards from the deck, which is what decides how many they take and whether
+/// they can play after.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Draw {
+    /// Their own draw, for having nothing to play or choosing not to: one card, or under
+    /// [`Rules::draw_until_playable`] as many as it takes to find a playable one. Under
+    /// [`Rules::play_drawn`] they can play it then; otherwise, or if it can't go, their turn ends.
+    Single,
+    /// What the draw cards played on them add up to. Taking it is their whole turn.
+    Penalty(u8),
+}
+
+impl Draw {
+    /// The draw a player takes with `to_draw` cards pending against them.
+    pub fn facing(to_draw: u8) -> Draw {
+        match to_draw {
+            0 => Draw::Single,
+            count => Draw::Penalty(count),
+        }
+    }
+
+    /// How many cards it is, or the fewest a single draw gives when it goes on to a playable card.
+    pub fn cards(self) -> u8 {
+        match self {
+            Draw::Single => 1,
+            Draw::Penalty(count) => count,
+        }
+    }
+}
+
+/// The effect `card` has at a table of `seats` players.
+pub fn effect_of(card: &Card, seats: usize, rules: &Rules) -> Option<Effect> {
+    if rules.zero_swap && matches!(card, Card::Numeric { value: CardValue::Zero, .. }) {
+        return Some(Effect::SwapHands);
+    }
+
+    Effect::of(card).map(|effect| match effect {
+        Effect::Reverse if rules.two_player_reverse_skips && seats == 2 => Effect::Skip,
+        effect => effect,
+    })
+}
+
+/// The seat after `current` going in `direction`.
+pub fn next_seat(current: usize, seats: usize, direction: Direction) -> usize {
+    match direction {
+        Direction::Clockwise => (current + 1) % seats,
+        Direction::CounterClockwise => (current + seats - 1) % seats,
+    }
+}
+
+/// Whose turn it is, which way play is going and what the next player has to draw.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TurnOrder {
+    pub current: usize,
+    pub seats: usize,
+    pub direction: Direction,
+    pub to_draw: u8,
+    /// The seats out of the round, which play passes over.
+    pub out: Vec<usize>,
+}
+
+impl TurnOrder {
+    /// Who plays after the current player.
+    pub fn next(&self) -> usize {
+        self.after(self.current, self.direction)
+    }
+
+    /// Who plays after the current player, then after them, and so on, as things stand. A
+    /// pending draw doesn't change it: taking the draw is the next player's turn.
+    pub fn upcoming(&self) -> impl Iterator<Item = usize> + '_ {
+        core::iter::successors(Some(self.next()), |seat| Some(self.after(*seat, self.direction)))
+    }
+
+    /// The first seat still in the round after `seat` going in `direction`, or `seat` itself if
+    /// nobody else is.
+    fn after(&self, seat: usize, direction: Direction) -> usize {
+        let mut next = next_seat(seat, self.seats, direction);
+
+        while self.out.contains(&next) && next != seat {
+            next = next_seat(next, self.seats, direction);
+        }
+
+        next
+    }
+}
+
+/// Carries out `effect` on `order`, returning the seat it skipped if it's a skip. A draw is only
+/// added to what's pending; it's taken when the next player's turn comes. Skipping everyone
+/// stands in the seat before the current player's, so the next turn comes back to them.
+pub fn apply_effect(effect: Effect, order: &mut TurnOrder) -> Option<usize> {
+    match effect {
+        Effect::Reverse => {
+            order.direction = reversed(order.direction);
+            None
+        }
+        Effect::Skip => {
+            order.current = order.next();
+            Some(order.current)
+        }
+        Effect::Draw(count) => {
+            order.to_draw = order.to_draw.saturating_add(count);
+            None
+        }
+        Effect::SkipEveryone => {
+            order.current = order.after(order.current, reversed(order.direction));
+            None
+        }
+        // These change hands, not whose turn it is, so the game carries them out itself.
+        Effect::TradeHands | Effect::SwapHands | Effect::DiscardAll(_) => None,
+    }
+}
+
+pub(crate) fn reversed(direction: Direction) -> Direction {
+    match direction {
+        Direction::Clockwise => Direction::CounterClockwise,
+        Direction::CounterClockwise => Direction::Clockwise,
+    }
+}
+
+/// Whether `top` is a wild without a color that the rules let anything cover.
+fn open_wild(top: &Card, rules: &Rules) -> bool {
+    rules.open_wilds && matches!(top, Card::Wild { color: None } | Card::DrawFour { color: None })
+}
+
+/// The wild on `top` as though it had been given `second` instead, for the cards that can go
+/// on it as that color. `None` if `top` isn't a wild or there's no second color.
+fn second_top(top: &Card, second: Option<CardColor>) -> Option<Card> {
+    let mut top = *top;
+    top.with_color(second?)?;
+    Some(top)
+}
+
+/// Whether a draw is pending on `top`, rather than left over from an effect a hook changed.
+fn pending_on(top: &Card, to_draw: u8) -> bool {
+    to_draw > 0 && matches!(top, Card::DrawTwo { .. } | Card::DrawFour { .. })
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::card::{CardColor, CardValue};
+
+    const RED_FIVE: Card = Card::Numeric { color: CardColor::Red, value: CardValue::Five };
+    const RED_TWO: Card = Card::DrawTwo { color: CardColor::Red };
+    const BLUE_TWO: Card = Card::DrawTwo { color: CardColor::Blue };
+    const DRAW_FOUR: Card = Card::DrawFour { color: Some(CardColor::Green) };
+
+    fn hand(cards: &[Card]) -> Hand {
+        cards.iter().copied().collect()
+    }
+
+    fn order(current: usize, seats: usize, direction: Direction) -> TurnOrder {
+        TurnOrder { current, seats, direction, to_draw: 0, out: vec![] }
+    }
+
+    #[test]
+    fn numbers_match_on_color_or_value() {
+        let rules = Rules::official();
+        let blue_five = Card::Numeric { color: CardColor::Blue, value: CardValue::Five };
+        let red_nine = Card::Numeric { color: CardColor::Red, value: CardValue::Nine };
+        let blue_nine = Card::Numeric { color: CardColor::Blue, value: CardValue::Nine };
+
+        assert!(can_play(&blue_five, &RED_FIVE, 0, &rules));
+        assert!(can_play(&red_nine, &RED_FIVE, 0, &rules));
+        assert!(!can_play(&blue_nine, &RED_FIVE, 0, &rules));
+    }
+
+    #[test]
+    fn wilds_go_on_anything() {
+        let rules = Rules::official();
+        let wild = Card::Wild { color: None };
+
+        for top in [RED_FIVE, RED_TWO, DRAW_FOUR, Card::Skip { color: CardColor::Yellow }] {
+            assert!(can_play(&wild, &top, 0, &rules));
+            assert!(can_play(&DRAW_FOUR, &top, 0, &rules));
+        }
+    }
+
+    #[test]
+    fn uncolored_wilds_only_take_anything_when_open() {
+        let strict = Rules::official();
+        let open = Rules { open_wilds: true, ..Rules::official() };
+        let wild = Card::Wild { color: None };
+        let cards = hand(&[RED_FIVE, BLUE_TWO]);
+
+        assert!(!can_play(&RED_FIVE, &wild, 0, &strict));
+        assert_eq!(playable_mask(&cards, &wild, 0, &strict), 0);
+        assert!(can_play(&RED_FIVE, &wild, 0, &open));
+        assert_eq!(playable_mask(&cards, &wild, 0, &open).count_ones(), 2);
+
+        // A chosen color still has to be matched.
+        assert!(!can_play(&BLUE_TWO, &Card::Wild { color: Some(CardColor::Red) }, 0, &open));
+        assert!(!can_play(&BLUE_TWO, &Card::DrawFour { color: None }, 4, &open));
+    }
+
+    #[test]
+    fn a_wild_named_as_two_colors_takes_either() {
+        let rules = Rules { two_color_wilds: true, ..Rules::official() };
+        let wild = Card::Wild { color: Some(CardColor::Red) };
+        let green_one = Card::Numeric { color: CardColor::Green, value: CardValue::One };
+        let cards = hand(&[RED_FIVE, BLUE_TWO, green_one]);
+
+        assert!(can_play_either(&BLUE_TWO, &wild, Some(CardColor::Blue), 0, &rules));
+        assert!(can_play_either(&RED_FIVE, &wild, Some(CardColor::Blue), 0, &rules));
+        assert!(!can_play_either(&green_one, &wild, Some(CardColor::Blue), 0, &rules));
+        assert!(!can_play_either(&BLUE_TWO, &wild, None, 0, &rules));
+
+        let mut playable = vec![];
+        fill_playable_either(&mut playable, &cards, &wild, Some(CardColor::Blue), 0, &rules);
+        assert_eq!(playable, [RED_FIVE, BLUE_TWO]);
+
+        // Only a wild has a color to be named along with; a pending draw still has to be met.
+        assert!(!can_play_either(&BLUE_TWO, &RED_FIVE, Some(CardColor::Blue), 0, &rules));
+        assert!(!can_play_either(&BLUE_TWO, &DRAW_FOUR, Some(CardColor::Blue), 4, &rules));
+    }
+
+    #[test]
+    fn zeros_only_swap_when_turned_on() {
+        let zero = Card::Numeric { color: CardColor::Red, value: CardValue::Zero };
+
+        assert_eq!(effect_of(&zero, 4, &Rules::official()), None);
+        assert_eq!(effect_of(&zero, 4, &Rules { zero_swap: true, ..Rules::official() }), Some(Effect::SwapHands));
+        assert_eq!(effect_of(&RED_FIVE, 4, &Rules { zero_swap: true, ..Rules::official() }), None);
+    }
+
+    #[test]
+    fn refusals_say_what_was_wrong() {
+        let rules = Rules::official();
+        let blue_nine = Card::Numeric { color: CardColor::Blue, value: CardValue::Nine };
+
+        assert_eq!(check_play(&blue_nine, &RED_FIVE, 0, &rules), Err(IllegalBecause::WrongColor));
+        assert_eq!(check_play(&RED_FIVE, &RED_TWO, 2, &rules), Err(IllegalBecause::MustRespondToDraw));
+        assert_eq!(check_play(&RED_TWO, &RED_TWO, 2, &Rules::classic_stacking()), Ok(()));
+    }
+
+    #[test]
+    fn action_cards_match_on_kind_or_color() {
+        let rules = Rules::official();
+        let red_skip = Card::Skip { color: CardColor::Red };
+        let blue_skip = Card::Skip { color: CardColor::Blue };
+
+        assert!(can_play(&blue_skip, &red_skip, 0, &rules));
+        assert!(can_play(&RED_FIVE, &red_skip, 0, &rules));
+        assert!(can_play(&BLUE_TWO, &RED_TWO, 0, &rules));
+        assert!(!can_play(&blue_skip, &RED_FIVE, 0, &rules));
+    }
+
+    #[test]
+    fn pending_draws_only_stack_the_same_card() {
+        let stacking = Rules::classic_stacking();
+
+        assert!(can_play(&RED_TWO, &RED_TWO, 2, &stacking));
+        assert!(!can_play(&BLUE_TWO, &RED_TWO, 2, &stacking));
+        assert!(!can_play(&RED_FIVE, &RED_TWO, 2, &stacking));
+        assert!(!can_play(&DRAW_FOUR, &RED_TWO, 2, &stacking));
+        assert!(can_play(&Card::DrawFour { color: None }, &DRAW_FOUR, 4, &stacking));
+        assert!(!can_play(&RED_TWO, &DRAW_FOUR, 4, &stacking));
+    }
+
+    #[test]
+    fn pending_draws_cant_be_answered_without_stacking() {
+        let official = Rules::official();
+        let cards = hand(&[RED_TWO, DRAW_FOUR, RED_FIVE]);
+
+        assert!(!can_play(&RED_TWO, &RED_TWO, 2, &official));
+        assert_eq!(playable_mask(&cards, &RED_TWO, 2, &official), 0);
+        assert!(must_draw(&[], &RED_TWO, 2));
+    }
+
+    #[test]
+    fn a_draw_pending_on_another_card_leaves_normal_play() {
+        // A hook can turn a Skip into a draw, which leaves a draw pending on a card that can't be
+        // stacked on.
+        let rules = Rules::official();
+        let red_skip = Card::Skip { color: CardColor::Red };
+
+        assert!(can_play(&RED_FIVE, &red_skip, 2, &rules));
+        assert_ne!(playable_mask(&hand(&[RED_FIVE]), &red_skip, 2, &rules), 0);
+    }
+
+    #[test]
+    fn fill_playable_lists_every_copy_that_can_play() {
+        let rules = Rules::classic_stacking();
+        let blue_nine = Card::Numeric { color: CardColor::Blue, value: CardValue::Nine };
+        let cards = hand(&[RED_TWO, RED_TWO, BLUE_TWO, blue_nine, DRAW_FOUR]);
+        let mut playable = vec![RED_FIVE];
+
+        fill_playable(&mut playable, &cards, &RED_FIVE, 0, &rules);
+        assert_eq!(playable.len(), 3);
+        assert!(playable.iter().all(|card| can_play(card, &RED_FIVE, 0, &rules)));
+        assert!(!playable.contains(&blue_nine));
+        assert!(!playable.contains(&BLUE_TWO));
+
+        fill_playable(&mut playable, &cards, &RED_TWO, 2, &rules);
+        assert_eq!(playable, vec![RED_TWO, RED_TWO]);
+        assert!(!must_draw(&playable, &RED_TWO, 2));
+    }
+
+    #[test]
+    fn must_draw_only_with_something_pending() {
+        assert!(!must_draw(&[], &RED_TWO, 0));
+        assert!(must_draw(&[RED_FIVE], &RED_TWO, 2));
+        assert!(!must_draw(&[RED_TWO], &RED_TWO, 2));
+    }
+
+    #[test]
+    fn only_a_pending_draw_is_a_penalty() {
+        assert_eq!(Draw::facing(0), Draw::Single);
+        assert_eq!(Draw::facing(4), Draw::Penalty(4));
+        assert_eq!(Draw::facing(0).cards(), 1);
+    }
+
+    #[test]
+    fn effects_of_each_card() {
+        let rules = Rules::classic_stacking();
+
+        assert_eq!(effect_of(&RED_FIVE, 4, &rules), None);
+        assert_eq!(effect_of(&Card::Wild { color: None }, 4, &rules), None);
+        assert_eq!(effect_of(&Card::Skip { color: CardColor::Red }, 4, &rules), Some(Effect::Skip));
+        assert_eq!(effect_of(&Card::Reverse { color: CardColor::Red }, 4, &rules), Some(Effect::Reverse));
+        assert_eq!(effect_of(&RED_TWO, 4, &rules), Some(Effect::Draw(2)));
+        assert_eq!(effect_of(&DRAW_FOUR, 4, &rules), Some(Effect::Draw(4)));
+    }
+
+    #[test]
+    fn reverse_skips_with_two_players_when_the_rules_say_so() {
+        let reverse = Card::Reverse { color: CardColor::Green };
+
+        assert_eq!(effect_of(&reverse, 2, &Rules::official()), Some(Effect::Skip));
+        assert_eq!(effect_of(&reverse, 3, &Rules::official()), Some(Effect::Reverse));
+        assert_eq!(effect_of(&reverse, 2, &Rules::classic_stacking()), Some(Effect::Reverse));
+    }
+
+    #[test]
+    fn next_seat_wraps_both_ways() {
+        assert_eq!(next_seat(0, 4, Direction::Clockwise), 1);
+        assert_eq!(next_seat(3, 4, Direction::Clockwise), 0);
+        assert_eq!(next_seat(0, 4, Direction::CounterClockwise), 3);
+        assert_eq!(next_seat(2, 4, Direction::CounterClockwise), 1);
+        assert_eq!(next_seat(1, 2, Direction::Clockwise), 0);
+        assert_eq!(next_seat(0, 1, Direction::CounterClockwise), 0);
+    }
+
+    #[test]
+    fn skips_pass_over_the_next_seat() {
+        let mut clockwise = order(3, 4, Direction::Clockwise);
+        assert_eq!(apply_effect(Effect::Skip, &mut clockwise), Some(0));
+        assert_eq!(clockwise.next(), 1);
+
+        let mut counter = order(0, 4, Direction::CounterClockwise);
+        assert_eq!(apply_effect(Effect::Skip, &mut counter), Some(3));
+        assert_eq!(counter.next(), 2);
+    }
+
+    #[test]
+    fn seats_out_of_the_round_are_passed_over() {
+        let mut skip = TurnOrder { out: vec![0, 2], ..order(3, 5, Direction::Clockwise) };
+        assert_eq!(skip.next(), 4);
+        assert_eq!(apply_effect(Effect::Skip, &mut skip), Some(4));
+        assert_eq!(skip.next(), 1);
+
+        let mut everyone = TurnOrder { out: vec![0], ..order(1, 3, Direction::Clockwise) };
+        assert_eq!(apply_effect(Effect::SkipEveryone, &mut everyone), None);
+        assert_eq!(everyone.current, 2);
+        assert_eq!(everyone.next(), 1);
+    }
+
+    #[test]
+    fn upcoming_turns_follow_the_skips_and_reverses_already_made() {
+        let mut order = order(0, 4, Direction::Clockwise);
+        assert_eq!(order.upcoming().take(5).collect::<Vec<usize>>(), [1, 2, 3, 0, 1]);
+
+        apply_effect(Effect::Skip, &mut order);
+        apply_effect(Effect::Reverse, &mut order);
+        assert_eq!(order.upcoming().take(4).collect::<Vec<usize>>(), [0, 3, 2, 1]);
+
+        // Whoever faces the draw still takes their turn to take it.
+        apply_effect(Effect::Draw(2), &mut order);
+        order.out = vec![3];
+        assert_eq!(order.upcoming().take(4).collect::<Vec<usize>>(), [0, 2, 1, 0]);
+
+        let alone = TurnOrder { out: vec![0, 1, 3], ..order };
+        assert_eq!(alone.upcoming().take(2).collect::<Vec<usize>>(), [2, 2]);
+    }
+
+    #[test]
+    fn tables_of_any_size_go_round() {
+        let order = order(63, 70, Direction::Clockwise);
+        assert_eq!(order.next(), 64);
+
+        let passed = TurnOrder { out: vec![64, 65], ..order.clone() };
+        assert_eq!(passed.upcoming().take(2).collect::<Vec<usize>>(), [66, 67]);
+
+        let back = TurnOrder { current: 69, out: vec![0], ..order };
+        assert_eq!(back.next(), 1);
+    }
+
+    #[test]
+    fn skipping_everyone_comes_back_around() {
+        for seats in 2..=6 {
+            for direction in [Direction::Clockwise, Direction::CounterClockwise] {
+                let mut order = order(1, seats, direction);
+
+                assert_eq!(apply_effect(Effect::SkipEveryone, &mut order), None);
+                assert_eq!(order.next(), 1);
+            }
+        }
+
+        assert_eq!(effect_of(&Card::WildSkipEveryone { color: None }, 4, &Rules::official()), Some(Effect::SkipEveryone));
+    }
+
+    #[test]
+    fn reverses_turn_play_around() {
+        let mut order = order(1, 4, Direction::Clockwise);
+
+        assert_eq!(apply_effect(Effect::Reverse, &mut order), None);
+        assert_eq!(order.direction, Direction::CounterClockwise);
+        assert_eq!(order.next(), 0);
+
+        apply_effect(Effect::Reverse, &mut order);
+        assert_eq!(order.direction, Direction::Clockwise);
+        assert_eq!(order.current, 1);
+    }
+
+    #[test]
+    fn draws_add_up_without_overflowing() {
+        let mut order = order(0, 3, Direction::Clockwise);
+
+        assert_eq!(apply_effect(Effect::Draw(2), &mut order), None);
+        apply_effect(Effect::Draw(4), &mut order);
+        assert_eq!(order.to_draw, 6);
+        assert_eq!(order.current, 0);
+
+        apply_effect(Effect::Draw(u8::MAX), &mut order);
+        assert_eq!(order.to_draw, u8::MAX);
+    }
+
+    #[test]
+    fn presets_by_name() {
+        assert_eq!(Rules::preset("official"), Some(Rules::official()));
+        assert_eq!(Rules::preset("classic-stacking"), Some(Rules::default()));
+        assert_eq!(Rules::preset("party"), Some(Rules::party()));
+        assert_eq!(Rules::preset("attack"), Some(Rules::attack()));
+        assert_eq!(Rules::preset("tournament"), Some(Rules { max_turns: Some(500), ..Rules::official() }));
+        assert_eq!(Rules::preset("speed"), None);
+    }
+
+    #[test]
+    fn launchers_fire_by_weight() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+
+        assert_eq!(Launcher { weights: [0; 8] }.fire(&mut rng), 0);
+        assert_eq!(Launcher { weights: [0, 0, 0, 5, 0, 0, 0, 0] }.fire(&mut rng), 3);
+    }
+
+    #[test]
+    fn pile_sizes_show_as_much_as_the_rules_allow() {
+        assert_eq!(PileVisibility::Exact.show(37), Some(PileSize::Exact(37)));
+        assert_eq!(PileVisibility::Bucketed.show(37), Some(PileSize::AtLeast(30)));
+        assert_eq!(PileVisibility::Bucketed.show(9), Some(PileSize::AtLeast(0)));
+        assert_eq!(PileVisibility::Hidden.show(37), None);
+    }
+}
@@ -0,0 +1,231 @@
+//! Best-of-N series: the same players in the same seats for game after game, until one of them
+//! has won more than half of the games the series could run to.
+//!
+//! A [`Series`] holds everything it needs to carry on, so it can be saved between games and
+//! picked up again, and the games themselves can be played here or anywhere else that takes a
+//! seed, such as a server, and [recorded](Series::record) as they finish.
+//!
+//! Each game starts afresh unless the series' [`Carryover`] says otherwise: who leads the next
+//! game can follow from how the last one went, and so can a draw or a wild it ended on.
+
+use alloc::{string::String, vec::Vec};
+
+use crate::card::Card;
+use crate::game::{GameState, Opening};
+use crate::player::Player;
+use crate::rules::Rules;
+use crate::sim::{GameRecord, SimulationReport};
+use crate::summary::GameOutcome;
+
+/// A best-of-N series between a fixed roster. Game `n` is seeded with `seed + n`, like a
+/// [`Simulation`](crate::sim::Simulation)'s, so any game in it can be replayed.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Series {
+    pub best_of: u32,
+    pub seed: u64,
+    pub rules: Rules,
+    /// Games still going after this many turns are aborted, and count for no one.
+    pub max_turns: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub carryover: Carryover,
+    played: SimulationReport,
+    /// How the next game starts, worked out from the last as it was recorded.
+    #[cfg_attr(feature = "serde", serde(default))]
+    next_first_player: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    next_opening: Opening,
+}
+
+/// What each game of a [`Series`] carries into the next. By default, nothing does.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Carryover {
+    pub lead: Lead,
+    /// A draw left pending when a game ends waits for the same player at the start of the next,
+    /// and they lead it, whatever `lead` says.
+    pub penalties: bool,
+    /// A game that ends on a wild starts the next on it, in the color it was played as.
+    pub colors: bool,
+}
+
+/// Who leads each game of a series after the first, which seat 1 always leads. An aborted game
+/// leaves the lead where it was.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Lead {
+    /// Seat 1, after seat 0 deals, every game.
+    #[default]
+    Fixed,
+    /// The deal passes to the next seat each game.
+    Rotate,
+    /// The winner of the last game.
+    Winner,
+    /// Whoever came last in the last game deals, so the seat after them leads.
+    LoserDeals,
+}
+
+/// How a seat stands in a series, from [`Series::standings`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Standing {
+    pub seat: usize,
+    pub name: String,
+    pub wins: u32,
+    /// What the seat scored over the games it won.
+    pub points: u32,
+}
+
+impl Series {
+    /// A series between `players`, in seating order.
+    pub fn new(players: Vec<String>, best_of: u32, seed: u64, rules: Rules) -> Series {
+        Series {
+            best_of,
+            seed,
+            rules,
+            max_turns: 2000,
+            carryover: Carryover::default(),
+            played: SimulationReport::new(players),
+            next_first_player: None,
+            next_opening: Opening::default(),
+        }
+    }
+
+    /// The roster, in seating order.
+    pub fn players(&self) -> &[String] {
+        &self.played.players
+    }
+
+    /// Every game played so far, in order.
+    pub fn games(&self) -> &[GameRecord] {
+        &self.played.games
+    }
+
+    /// How many wins settle the series.
+    pub fn wins_needed(&self) -> u32 {
+        self.best_of / 2 + 1
+    }
+
+    /// Whether someone has won enough games, or every game has been played.
+    pub fn is_over(&self) -> bool {
+        self.games().len() as u32 >= self.best_of || self.standings().first().is_some_and(|leader| leader.wins >= self.wins_needed())
+    }
+
+    /// What the next game should be seeded with, or `None` once the series is over.
+    pub fn next_seed(&self) -> Option<u64> {
+        (!self.is_over()).then(|| self.seed.wrapping_add(self.games().len() as u64))
+    }
+
+    /// Who should take the first turn of the next game, for
+    /// [`GameState::set_first_player`]. `None` leaves it to the game.
+    pub fn next_first_player(&self) -> Option<usize> {
+        self.next_first_player
+    }
+
+    /// What the next game should start with from the last, for [`GameState::set_opening`].
+    pub fn next_opening(&self) -> Opening {
+        self.next_opening
+    }
+
+    /// Plays the next game with `players` taking the roster's seats in order, without pausing
+    /// between turns. Returns `None`, playing nothing, once the series is over.
+    pub fn play_next(&mut self, players: &mut [&mut dyn Player]) -> Option<&GameRecord> {
+        let seed = self.next_seed()?;
+        let mut state = GameState::with_seed(players.iter_mut().map(|player| &mut **player as &mut dyn Player).collect(), seed);
+        state.set_rules(self.rules);
+        state.set_opening(self.next_opening);
+
+        if let Some(first) = self.next_first_player {
+            state.set_first_player(first);
+        }
+
+        let mut turns = 0;
+
+        let outcome = loop {
+            if let Some(outcome) = state.play_turn() {
+                break outcome;
+            }
+
+            turns += 1;
+
+            if turns >= self.max_turns {
+                break state.abort("turn limit reached");
+            }
+        };
+
+        drop(state);
+        self.record(&outcome)
+    }
+
+    /// Records the outcome of the next game, played elsewhere from [`next_seed`](Series::next_seed)
+    /// with the roster in the same seats, and set up by [`next_first_player`](Series::next_first_player)
+    /// and [`next_opening`](Series::next_opening). Returns `None`, recording nothing, once the
+    /// series is over.
+    pub fn record(&mut self, outcome: &GameOutcome) -> Option<&GameRecord> {
+        let seed = self.next_seed()?;
+        self.played.push(seed, outcome);
+        self.carry(outcome);
+        self.played.games.last()
+    }
+
+    /// Works out how the game after `outcome` starts.
+    fn carry(&mut self, outcome: &GameOutcome) {
+        let seats = self.players().len().max(1);
+        let won = match outcome {
+            GameOutcome::Won(summary) => Some(summary),
+            GameOutcome::Aborted { .. } => None,
+        };
+
+        self.next_first_player = match (self.carryover.lead, won) {
+            (Lead::Fixed, _) => None,
+            (Lead::Rotate, _) => Some((self.games().len() + 1) % seats),
+            (Lead::Winner, Some(summary)) => summary.ranking.first().copied(),
+            (Lead::LoserDeals, Some(summary)) => summary.ranking.last().map(|loser| (loser + 1) % seats),
+            (_, None) => self.next_first_player,
+        };
+        self.next_opening = Opening::default();
+
+        let Some(summary) = won else {
+            return;
+        };
+
+        if let Some((seat, draw)) = summary.left_to_draw.filter(|_| self.carryover.penalties) {
+            self.next_first_player = Some(seat);
+            self.next_opening.draw = draw;
+        }
+
+        if self.carryover.colors {
+            self.next_opening.wild = summary.last_card.filter(Card::is_wild);
+        }
+    }
+
+    /// Every seat, most wins first, with points won breaking ties and then the seating order.
+    pub fn standings(&self) -> Vec<Standing> {
+        let mut standings = self.players()
+            .iter()
+            .enumerate()
+            .map(|(seat, name)| {
+                let won = self.games().iter().filter(|game| game.winner_seat == Some(seat));
+                let (wins, points) = won.fold((0, 0), |(wins, points), game| (wins + 1, points + game.points));
+                Standing { seat, name: name.clone(), wins, points }
+            })
+            .collect::<Vec<Standing>>();
+
+        standings.sort_by(|a, b| b.wins.cmp(&a.wins).then(b.points.cmp(&a.points)).then(a.seat.cmp(&b.seat)));
+        standings
+    }
+
+    /// The seat that won the series, once it's over. A series that ran out of games with two
+    /// seats level on wins and points has no winner.
+    pub fn winner(&self) -> Option<usize> {
+        if !self.is_over() {
+            return None;
+        }
+
+        match self.standings().as_slice() {
+            [leader, second, ..] if (leader.wins, leader.points) == (second.wins, second.points) => None,
+            [leader, ..] if leader.wins > 0 => Some(leader.seat),
+            _ => None,
+        }
+    }
+}
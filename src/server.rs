@@ -0,0 +1,10 @@
+//! Pieces for hosting games for remote players.
+
+pub mod client;
+pub mod manager;
+pub mod matchmaking;
+pub mod protocol;
+pub mod session;
+pub mod store;
+#[cfg(feature = "wire")]
+pub mod wire;
@@ -0,0 +1,241 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::card::{Card, CardColor};
+use crate::game::{GameEvent, GameView, StackedDraw};
+use crate::hook::Effect;
+use crate::rules::{self, IllegalBecause, Rules, TurnOrder};
+use crate::server::protocol::{Action, ClientMessage, ServerMessage};
+use crate::server::session::SessionToken;
+
+/// Why a [`ClientState`] wouldn't send an action.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ClientError {
+    /// The client hasn't been given a seat yet.
+    NotSeated,
+    /// The last action hasn't been answered yet.
+    Waiting,
+    Illegal(IllegalBecause),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::NotSeated => write!(f, "the client doesn't have a seat yet"),
+            ClientError::Waiting => write!(f, "the server hasn't answered the last action yet"),
+            ClientError::Illegal(reason) => write!(f, "that move isn't allowed: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+/// How the server's answer to an action compared with what the client assumed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Reconciled {
+    /// The action went through as predicted.
+    Confirmed,
+    /// The server did something else, and its view has replaced the prediction.
+    Corrected,
+    /// The server turned the action down, and the table is back as it was before it.
+    Rejected,
+}
+
+/// The table as one remote player sees it, for frontends on slow connections.
+///
+/// An action is shown straight away as the client expects it to turn out, before the server
+/// has answered. The next update whose fingerprint shows the game has moved on replaces the
+/// prediction, and a rejection undoes it.
+#[derive(Debug, Clone, Default)]
+pub struct ClientState {
+    seat: Option<usize>,
+    token: Option<SessionToken>,
+    /// Taken from the game's setup when the client sees it, and otherwise the defaults.
+    rules: Rules,
+    confirmed: Option<GameView>,
+    pending: Option<Pending>,
+}
+
+#[derive(Debug, Clone)]
+struct Pending {
+    /// The card the action plays, or `None` for a draw.
+    card: Option<Card>,
+    /// The fingerprint of the view the action was made from. Until an update has another
+    /// one, the server hasn't carried the action out.
+    based_on: u64,
+    predicted: GameView,
+}
+
+impl ClientState {
+    pub fn new() -> ClientState {
+        ClientState::default()
+    }
+
+    /// For a client that joins after the game has been set up, and so never sees its rules.
+    pub fn set_rules(&mut self, rules: Rules) {
+        self.rules = rules;
+    }
+
+    /// The message taking back this client's seat after a dropped connection.
+    pub fn rejoin(&mut self, token: SessionToken) -> ClientMessage {
+        self.token = Some(token.clone());
+        ClientMessage::Rejoin { token }
+    }
+
+    pub fn seat(&self) -> Option<usize> {
+        self.seat
+    }
+
+    /// The table as the client should show it, with any action still in flight already made.
+    pub fn view(&self) -> Option<&GameView> {
+        self.pending.as_ref().map(|pending| &pending.predicted).or(self.confirmed.as_ref())
+    }
+
+    /// The table as the server last described it.
+    pub fn confirmed(&self) -> Option<&GameView> {
+        self.confirmed.as_ref()
+    }
+
+    /// Whether an action has been sent that the server hasn't answered.
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Every action [`act`](ClientState::act) would send now, for disabling the rest. Empty
+    /// while it isn't this seat's turn or an action is still waiting on the server.
+    pub fn legal_moves(&self) -> Vec<Action> {
+        let (Some(seat), Some(view), None) = (self.seat, &self.confirmed, &self.pending) else {
+            return vec![];
+        };
+
+        match view.top_card {
+            Some(top) if view.current_player == seat => Action::legal(&view.hand, &top, view.second_color, view.to_draw, &self.rules),
+            _ => vec![],
+        }
+    }
+
+    /// Checks `action` against the table, shows its result and returns the message to send
+    /// for it.
+    pub fn act(&mut self, action: Action) -> Result<ClientMessage, ClientError> {
+        let (Some(seat), Some(token), Some(view)) = (self.seat, &self.token, &self.confirmed) else {
+            return Err(ClientError::NotSeated);
+        };
+
+        if self.pending.is_some() {
+            return Err(ClientError::Waiting);
+        }
+
+        if view.current_player != seat {
+            return Err(ClientError::Illegal(IllegalBecause::NotYourTurn));
+        }
+
+        let mut predicted = view.clone();
+        let card = match action {
+            Action::Draw => {
+                predicted.players[seat].cards += usize::from(rules::Draw::facing(predicted.to_draw).cards());
+                predicted.to_draw = 0;
+                predicted.draw_chain.clear();
+                None
+            }
+            Action::Play { index, color } => Some(self.predict_play(&mut predicted, seat, index, color)?),
+        };
+
+        self.pending = Some(Pending { card, based_on: view.fingerprint, predicted });
+
+        Ok(ClientMessage::Act { token: token.clone(), action })
+    }
+
+    /// Takes in a message from the server, saying what became of the action in flight if this
+    /// is the answer to it.
+    pub fn receive(&mut self, message: &ServerMessage) -> Option<Reconciled> {
+        match message {
+            ServerMessage::Joined { seat, token } => {
+                self.seat = Some(*seat);
+                self.token = Some(token.clone());
+                None
+            }
+            ServerMessage::Rejoined { seat } => {
+                self.seat = Some(*seat);
+                None
+            }
+            ServerMessage::Rejected { .. } => self.pending.take().map(|_| Reconciled::Rejected),
+            // Whatever comes of them arrives as updates.
+            ServerMessage::Warned { .. } | ServerMessage::Penalized { .. } | ServerMessage::OutOfTime { .. } | ServerMessage::GameExpired { .. } => None,
+            ServerMessage::Update { event, view } => {
+                if let GameEvent::Setup { rules, .. } = event {
+                    self.rules = *rules;
+                }
+
+                self.confirmed = Some(GameView::clone(view));
+
+                // Emotes and the like arrive without the game moving on.
+                if self.pending.as_ref().is_some_and(|pending| pending.based_on == view.fingerprint) {
+                    return None;
+                }
+
+                let pending = self.pending.take()?;
+                let me = view.player_id()?;
+
+                let expected = match (event, pending.card) {
+                    (GameEvent::Played { player, card }, Some(played)) => *player == me && *card == played,
+                    (GameEvent::Drew { player, .. }, None) => *player == me,
+                    _ => false,
+                };
+
+                Some(if expected { Reconciled::Confirmed } else { Reconciled::Corrected })
+            }
+        }
+    }
+
+    /// Moves the card at `index` onto the pile in `view`, as the engine would.
+    fn predict_play(&self, view: &mut GameView, seat: usize, index: usize, color: Option<CardColor>) -> Result<Card, ClientError> {
+        let mut card = *view.hand.get(index).ok_or(ClientError::Illegal(IllegalBecause::CardNotInHand))?;
+        // Nobody's turn has started before the first card is turned up.
+        let top = view.top_card.ok_or(ClientError::Illegal(IllegalBecause::NotYourTurn))?;
+
+        if !rules::can_play_either(&card, &top, view.second_color, view.to_draw, &self.rules) {
+            return Err(ClientError::Illegal(rules::refusal(&top, view.to_draw)));
+        }
+
+        if card.is_wild() {
+            card.with_color(color.ok_or(ClientError::Illegal(IllegalBecause::NoColorChosen))?);
+        }
+
+        view.hand.remove(index);
+        view.players[seat].cards -= 1;
+        view.top_card = Some(card);
+        view.active_color = card.color();
+        // Whatever second color the server asks for comes with the next update.
+        view.second_color = None;
+
+        let mut order = TurnOrder {
+            current: seat,
+            seats: view.players.len(),
+            direction: view.direction,
+            to_draw: view.to_draw,
+            out: view.players.iter().enumerate().filter(|(_, player)| player.out).map(|(seat, _)| seat).collect(),
+        };
+
+        match rules::effect_of(&card, order.seats, &self.rules) {
+            Some(Effect::DiscardAll(color)) => {
+                let before = view.hand.len();
+                view.hand.retain(|held| held.is_wild() || held.color() != Some(color));
+                view.players[seat].cards -= before - view.hand.len();
+            }
+            // Where the other hand goes isn't known until the server says.
+            Some(Effect::TradeHands | Effect::SwapHands) | None => {}
+            Some(effect) => {
+                rules::apply_effect(effect, &mut order);
+
+                if let Effect::Draw(count) = effect {
+                    view.draw_chain.push(StackedDraw { seat, card, count });
+                }
+            }
+        }
+
+        view.current_player = order.current;
+        view.direction = order.direction;
+        view.to_draw = order.to_draw;
+
+        Ok(card)
+    }
+}
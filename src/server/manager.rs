@@ -0,0 +1,1099 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt::{self, Display, Formatter};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+use std::time::{Duration, Instant};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::card::Card;
+use crate::game::{DealError, GameEvent, GameState, GameView, LeftHand, Spectator, Turn, TurnResult};
+#[cfg(feature = "bots")]
+use crate::player::{AIDifficulty, Ai};
+use crate::player::Player;
+use crate::rules::{IllegalBecause, Rules};
+use crate::server::protocol::{Action, ServerMessage};
+use crate::server::store::{GameStore, StoreError};
+use crate::snapshot::Snapshot;
+use crate::summary::GameOutcome;
+
+/// Identifies a game held by a [`GameManager`].
+pub type GameId = u64;
+
+/// How a [`GameManager`] sets up a new game.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GameConfig {
+    pub rules: Rules,
+    /// Picked by the manager when `None`.
+    pub seed: Option<u64>,
+    pub shuffle_seats: bool,
+    /// See [`GameState::set_first_player`].
+    pub first_player: Option<usize>,
+    /// See [`GameState::set_hand_sizes`]. By the order the players are given in, with a [`Fill`]'s
+    /// bots after them.
+    pub hand_sizes: Vec<u8>,
+    pub misbehavior: MisbehaviorPolicy,
+    /// Gives every seat a clock when set.
+    pub time_bank: Option<TimeBank>,
+    /// Bots for the seats that no one has taken.
+    #[cfg(feature = "bots")]
+    pub fill: Option<Fill>,
+}
+
+/// Fills a game up with bots, so it can start with fewer people than seats.
+#[cfg(feature = "bots")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Fill {
+    /// How many seats the game has, people and bots together.
+    pub seats: usize,
+    pub difficulty: AIDifficulty,
+    /// How long every bot in the game, including those substituted for a seat, waits before
+    /// each move it makes, so people can follow them. Bots that wait only move when
+    /// [`GameManager::play_bots`] is called.
+    pub think: Duration,
+}
+
+/// A chess clock for every seat. A seat's clock runs while the game waits on it, and once it
+/// runs out the seat has `penalty` taken.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TimeBank {
+    /// What each seat starts with.
+    pub initial: Duration,
+    /// Added to a seat's clock for each move it makes in time.
+    pub increment: Duration,
+    pub penalty: Penalty,
+}
+
+/// What a [`GameManager`] does about seats that time out or keep trying illegal moves.
+///
+/// The default only ever warns.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct MisbehaviorPolicy {
+    /// How many times a seat is warned before `penalty` is taken.
+    pub warnings: u8,
+    /// `None` to keep warning however often it happens.
+    pub penalty: Option<Penalty>,
+}
+
+/// Something a seat did that counts against it under a [`MisbehaviorPolicy`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Misbehavior {
+    /// The host reported, with [`GameManager::time_out`], that the seat took too long.
+    TimedOut,
+    /// The seat submitted a move that was refused.
+    Illegal(IllegalBecause),
+}
+
+/// What happens to a seat that's misbehaved once too often.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Penalty {
+    /// A bot takes over the seat for the rest of the game, keeping the seat's name and hand.
+    #[cfg(feature = "bots")]
+    Substitute(AIDifficulty),
+    /// The seat leaves the game, as [`GameState::remove_player`] has it.
+    Remove(LeftHand),
+}
+
+/// A game [reaped](GameManager::reap) for sitting idle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expired {
+    pub game: GameId,
+    /// How long it had gone without a move.
+    pub idle: Duration,
+    pub outcome: GameOutcome,
+    /// What its seats still have to be told, as [`take_updates`](GameManager::take_updates)
+    /// would have given it, the news that it expired included.
+    pub updates: Vec<(usize, ServerMessage)>,
+}
+
+/// Why a [`GameManager`] turned a request down.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ManagerError {
+    UnknownGame,
+    UnknownSeat,
+    /// A game needs at least two players.
+    TooFewPlayers,
+    /// The deck can't deal to the table as it was set up, such as one with more players than
+    /// there are cards for.
+    CantDeal(DealError),
+    Illegal(IllegalBecause),
+    Finished,
+    /// The seat's clock ran out before it acted, and its time bank's penalty has been taken.
+    OutOfTime,
+}
+
+impl Display for ManagerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ManagerError::UnknownGame => write!(f, "there's no game with that id"),
+            ManagerError::UnknownSeat => write!(f, "the game has no such seat"),
+            ManagerError::TooFewPlayers => write!(f, "a game needs at least two players"),
+            ManagerError::CantDeal(error) => write!(f, "the game can't be dealt: {error}"),
+            ManagerError::Illegal(reason) => write!(f, "that move isn't allowed: {reason}"),
+            ManagerError::Finished => write!(f, "the game is over"),
+            ManagerError::OutOfTime => write!(f, "the seat ran out of time"),
+        }
+    }
+}
+
+impl std::error::Error for ManagerError {}
+
+/// Many games at once, each between remote players who act by seat, for embedding in whatever
+/// server or bot is carrying the messages.
+///
+/// Games are kept as [`Snapshot`]s, so the manager owns them outright and they can be saved at
+/// any point. Each action is played from the last snapshot, turn by turn, until a seat is
+/// needed that hasn't acted yet. What every seat should be told is queued for
+/// [`take_updates`](GameManager::take_updates). Given a [`GameStore`], it also keeps every
+/// game there as it goes, whenever it's [flushed](GameManager::flush).
+pub struct GameManager {
+    games: BTreeMap<GameId, ManagedGame>,
+    next_id: GameId,
+    seeds: StdRng,
+    store: Option<Box<dyn GameStore>>,
+    idle_limit: Option<Duration>,
+}
+
+struct ManagedGame {
+    names: Vec<String>,
+    snapshot: Snapshot,
+    /// Actions submitted for the turn in progress, per seat.
+    queued: Vec<VecDeque<Action>>,
+    /// The seat the game is waiting on, until it's over.
+    waiting: Option<usize>,
+    /// What the waiting seat can do on the turn it was handed, which can differ from what the
+    /// snapshot's hand allows once it's drawn.
+    offered: Vec<Action>,
+    /// How many updates each seat has been sent from the turn in progress, so playing it again
+    /// once the waiting seat acts doesn't send them twice.
+    sent: Vec<usize>,
+    /// The last view each seat was sent during the turn in progress.
+    views: Vec<Option<Arc<GameView>>>,
+    /// The spectator's view of the snapshot, taken from the turn that made it, so that views
+    /// can be served without restoring the game.
+    public: Arc<GameView>,
+    /// Each seat's view of the snapshot, made from [`public`](ManagedGame::public) the first
+    /// time it's asked for, and shared by every ask after until the next turn is kept.
+    seat_views: Vec<OnceLock<Arc<GameView>>>,
+    outcome: Option<GameOutcome>,
+    updates: Vec<(usize, ServerMessage)>,
+    policy: MisbehaviorPolicy,
+    /// How many times each seat has misbehaved.
+    strikes: Vec<u8>,
+    /// The seats bots have taken over.
+    #[cfg(feature = "bots")]
+    bots: Vec<Option<AIDifficulty>>,
+    /// Where the bots' choices come from. Each turn seeds them afresh, so playing a turn again
+    /// makes the same ones.
+    #[cfg(feature = "bots")]
+    bot_seed: u64,
+    /// How long bots wait before moving.
+    #[cfg(feature = "bots")]
+    think: Duration,
+    /// A bot that has waited long enough and may move, until its turn is over.
+    #[cfg(feature = "bots")]
+    ready: Option<usize>,
+    /// When the game started waiting on the seat it's waiting on.
+    since: Instant,
+    clock: Option<Clock>,
+    /// The events of every turn kept since the game was last flushed to the store, or `None`
+    /// without one.
+    log: Option<Vec<GameEvent>>,
+    /// Whether the snapshot has changed since the game was last flushed.
+    changed: bool,
+    /// How long the game had gone without a move when it was aborted for it, until it's been
+    /// stored and forgotten.
+    expired: Option<Duration>,
+}
+
+/// Every seat's time left under a [`TimeBank`]. Bots, who play on nobody's clock, aren't
+/// charged for theirs.
+struct Clock {
+    bank: TimeBank,
+    left: Vec<Duration>,
+    /// When the waiting seat's clock was last charged.
+    since: Instant,
+}
+
+impl Clock {
+    /// Takes the time since the clock was last charged off `seat`'s. Returns whether that ran
+    /// it out.
+    fn charge(&mut self, seat: usize) -> bool {
+        let now = Instant::now();
+        self.left[seat] = self.left[seat].saturating_sub(now - self.since);
+        self.since = now;
+        self.left[seat].is_zero()
+    }
+
+    /// Starts timing from now without charging anyone, once a bot has moved.
+    fn restart(&mut self) {
+        self.since = Instant::now();
+    }
+}
+
+impl GameManager {
+    pub fn new() -> GameManager {
+        GameManager::with_seeds(StdRng::from_entropy())
+    }
+
+    /// A manager whose games' seeds all come from `seed`, when they aren't given their own.
+    pub fn with_seed(seed: u64) -> GameManager {
+        GameManager::with_seeds(StdRng::seed_from_u64(seed))
+    }
+
+    fn with_seeds(seeds: StdRng) -> GameManager {
+        GameManager { games: BTreeMap::new(), next_id: 0, seeds, store: None, idle_limit: None }
+    }
+
+    /// Deals a new game between `players`, named in seating order, and plays up to the first
+    /// seat's decision. With a [`Fill`], bots named the way the computer players are take the
+    /// seats after theirs.
+    pub fn create_game(&mut self, config: GameConfig, players: Vec<String>) -> Result<GameId, ManagerError> {
+        #[cfg(feature = "bots")]
+        let (people, players) = (players.len(), self.fill(players, config.fill));
+
+        if players.len() < 2 {
+            return Err(ManagerError::TooFewPlayers);
+        }
+
+        let mut seats = players.iter().map(|name| Seat::empty(name)).collect::<Vec<Seat>>();
+        let mut state = GameState::with_seed(seats.iter_mut().map(|seat| seat as &mut dyn Player).collect(), config.seed.unwrap_or_else(|| self.seeds.gen()));
+        state.set_rules(config.rules);
+
+        if config.shuffle_seats {
+            state.shuffle_seats();
+        }
+
+        if let Some(first) = config.first_player {
+            state.set_first_player(first);
+        }
+
+        // Checks the deal as a whole, handicap or not.
+        state.set_hand_sizes(config.hand_sizes).map_err(ManagerError::CantDeal)?;
+
+        let snapshot = state.snapshot();
+        let public = Arc::new(state.view(None));
+        #[cfg(feature = "bots")]
+        let bots = state.seating().iter().map(|from| config.fill.filter(|_| *from >= people).map(|fill| fill.difficulty)).collect();
+        drop(state);
+
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let count = players.len();
+        let mut game = ManagedGame {
+            // The snapshot has them in their seats, which may have been shuffled.
+            names: snapshot.players.clone(),
+            snapshot,
+            queued: vec![VecDeque::new(); count],
+            waiting: None,
+            offered: vec![],
+            sent: vec![0; count],
+            views: vec![None; count],
+            public,
+            seat_views: vec![OnceLock::new(); count],
+            outcome: None,
+            updates: vec![],
+            policy: config.misbehavior,
+            strikes: vec![0; count],
+            #[cfg(feature = "bots")]
+            bots,
+            #[cfg(feature = "bots")]
+            bot_seed: self.seeds.gen(),
+            #[cfg(feature = "bots")]
+            think: config.fill.map_or(Duration::ZERO, |fill| fill.think),
+            #[cfg(feature = "bots")]
+            ready: None,
+            since: Instant::now(),
+            clock: config.time_bank.map(|bank| Clock { bank, left: vec![bank.initial; count], since: Instant::now() }),
+            log: self.store.as_ref().map(|_| vec![]),
+            changed: true,
+            expired: None,
+        };
+
+        game.advance().expect("a new game has no actions to refuse");
+        self.games.insert(id, game);
+
+        Ok(id)
+    }
+
+    /// Makes `seat`'s move, then plays on until another seat has to decide.
+    pub fn submit_action(&mut self, game: GameId, seat: usize, action: Action) -> Result<(), ManagerError> {
+        let game = self.games.get_mut(&game).ok_or(ManagerError::UnknownGame)?;
+
+        if game.outcome.is_some() {
+            return Err(ManagerError::Finished);
+        }
+
+        if seat >= game.names.len() {
+            return Err(ManagerError::UnknownSeat);
+        }
+
+        if game.waiting != Some(seat) || game.is_bot(seat) {
+            return Err(ManagerError::Illegal(IllegalBecause::NotYourTurn));
+        }
+
+        if game.out_of_time().is_some() {
+            game.flag(seat);
+            return Err(ManagerError::OutOfTime);
+        }
+
+        game.queued[seat].push_back(action);
+
+        game.advance().map_err(|reason| {
+            game.queued[seat].pop_back();
+
+            if game.misbehaved(seat, Misbehavior::Illegal(reason)) {
+                game.advance().expect("only the penalized seat had actions queued");
+            }
+
+            ManagerError::Illegal(reason)
+        })?;
+
+        if let Some(clock) = &mut game.clock {
+            clock.left[seat] += clock.bank.increment;
+        }
+
+        Ok(())
+    }
+
+    /// Takes the time bank's penalty from every seat whose clock has run out while its game
+    /// waited on it, returning the games and seats. A seat that never acts isn't noticed
+    /// otherwise, so hosts with time banks should call this regularly.
+    pub fn check_clocks(&mut self) -> Vec<(GameId, usize)> {
+        let mut flagged = vec![];
+
+        for (id, game) in &mut self.games {
+            if let Some(seat) = game.out_of_time() {
+                game.flag(seat);
+                flagged.push((*id, seat));
+            }
+        }
+
+        flagged
+    }
+
+    /// How long a game can wait on a seat before [`reap`](GameManager::reap) gives up on it, or
+    /// `None`, the default, to wait however long it takes.
+    pub fn set_idle_limit(&mut self, limit: Option<Duration>) {
+        self.idle_limit = limit;
+    }
+
+    /// Aborts every game that has waited on a seat for longer than the
+    /// [idle limit](GameManager::set_idle_limit), telling its seats with
+    /// [`ServerMessage::GameExpired`], then stores and forgets it, so abandoned tables don't
+    /// pile up on a server that runs for a long time. Returns what became of them. Hosts with
+    /// an idle limit should call this regularly.
+    ///
+    /// Games are only forgotten once the store has them. If it can't be written, they're kept,
+    /// already aborted, and the next reap tries again.
+    pub fn reap(&mut self) -> Result<Vec<Expired>, StoreError> {
+        if let Some(limit) = self.idle_limit {
+            for game in self.games.values_mut().filter(|game| game.outcome.is_none()) {
+                let idle = game.since.elapsed();
+
+                if idle >= limit {
+                    game.expire(idle);
+                }
+            }
+        }
+
+        self.flush()?;
+
+        let expired = self.games.iter().filter(|(_, game)| game.expired.is_some()).map(|(id, _)| *id).collect::<Vec<GameId>>();
+
+        Ok(expired
+            .into_iter()
+            .filter_map(|id| {
+                let game = self.games.remove(&id)?;
+                Some(Expired { game: id, idle: game.expired?, outcome: game.outcome?, updates: game.updates })
+            })
+            .collect())
+    }
+
+    /// Lets every bot that has been thinking long enough make its move, returning the games
+    /// that moved on. When bots are given time to think, hosts should call this regularly.
+    #[cfg(feature = "bots")]
+    pub fn play_bots(&mut self) -> Vec<GameId> {
+        let mut moved = vec![];
+
+        for (id, game) in &mut self.games {
+            let Some(seat) = game.waiting.filter(|seat| game.is_bot(*seat)) else {
+                continue;
+            };
+
+            if game.since.elapsed() >= game.think {
+                game.ready = Some(seat);
+                game.advance().expect("bots only make legal moves");
+                moved.push(*id);
+            }
+        }
+
+        moved
+    }
+
+    /// Names the bots for the seats after `players`.
+    #[cfg(feature = "bots")]
+    fn fill(&mut self, mut players: Vec<String>, fill: Option<Fill>) -> Vec<String> {
+        let Some(fill) = fill else {
+            return players;
+        };
+
+        while players.len() < fill.seats {
+            // Bots get names at random, so a few tries finds one that isn't taken.
+            let name = (0..8)
+                .map(|_| Ai::new(&mut self.seeds, fill.difficulty).name().to_string())
+                .find(|name| !players.contains(name))
+                .unwrap_or_else(|| format!("Bot {}", players.len()));
+
+            players.push(name);
+        }
+
+        players
+    }
+
+    /// How long `seat` has left on its clock, or `None` if the game has no time bank.
+    pub fn time_left(&self, game: GameId, seat: usize) -> Result<Option<Duration>, ManagerError> {
+        let game = self.games.get(&game).ok_or(ManagerError::UnknownGame)?;
+
+        if seat >= game.names.len() {
+            return Err(ManagerError::UnknownSeat);
+        }
+
+        Ok(game.clock.as_ref().map(|clock| match game.waiting == Some(seat) && !game.is_bot(seat) {
+            true => clock.left[seat].saturating_sub(clock.since.elapsed()),
+            false => clock.left[seat],
+        }))
+    }
+
+    /// Counts it against `seat` that they took too long over the decision the game is waiting
+    /// on, for hosts that keep a clock. Unless that costs them their seat, they draw, so the
+    /// game can go on.
+    pub fn time_out(&mut self, game: GameId, seat: usize) -> Result<(), ManagerError> {
+        let game = self.games.get_mut(&game).ok_or(ManagerError::UnknownGame)?;
+
+        if game.outcome.is_some() {
+            return Err(ManagerError::Finished);
+        }
+
+        if seat >= game.names.len() {
+            return Err(ManagerError::UnknownSeat);
+        }
+
+        if game.waiting != Some(seat) {
+            return Err(ManagerError::Illegal(IllegalBecause::NotYourTurn));
+        }
+
+        if !game.misbehaved(seat, Misbehavior::TimedOut) {
+            game.queued[seat].push_back(Action::Draw);
+        }
+
+        game.advance().map_err(ManagerError::Illegal)
+    }
+
+    /// Takes `seat` out of the game for good, such as when its player quits, and plays on if
+    /// the game was waiting on them.
+    pub fn leave(&mut self, game: GameId, seat: usize, hand: LeftHand) -> Result<(), ManagerError> {
+        let game = self.games.get_mut(&game).ok_or(ManagerError::UnknownGame)?;
+
+        if game.outcome.is_some() {
+            return Err(ManagerError::Finished);
+        }
+
+        if seat >= game.names.len() {
+            return Err(ManagerError::UnknownSeat);
+        }
+
+        if game.snapshot.out.contains(&seat) {
+            return Ok(());
+        }
+
+        game.remove(seat, hand);
+        game.advance().map_err(ManagerError::Illegal)
+    }
+
+    /// Every action `seat` could submit now, for showing only the moves that would be taken.
+    /// Empty when it isn't their turn.
+    pub fn legal_moves(&self, game: GameId, seat: usize) -> Result<Vec<Action>, ManagerError> {
+        let game = self.games.get(&game).ok_or(ManagerError::UnknownGame)?;
+
+        if seat >= game.names.len() {
+            return Err(ManagerError::UnknownSeat);
+        }
+
+        match game.waiting == Some(seat) {
+            true => Ok(game.offered.clone()),
+            false => Ok(vec![]),
+        }
+    }
+
+    /// The table as `seat` may see it, or as a spectator would when `seat` is `None`. Views are
+    /// kept between turns, so a game polled over and over shares the same one until it moves on.
+    pub fn get_view(&self, game: GameId, seat: Option<usize>) -> Result<Arc<GameView>, ManagerError> {
+        let game = self.games.get(&game).ok_or(ManagerError::UnknownGame)?;
+
+        let Some(seat) = seat else {
+            return Ok(game.public.clone());
+        };
+
+        if seat >= game.names.len() {
+            return Err(ManagerError::UnknownSeat);
+        }
+
+        // Part way through a turn, the snapshot is from before it started.
+        if let Some(view) = &game.views[seat] {
+            return Ok(view.clone());
+        }
+
+        // Everything but the seat's own hand is the same for everyone.
+        let view = game.seat_views[seat].get_or_init(|| Arc::new(GameView {
+            seat: Some(seat),
+            hand: game.snapshot.hands[seat].clone(),
+            ..GameView::clone(&game.public)
+        }));
+
+        Ok(view.clone())
+    }
+
+    /// The seat the game is waiting on, or `None` once it's over.
+    pub fn waiting_for(&self, game: GameId) -> Result<Option<usize>, ManagerError> {
+        Ok(self.games.get(&game).ok_or(ManagerError::UnknownGame)?.waiting)
+    }
+
+    pub fn outcome(&self, game: GameId) -> Option<&GameOutcome> {
+        self.games.get(&game)?.outcome.as_ref()
+    }
+
+    /// The game as of its last complete turn, for saving.
+    pub fn snapshot(&self, game: GameId) -> Option<&Snapshot> {
+        Some(&self.games.get(&game)?.snapshot)
+    }
+
+    /// Everything that has happened in a game since this was last called, as the seat each
+    /// message is for should see it.
+    pub fn take_updates(&mut self, game: GameId) -> Vec<(usize, ServerMessage)> {
+        self.games.get_mut(&game).map(|game| std::mem::take(&mut game.updates)).unwrap_or_default()
+    }
+
+    /// Forgets a game, finished or not.
+    pub fn remove_game(&mut self, game: GameId) -> Option<GameOutcome> {
+        self.games.remove(&game)?.outcome
+    }
+
+    pub fn games(&self) -> impl Iterator<Item = GameId> + '_ {
+        self.games.keys().copied()
+    }
+
+    /// Keeps games in `store` from now on, starting with every game's snapshot as it stands at
+    /// the next [`flush`](GameManager::flush). Only events from turns played after this are
+    /// stored. Forgetting a game with [`remove_game`](GameManager::remove_game) leaves what's
+    /// stored of it alone.
+    pub fn set_store(&mut self, store: Box<dyn GameStore>) {
+        self.store = Some(store);
+
+        for game in self.games.values_mut() {
+            game.log = Some(vec![]);
+            game.changed = true;
+        }
+    }
+
+    pub fn store(&self) -> Option<&dyn GameStore> {
+        self.store.as_deref()
+    }
+
+    /// Writes every game that has changed since it was last flushed to the store: the events
+    /// of the turns it has played, then its snapshot. Only whole turns are stored, so a game
+    /// waiting on a seat is stored as of the turn before. Games that couldn't be written are
+    /// tried again at the next flush.
+    pub fn flush(&mut self) -> Result<(), StoreError> {
+        let Some(store) = &mut self.store else {
+            return Ok(());
+        };
+
+        for (id, game) in &mut self.games {
+            if let Some(log) = game.log.as_mut().filter(|log| !log.is_empty()) {
+                store.append_events(*id, log)?;
+                log.clear();
+            }
+
+            if game.changed {
+                store.save_snapshot(*id, &game.snapshot)?;
+                game.changed = false;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for GameManager {
+    fn default() -> Self {
+        GameManager::new()
+    }
+}
+
+impl ManagedGame {
+    /// Plays turns from the snapshot until the game needs a seat that has nothing queued, or
+    /// ends. Fails if a queued action turned out to be illegal, in which case nothing is kept
+    /// from the turn it was for.
+    fn advance(&mut self) -> Result<(), IllegalBecause> {
+        // The seat that was waited on is charged up to now, so whoever is waited on next starts
+        // from here.
+        let bot = self.waiting.is_some_and(|seat| self.is_bot(seat));
+
+        match (&mut self.clock, self.waiting) {
+            (Some(clock), Some(seat)) if !bot => {
+                clock.charge(seat);
+            }
+            (Some(clock), _) => clock.restart(),
+            _ => {}
+        }
+
+        let mut moved = false;
+
+        while self.outcome.is_none() {
+            let (outcome, kept, table) = self.attempt(|state| state.play_turn());
+
+            if let Some(Stop::Illegal(reason)) = table.stopped {
+                return Err(reason);
+            }
+
+            self.send(table.updates);
+
+            if let Some(Stop::Waiting(seat)) = table.stopped {
+                if moved || self.waiting != Some(seat) {
+                    self.since = Instant::now();
+                }
+
+                self.waiting = Some(seat);
+                self.offered = table.offered;
+                return Ok(());
+            }
+
+            // The turn went through without waiting on anyone, so it's kept.
+            #[cfg(feature = "bots")]
+            {
+                self.ready = None;
+            }
+
+            moved = true;
+            self.keep(kept, table.events);
+            self.queued = table.queued;
+            self.sent.iter_mut().for_each(|sent| *sent = 0);
+            self.views.iter_mut().for_each(|view| *view = None);
+            self.outcome = outcome;
+        }
+
+        self.waiting = None;
+        Ok(())
+    }
+
+    /// Restores the game from its snapshot, with each seat playing from what it has queued, and
+    /// does `play` with it. The game comes back as a snapshot and the view of it a spectator
+    /// would have.
+    fn attempt(&self, play: impl FnOnce(&mut GameState) -> Option<GameOutcome>) -> (Option<GameOutcome>, Kept, Table) {
+        let table = Mutex::new(Table {
+            queued: self.queued.clone(),
+            stopped: None,
+            offered: vec![],
+            updates: vec![],
+            events: vec![],
+        });
+
+        let mut seats = self.names.iter().enumerate().map(|(seat, name)| Seat::at(name, seat, &table)).collect::<Vec<Seat>>();
+
+        // A bot that has to think first waits for its turn like anyone else.
+        #[cfg(feature = "bots")]
+        for (index, (seat, difficulty)) in seats.iter_mut().zip(&self.bots).enumerate() {
+            let thought = self.think.is_zero() || self.ready == Some(index);
+            seat.bot = difficulty.filter(|_| thought).map(|difficulty| (difficulty, StdRng::seed_from_u64(self.bot_seed ^ u64::from(self.snapshot.turns))));
+        }
+
+        let mut logger = Logger { table: &table };
+        let mut state = GameState::from_snapshot(seats.iter_mut().map(|seat| seat as &mut dyn Player).collect(), &self.snapshot)
+            .expect("the manager's own snapshots restore");
+
+        if self.log.is_some() {
+            state.add_spectator(&mut logger);
+        }
+
+        let outcome = play(&mut state);
+        let kept = Kept { snapshot: state.snapshot(), public: state.view(None) };
+        drop(state);
+
+        (outcome, kept, table.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner()))
+    }
+
+    /// Counts a misbehavior against `seat`, warning them or, once they've had their warnings,
+    /// taking the policy's penalty. Returns whether it was taken, in which case the turn in
+    /// progress has to be played again.
+    fn misbehaved(&mut self, seat: usize, misbehavior: Misbehavior) -> bool {
+        self.strikes[seat] = self.strikes[seat].saturating_add(1);
+
+        let Some(penalty) = self.policy.penalty.filter(|_| self.strikes[seat] > self.policy.warnings) else {
+            let warnings_left = self.policy.penalty.map(|_| self.policy.warnings - self.strikes[seat]);
+            self.updates.push((seat, ServerMessage::Warned { misbehavior, warnings_left }));
+            return false;
+        };
+
+        self.penalize(seat, penalty);
+        true
+    }
+
+    #[cfg(feature = "bots")]
+    fn is_bot(&self, seat: usize) -> bool {
+        self.bots[seat].is_some()
+    }
+
+    #[cfg(not(feature = "bots"))]
+    fn is_bot(&self, _seat: usize) -> bool {
+        false
+    }
+
+    /// The seat the game is waiting on, if its clock has run out, unless a bot has it.
+    fn out_of_time(&mut self) -> Option<usize> {
+        let seat = self.waiting.filter(|seat| !self.is_bot(*seat))?;
+        self.clock.as_mut()?.charge(seat).then_some(seat)
+    }
+
+    /// Takes the time bank's penalty from `seat`, whose clock has run out, and plays on.
+    fn flag(&mut self, seat: usize) {
+        let Some(clock) = &self.clock else {
+            return;
+        };
+
+        let penalty = clock.bank.penalty;
+        self.updates.extend((0..self.names.len()).map(|other| (other, ServerMessage::OutOfTime { seat })));
+        self.penalize(seat, penalty);
+        self.advance().expect("only the flagged seat had actions queued");
+    }
+
+    fn penalize(&mut self, seat: usize, penalty: Penalty) {
+        self.updates.extend((0..self.names.len()).map(|other| (other, ServerMessage::Penalized { seat, penalty })));
+
+        match penalty {
+            #[cfg(feature = "bots")]
+            Penalty::Substitute(difficulty) => {
+                self.queued[seat].clear();
+                self.bots[seat] = Some(difficulty);
+            }
+            Penalty::Remove(hand) => self.remove(seat, hand),
+        }
+    }
+
+    /// Takes `seat` out of the game. The turn in progress starts again without them, so
+    /// everything about it that was sent still stands but is no longer counted.
+    fn remove(&mut self, seat: usize, hand: LeftHand) {
+        self.queued[seat].clear();
+        let (outcome, kept, table) = self.attempt(|state| state.remove_player(seat, hand));
+
+        self.sent.iter_mut().for_each(|sent| *sent = 0);
+        self.send(table.updates);
+
+        self.keep(kept, table.events);
+        self.sent.iter_mut().for_each(|sent| *sent = 0);
+        self.views.iter_mut().for_each(|view| *view = None);
+        self.outcome = outcome;
+    }
+
+    /// Aborts the game for having gone `idle` without a move, after telling every seat why. The
+    /// turn in progress is dropped, as the waiting seat never finished it.
+    fn expire(&mut self, idle: Duration) {
+        self.updates.extend((0..self.names.len()).map(|seat| (seat, ServerMessage::GameExpired { idle })));
+        let reason = format!("nobody moved for {idle:.0?}");
+        let (outcome, kept, table) = self.attempt(|state| Some(state.abort(reason)));
+
+        self.sent.iter_mut().for_each(|sent| *sent = 0);
+        self.send(table.updates);
+
+        self.keep(kept, table.events);
+        self.sent.iter_mut().for_each(|sent| *sent = 0);
+        self.views.iter_mut().for_each(|view| *view = None);
+        self.outcome = outcome;
+        self.waiting = None;
+        self.expired = Some(idle);
+    }
+
+    /// Moves the game on to what a turn left, with `events` the turn that got it there.
+    fn keep(&mut self, kept: Kept, mut events: Vec<GameEvent>) {
+        self.snapshot = kept.snapshot;
+        self.public = Arc::new(kept.public);
+        self.seat_views.iter_mut().for_each(|view| *view = OnceLock::new());
+        self.changed = true;
+
+        if let Some(log) = &mut self.log {
+            log.append(&mut events);
+        }
+    }
+
+    /// Queues whatever each seat hasn't already been sent from the turn in progress.
+    fn send(&mut self, updates: Vec<(usize, GameEvent, GameView)>) {
+        let mut seen = vec![0; self.names.len()];
+
+        for (seat, event, view) in updates {
+            seen[seat] += 1;
+
+            if seen[seat] <= self.sent[seat] {
+                continue;
+            }
+
+            self.sent[seat] += 1;
+            self.updates.push((seat, ServerMessage::Update { event, view: Box::new(view.clone()) }));
+            self.views[seat] = Some(Arc::new(view));
+        }
+    }
+}
+
+/// The game as an attempt at a turn left it.
+struct Kept {
+    snapshot: Snapshot,
+    public: GameView,
+}
+
+/// What one attempt at a turn shares between the seats.
+struct Table {
+    /// What's left of each seat's queued actions as the turn is played.
+    queued: Vec<VecDeque<Action>>,
+    stopped: Option<Stop>,
+    /// What the seat the attempt stopped to wait on could have done.
+    offered: Vec<Action>,
+    updates: Vec<(usize, GameEvent, GameView)>,
+    /// Everything that happened, as a spectator saw it, for the store.
+    events: Vec<GameEvent>,
+}
+
+/// Records the events of an attempt for the store, until it's abandoned.
+struct Logger<'t> {
+    table: &'t Mutex<Table>,
+}
+
+impl Spectator for Logger<'_> {
+    fn observe_event(&mut self, event: &GameEvent, _view: &GameView) {
+        let mut table = self.table.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if table.stopped.is_none() {
+            table.events.push(event.clone());
+        }
+    }
+}
+
+/// Why an attempt at a turn had to be abandoned.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Stop {
+    /// The seat has to decide before the turn can go on.
+    Waiting(usize),
+    Illegal(IllegalBecause),
+}
+
+/// Plays a seat's queued actions. Once one is missing or illegal, the attempt is abandoned:
+/// the seat draws so the engine can finish the turn, but nothing after that is kept.
+struct Seat<'t> {
+    name: &'t str,
+    seat: usize,
+    table: Option<&'t Mutex<Table>>,
+    /// Plays instead, once the seat has been handed to a bot.
+    #[cfg(feature = "bots")]
+    bot: Option<(AIDifficulty, StdRng)>,
+}
+
+impl<'t> Seat<'t> {
+    /// A seat that only lends its name, for restoring a game to look at it.
+    fn empty(name: &'t str) -> Seat<'t> {
+        Seat {
+            name,
+            seat: 0,
+            table: None,
+            #[cfg(feature = "bots")]
+            bot: None,
+        }
+    }
+
+    fn at(name: &'t str, seat: usize, table: &'t Mutex<Table>) -> Seat<'t> {
+        Seat {
+            name,
+            seat,
+            table: Some(table),
+            #[cfg(feature = "bots")]
+            bot: None,
+        }
+    }
+
+    fn table(&self) -> Option<MutexGuard<'t, Table>> {
+        self.table.map(|table| table.lock().unwrap_or_else(|poisoned| poisoned.into_inner()))
+    }
+}
+
+impl Player for Seat<'_> {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn execute_turn(&mut self, turn: &Turn) -> TurnResult {
+        let Some(mut table) = self.table() else {
+            return TurnResult::Drew;
+        };
+
+        if table.stopped.is_some() {
+            return TurnResult::Drew;
+        }
+
+        #[cfg(feature = "bots")]
+        if let Some((difficulty, rng)) = &mut self.bot {
+            return Ai::new(rng, *difficulty).execute_turn(turn);
+        }
+
+        let Some(action) = table.queued[self.seat].pop_front() else {
+            table.stopped = Some(Stop::Waiting(self.seat));
+            table.offered = Action::offered(turn);
+            return TurnResult::Drew;
+        };
+
+        action.check(turn).unwrap_or_else(|reason| {
+            table.stopped = Some(Stop::Illegal(reason));
+            TurnResult::Drew
+        })
+    }
+
+    fn observe_turn(&self, _other: &dyn Player, _card: &Card) {}
+
+    fn observe_turn_skip(&self, _observed_cards: Option<&[Card]>) {}
+
+    fn observe_event(&mut self, event: &GameEvent, view: &GameView) {
+        if let Some(mut table) = self.table() {
+            if table.stopped.is_none() {
+                table.updates.push((self.seat, event.clone(), view.clone()));
+            }
+        }
+    }
+
+    fn wants_events(&self) -> bool {
+        self.table.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::CardColor;
+
+    fn names(count: usize) -> Vec<String> {
+        (1..=count).map(|number| format!("Player {number}")).collect()
+    }
+
+    #[test]
+    fn a_table_the_deck_cant_deal_to_is_turned_down() {
+        let mut manager = GameManager::with_seed(1);
+
+        assert!(manager.create_game(GameConfig::default(), names(8)).is_ok());
+        assert!(matches!(
+            manager.create_game(GameConfig::default(), names(9)),
+            Err(ManagerError::CantDeal(DealError::TooManyCards { needed: 63, .. })),
+        ));
+    }
+
+    #[test]
+    fn a_handicap_the_deck_cant_deal_is_turned_down() {
+        let mut manager = GameManager::with_seed(1);
+        let config = |hand_sizes| GameConfig { hand_sizes, ..GameConfig::default() };
+
+        assert!(manager.create_game(config(vec![12, 3]), names(2)).is_ok());
+        assert!(matches!(
+            manager.create_game(config(vec![255, 7]), names(2)),
+            Err(ManagerError::CantDeal(DealError::TooManyCards { needed: 262, .. })),
+        ));
+        assert_eq!(
+            manager.create_game(config(vec![7]), names(2)),
+            Err(ManagerError::CantDeal(DealError::HandSizes { expected: 2, actual: 1 })),
+        );
+    }
+
+    #[test]
+    fn kept_views_match_the_game_they_come_from() {
+        let mut manager = GameManager::with_seed(3);
+        let game = manager.create_game(GameConfig::default(), names(3)).unwrap();
+
+        let restored = |manager: &GameManager, seat| {
+            let snapshot = manager.snapshot(game).unwrap();
+            let mut seats = snapshot.players.iter().map(|name| Seat::empty(name)).collect::<Vec<Seat>>();
+            let state = GameState::from_snapshot(seats.iter_mut().map(|seat| seat as &mut dyn Player).collect(), snapshot).unwrap();
+            state.view(seat)
+        };
+
+        while let Some(seat) = manager.waiting_for(game).unwrap() {
+            assert_eq!(*manager.get_view(game, None).unwrap(), restored(&manager, None));
+
+            let moves = manager.legal_moves(game, seat).unwrap();
+            let action = moves.iter().find(|action| matches!(action, Action::Play { .. })).or(moves.last()).cloned().unwrap();
+            manager.submit_action(game, seat, action).unwrap();
+        }
+
+        // Once it's over, no turn is in progress, and every seat sees the last one kept.
+        for seat in [None, Some(0), Some(1), Some(2)] {
+            let view = manager.get_view(game, seat).unwrap();
+            assert_eq!(*view, restored(&manager, seat));
+            // Asked again, it's the same view rather than another copy.
+            assert!(Arc::ptr_eq(&view, &manager.get_view(game, seat).unwrap()));
+        }
+    }
+
+    #[test]
+    fn the_moves_offered_for_a_drawn_card_are_taken() {
+        let mut manager = GameManager::with_seed(0);
+        let config = GameConfig { rules: Rules::official(), seed: Some(0), ..GameConfig::default() };
+        let game = manager.create_game(config, names(2)).unwrap();
+        let mut offers = 0;
+
+        // Everyone draws rather than play, until a few of the cards drawn could go on the pile.
+        while offers < 3 {
+            let seat = manager.waiting_for(game).unwrap().unwrap();
+            manager.submit_action(game, seat, Action::Draw).unwrap();
+
+            if manager.waiting_for(game).unwrap() != Some(seat) {
+                continue;
+            }
+
+            let moves = manager.legal_moves(game, seat).unwrap();
+            assert!(moves.len() <= CardColor::ALL.len() + 1, "only the drawn card is offered: {moves:?}");
+
+            // Any other card is turned down because only the drawn one may go, whether it matches or not.
+            let Action::Play { index: drawn, .. } = moves[0] else { panic!("the drawn card comes first") };
+            let held = Action::Play { index: usize::from(drawn == 0), color: Some(CardColor::Red) };
+            assert_eq!(manager.submit_action(game, seat, held), Err(ManagerError::Illegal(IllegalBecause::OnlyDrawnCardPlayable)));
+
+            manager.submit_action(game, seat, moves[0].clone()).unwrap();
+            offers += 1;
+        }
+    }
+
+    #[cfg(feature = "bots")]
+    #[test]
+    fn bots_filling_the_table_count_towards_the_deal() {
+        let fill = Fill { seats: 12, difficulty: AIDifficulty::Easy, think: Duration::ZERO };
+        let config = GameConfig { fill: Some(fill), ..GameConfig::default() };
+
+        assert!(matches!(GameManager::with_seed(1).create_game(config, names(2)), Err(ManagerError::CantDeal(_))));
+    }
+
+    #[cfg(feature = "bots")]
+    #[test]
+    fn a_bot_taking_over_a_seat_isnt_flagged_again() {
+        let config = GameConfig {
+            fill: Some(Fill { seats: 2, difficulty: AIDifficulty::Easy, think: Duration::from_secs(60) }),
+            time_bank: Some(TimeBank { initial: Duration::ZERO, increment: Duration::ZERO, penalty: Penalty::Substitute(AIDifficulty::Easy) }),
+            ..GameConfig::default()
+        };
+        let mut manager = GameManager::with_seed(1);
+        let game = manager.create_game(config, names(2)).unwrap();
+        let seat = manager.waiting_for(game).unwrap().unwrap();
+
+        assert_eq!(manager.check_clocks(), [(game, seat)]);
+        manager.take_updates(game);
+
+        // The bot is still thinking over the seat's move, on a clock that has run out.
+        assert_eq!(manager.waiting_for(game).unwrap(), Some(seat));
+        assert_eq!(manager.check_clocks(), []);
+        assert_eq!(manager.take_updates(game), []);
+    }
+}
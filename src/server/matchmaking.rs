@@ -0,0 +1,134 @@
+use std::time::{Duration, Instant};
+
+/// Someone waiting for a game.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ticket {
+    pub name: String,
+    /// How many players, this one included, they want at the table.
+    pub table_size: usize,
+    /// Only used when the matchmaker has a rating window. Unrated players can sit with anyone.
+    pub rating: Option<f64>,
+}
+
+/// Identifies a queued [`Ticket`], so it can be withdrawn.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TicketId(u64);
+
+/// Players grouped into a game.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match {
+    pub players: Vec<Ticket>,
+}
+
+struct Waiting {
+    id: TicketId,
+    ticket: Ticket,
+    since: Instant,
+}
+
+/// Groups waiting players into games and hands each game to a spawn callback.
+///
+/// Players only share a table with others who asked for the same size. With a rating window,
+/// everyone at a table must also be within the window of the player who has waited longest, and
+/// the window widens by `widen_per_minute` the longer that player waits, so nobody is stuck in the
+/// queue forever. The longest-waiting player is always matched first.
+pub struct Matchmaker<F: FnMut(Match)> {
+    queue: Vec<Waiting>,
+    rating_window: Option<f64>,
+    widen_per_minute: f64,
+    next_id: u64,
+    spawn: F,
+}
+
+impl<F: FnMut(Match)> Matchmaker<F> {
+    pub fn new(spawn: F) -> Matchmaker<F> {
+        Matchmaker {
+            queue: vec![],
+            rating_window: None,
+            widen_per_minute: 0.0,
+            next_id: 0,
+            spawn,
+        }
+    }
+
+    /// Only seats players together if their ratings are within `window` of each other.
+    pub fn with_rating_window(mut self, window: f64, widen_per_minute: f64) -> Matchmaker<F> {
+        self.rating_window = Some(window);
+        self.widen_per_minute = widen_per_minute;
+        self
+    }
+
+    /// Queues a player, spawning any game they complete.
+    pub fn enqueue(&mut self, ticket: Ticket) -> TicketId {
+        let id = TicketId(self.next_id);
+        self.next_id += 1;
+
+        self.queue.push(Waiting { id, ticket, since: Instant::now() });
+        self.poll();
+
+        id
+    }
+
+    /// Takes a player out of the queue. Returns false if they weren't waiting.
+    pub fn cancel(&mut self, id: TicketId) -> bool {
+        let before = self.queue.len();
+        self.queue.retain(|waiting| waiting.id != id);
+        self.queue.len() != before
+    }
+
+    pub fn waiting(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Spawns every game that can be formed. Call this periodically when using a rating window,
+    /// so widening windows get a chance to match players.
+    pub fn poll(&mut self) {
+        let mut anchor = 0;
+
+        while anchor < self.queue.len() {
+            match self.table_for(anchor) {
+                Some(seats) => {
+                    // Removing from the back keeps the earlier indices valid.
+                    let mut players = seats
+                        .into_iter()
+                        .rev()
+                        .map(|seat| self.queue.remove(seat).ticket)
+                        .collect::<Vec<Ticket>>();
+                    players.reverse();
+
+                    (self.spawn)(Match { players });
+                }
+                None => anchor += 1,
+            }
+        }
+    }
+
+    /// The queue positions of a full table for the player at `anchor`, in queue order.
+    fn table_for(&self, anchor: usize) -> Option<Vec<usize>> {
+        let first = &self.queue[anchor];
+        let size = first.ticket.table_size.max(1);
+
+        let window = self.rating_window.map(|window| {
+            window + self.widen_per_minute * elapsed_minutes(first.since.elapsed())
+        });
+
+        let seats = self.queue
+            .iter()
+            .enumerate()
+            .skip(anchor)
+            .filter(|(_, waiting)| waiting.ticket.table_size.max(1) == size)
+            .filter(|(_, waiting)| match (window, first.ticket.rating, waiting.ticket.rating) {
+                (Some(window), Some(a), Some(b)) => (a - b).abs() <= window,
+                _ => true,
+            })
+            .map(|(seat, _)| seat)
+            .take(size)
+            .collect::<Vec<usize>>();
+
+        (seats.len() == size).then_some(seats)
+    }
+}
+
+fn elapsed_minutes(elapsed: Duration) -> f64 {
+    elapsed.as_secs_f64() / 60.0
+}
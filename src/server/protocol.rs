@@ -0,0 +1,133 @@
+use core::time::Duration;
+
+use crate::card::{Card, CardColor};
+use crate::game::{GameEvent, GameView, Turn, TurnResult};
+use crate::rules::{self, IllegalBecause, Rules};
+use crate::server::manager::{Misbehavior, Penalty};
+use crate::server::session::SessionToken;
+
+/// The version of the messages here. It goes up whenever one changes in a way a client written
+/// for an earlier version would misread, and clients say which they speak when they join.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// What a player does on their turn.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Action {
+    /// Plays the card at `index` in the player's hand, in the order it iterates; wild cards
+    /// need a color.
+    Play { index: usize, color: Option<CardColor> },
+    Draw,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ClientMessage {
+    Join {
+        name: String,
+        /// The [`PROTOCOL_VERSION`] the client was written for. Clients from before it was sent
+        /// have `0`, and speak version 1.
+        #[cfg_attr(feature = "serde", serde(default))]
+        protocol: u32,
+    },
+    /// Takes a seat back after a dropped connection.
+    Rejoin { token: SessionToken },
+    Act { token: SessionToken, action: Action },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ServerMessage {
+    /// The token is only ever sent to the client that took the seat.
+    Joined { seat: usize, token: SessionToken },
+    Rejoined { seat: usize },
+    /// Something happened, with the table as the receiving seat may see it afterwards.
+    Update { event: GameEvent, view: Box<GameView> },
+    Rejected { reason: Rejection },
+    /// Sent to a seat that has misbehaved. `warnings_left` is how many more times it can
+    /// happen before the game's penalty is taken, or `None` if there isn't one.
+    Warned { misbehavior: Misbehavior, warnings_left: Option<u8> },
+    /// Sent to every seat when one has misbehaved once too often.
+    Penalized { seat: usize, penalty: Penalty },
+    /// Sent to every seat when one has run out of time on its clock, just before its time
+    /// bank's penalty is taken.
+    OutOfTime { seat: usize },
+    /// Sent to every seat of a game that went `idle` without a move, past the manager's
+    /// [limit](crate::server::manager::GameManager::set_idle_limit), just before it's aborted.
+    GameExpired { idle: Duration },
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Rejection {
+    TableFull,
+    UnknownToken,
+    /// The client was written for a later version of the protocol than the server's, which is
+    /// `supported`.
+    Incompatible { supported: u32 },
+    Illegal(IllegalBecause),
+}
+
+impl Action {
+    /// The move this action makes on `turn`, if it's legal.
+    pub fn resolve(&self, turn: &Turn) -> Option<TurnResult> {
+        self.check(turn).ok()
+    }
+
+    /// [`resolve`](Action::resolve), with the reason when the action isn't legal.
+    pub fn check(&self, turn: &Turn) -> Result<TurnResult, IllegalBecause> {
+        match *self {
+            Action::Draw => Ok(TurnResult::Drew),
+            Action::Play { index, color } => {
+                let mut card = turn.full_hand.iter().nth(index).ok_or(IllegalBecause::CardNotInHand)?;
+
+                if !turn.playable_hand.contains(&card) {
+                    return Err(match turn.drawn {
+                        Some(_) => IllegalBecause::OnlyDrawnCardPlayable,
+                        None => rules::refusal(&turn.last_card, turn.to_draw),
+                    });
+                }
+
+                if card.is_wild() {
+                    card.with_color(color.ok_or(IllegalBecause::NoColorChosen)?);
+                }
+
+                Ok(TurnResult::Played(card))
+            }
+        }
+    }
+
+    /// Every action open to a player holding `hand`, in the order it iterates, with `top` on
+    /// the pile and `second` named along with it, if it's a wild declared as two colors: each
+    /// card that can be played, once per color if it's wild, and then drawing.
+    pub fn legal(hand: &[Card], top: &Card, second: Option<CardColor>, to_draw: u8, rules: &Rules) -> Vec<Action> {
+        let mut actions = vec![];
+
+        for (index, card) in hand.iter().enumerate().filter(|(_, card)| rules::can_play_either(card, top, second, to_draw, rules)) {
+            match card.is_wild() {
+                true => actions.extend(CardColor::ALL.map(|color| Action::Play { index, color: Some(color) })),
+                false => actions.push(Action::Play { index, color: None }),
+            }
+        }
+
+        actions.push(Action::Draw);
+        actions
+    }
+
+    /// Every action open to the player on `turn`, in the same order as [`legal`](Action::legal).
+    /// Unlike that, it goes by what the engine offers, so a turn that only lets them play the
+    /// card they just drew only offers that.
+    pub fn offered(turn: &Turn) -> Vec<Action> {
+        let mut actions = vec![];
+
+        for (index, card) in turn.full_hand.iter().enumerate().filter(|(_, card)| turn.playable_hand.contains(card)) {
+            match card.is_wild() {
+                true => actions.extend(CardColor::ALL.map(|color| Action::Play { index, color: Some(color) })),
+                false => actions.push(Action::Play { index, color: None }),
+            }
+        }
+
+        actions.push(Action::Draw);
+        actions
+    }
+}
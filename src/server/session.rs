@@ -0,0 +1,134 @@
+use std::fmt::{self, Display, Formatter};
+
+use rand::RngCore;
+
+use crate::server::protocol::{Action, ClientMessage, Rejection, ServerMessage, PROTOCOL_VERSION};
+
+/// Identifies one network connection to the server.
+pub type ConnectionId = u64;
+
+/// A secret proving ownership of a seat, handed out when the seat is taken.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SessionToken(String);
+
+impl SessionToken {
+    fn generate(rng: &mut impl RngCore) -> SessionToken {
+        let mut bytes = [0u8; 16];
+        rng.fill_bytes(&mut bytes);
+        SessionToken(bytes.iter().map(|byte| format!("{byte:02x}")).collect())
+    }
+
+    /// Compares in constant time, so response timing doesn't leak how much of a guess was right.
+    fn matches(&self, other: &SessionToken) -> bool {
+        let (a, b) = (self.0.as_bytes(), other.0.as_bytes());
+        a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+    }
+}
+
+impl From<String> for SessionToken {
+    fn from(token: String) -> SessionToken {
+        SessionToken(token)
+    }
+}
+
+impl Display for SessionToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+struct Seat {
+    name: String,
+    token: SessionToken,
+    connection: Option<ConnectionId>,
+}
+
+/// What the server should do with a client message.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    /// Send this back to the connection the message came from.
+    Reply(ServerMessage),
+    /// Apply the action for this seat; the sender has proven it owns it.
+    Act { seat: usize, action: Action },
+}
+
+/// The seats of one table and the tokens that own them.
+///
+/// Actions are attributed to whichever seat their token belongs to, never to a seat the client
+/// names, so a connection can only ever act for a seat it was given the token for.
+pub struct Sessions {
+    seats: Vec<Seat>,
+    capacity: usize,
+}
+
+impl Sessions {
+    pub fn new(capacity: usize) -> Sessions {
+        Sessions { seats: vec![], capacity }
+    }
+
+    pub fn handle(&mut self, connection: ConnectionId, message: ClientMessage, rng: &mut impl RngCore) -> Outcome {
+        let result = match message {
+            ClientMessage::Join { protocol, .. } if protocol > PROTOCOL_VERSION => Err(Rejection::Incompatible { supported: PROTOCOL_VERSION }),
+            ClientMessage::Join { name, .. } => self.join(connection, name, rng)
+                .map(|(seat, token)| ServerMessage::Joined { seat, token }),
+            ClientMessage::Rejoin { token } => self.rejoin(connection, &token)
+                .map(|seat| ServerMessage::Rejoined { seat }),
+            ClientMessage::Act { token, action } => match self.authorize(&token) {
+                Some(seat) => return Outcome::Act { seat, action },
+                None => Err(Rejection::UnknownToken),
+            },
+        };
+
+        Outcome::Reply(result.unwrap_or_else(|reason| ServerMessage::Rejected { reason }))
+    }
+
+    pub fn join(&mut self, connection: ConnectionId, name: String, rng: &mut impl RngCore) -> Result<(usize, SessionToken), Rejection> {
+        if self.seats.len() >= self.capacity {
+            return Err(Rejection::TableFull);
+        }
+
+        let token = SessionToken::generate(rng);
+        self.seats.push(Seat { name, token: token.clone(), connection: Some(connection) });
+
+        Ok((self.seats.len() - 1, token))
+    }
+
+    /// Moves a seat to a new connection. The old connection, if it's still open, loses it.
+    pub fn rejoin(&mut self, connection: ConnectionId, token: &SessionToken) -> Result<usize, Rejection> {
+        let seat = self.authorize(token).ok_or(Rejection::UnknownToken)?;
+        self.seats[seat].connection = Some(connection);
+        Ok(seat)
+    }
+
+    /// Frees up the seats held by a closed connection for rejoining. Their tokens stay valid.
+    pub fn disconnect(&mut self, connection: ConnectionId) {
+        for seat in &mut self.seats {
+            if seat.connection == Some(connection) {
+                seat.connection = None;
+            }
+        }
+    }
+
+    /// The seat `token` owns.
+    pub fn authorize(&self, token: &SessionToken) -> Option<usize> {
+        self.seats.iter().position(|seat| seat.token.matches(token))
+    }
+
+    /// The connection to send a seat's messages to, if its player is connected.
+    pub fn connection(&self, seat: usize) -> Option<ConnectionId> {
+        self.seats.get(seat)?.connection
+    }
+
+    pub fn name(&self, seat: usize) -> Option<&str> {
+        self.seats.get(seat).map(|seat| seat.name.as_str())
+    }
+
+    pub fn len(&self) -> usize {
+        self.seats.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seats.is_empty()
+    }
+}
@@ -0,0 +1,199 @@
+//! Where a [`GameManager`](crate::server::manager::GameManager) keeps its games between
+//! restarts. Anything that can hold a snapshot and a list of events per game can back it by
+//! implementing [`GameStore`]; [`MemoryStore`] and, with the `persistence` feature,
+//! [`FileStore`] come with the crate.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+#[cfg(feature = "persistence")]
+use std::fs::{self, OpenOptions};
+use std::io;
+#[cfg(feature = "persistence")]
+use std::io::Write;
+#[cfg(feature = "persistence")]
+use std::path::PathBuf;
+
+use crate::game::GameEvent;
+use crate::server::manager::GameId;
+use crate::snapshot::Snapshot;
+
+/// Storage for games by id: the latest snapshot of each, and every event it's had.
+pub trait GameStore: Send {
+    /// Keeps `snapshot` as the game's latest, replacing the one before it.
+    fn save_snapshot(&mut self, game: GameId, snapshot: &Snapshot) -> Result<(), StoreError>;
+
+    /// The game's latest snapshot, or `None` if nothing has been saved for it.
+    fn load_snapshot(&self, game: GameId) -> Result<Option<Snapshot>, StoreError>;
+
+    /// Adds `events` after those already stored for the game.
+    fn append_events(&mut self, game: GameId, events: &[GameEvent]) -> Result<(), StoreError>;
+
+    /// Every event stored for the game, in the order they happened.
+    fn load_events(&self, game: GameId) -> Result<Vec<GameEvent>, StoreError>;
+
+    /// Every game with a snapshot or events stored, in order of id.
+    fn list_games(&self) -> Result<Vec<GameId>, StoreError>;
+}
+
+/// Why a [`GameStore`] couldn't do what it was asked.
+#[derive(Debug)]
+pub enum StoreError {
+    /// Whatever holds the games failed, such as a file that couldn't be written or a database
+    /// that couldn't be reached.
+    Backend(Box<dyn std::error::Error + Send + Sync>),
+    /// Something stored couldn't be read back.
+    Corrupt(String),
+}
+
+impl Display for StoreError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            StoreError::Backend(error) => write!(f, "the store failed: {error}"),
+            StoreError::Corrupt(problem) => write!(f, "the store holds something unreadable: {problem}"),
+        }
+    }
+}
+
+impl std::error::Error for StoreError {}
+
+impl From<io::Error> for StoreError {
+    fn from(error: io::Error) -> Self {
+        StoreError::Backend(Box::new(error))
+    }
+}
+
+/// Keeps everything in memory, for tests and for hosts that only need games to outlive the
+/// manager rather than the process.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MemoryStore {
+    games: BTreeMap<GameId, Stored>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Stored {
+    snapshot: Option<Snapshot>,
+    events: Vec<GameEvent>,
+}
+
+impl MemoryStore {
+    pub fn new() -> MemoryStore {
+        MemoryStore::default()
+    }
+}
+
+impl GameStore for MemoryStore {
+    fn save_snapshot(&mut self, game: GameId, snapshot: &Snapshot) -> Result<(), StoreError> {
+        self.games.entry(game).or_default().snapshot = Some(snapshot.clone());
+        Ok(())
+    }
+
+    fn load_snapshot(&self, game: GameId) -> Result<Option<Snapshot>, StoreError> {
+        Ok(self.games.get(&game).and_then(|stored| stored.snapshot.clone()))
+    }
+
+    fn append_events(&mut self, game: GameId, events: &[GameEvent]) -> Result<(), StoreError> {
+        self.games.entry(game).or_default().events.extend_from_slice(events);
+        Ok(())
+    }
+
+    fn load_events(&self, game: GameId) -> Result<Vec<GameEvent>, StoreError> {
+        Ok(self.games.get(&game).map(|stored| stored.events.clone()).unwrap_or_default())
+    }
+
+    fn list_games(&self) -> Result<Vec<GameId>, StoreError> {
+        Ok(self.games.keys().copied().collect())
+    }
+}
+
+/// Keeps each game in a directory as two files: `<id>.json` with its latest snapshot, and
+/// `<id>.events.jsonl` with an event on each line, so events are only ever appended.
+#[cfg(feature = "persistence")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileStore {
+    directory: PathBuf,
+}
+
+#[cfg(feature = "persistence")]
+impl FileStore {
+    /// A store in `directory`, which is made if it doesn't exist yet.
+    pub fn open(directory: impl Into<PathBuf>) -> io::Result<FileStore> {
+        let directory = directory.into();
+        fs::create_dir_all(&directory)?;
+        Ok(FileStore { directory })
+    }
+
+    fn snapshot_path(&self, game: GameId) -> PathBuf {
+        self.directory.join(format!("{game}.json"))
+    }
+
+    fn events_path(&self, game: GameId) -> PathBuf {
+        self.directory.join(format!("{game}.events.jsonl"))
+    }
+}
+
+#[cfg(feature = "persistence")]
+impl GameStore for FileStore {
+    fn save_snapshot(&mut self, game: GameId, snapshot: &Snapshot) -> Result<(), StoreError> {
+        let json = serde_json::to_string(snapshot).map_err(|error| StoreError::Backend(Box::new(error)))?;
+
+        // Written alongside and then moved over the old one, so a crash part way leaves the
+        // last snapshot whole.
+        let path = self.snapshot_path(game);
+        let partial = path.with_extension("json.partial");
+        fs::write(&partial, json)?;
+        fs::rename(partial, path)?;
+        Ok(())
+    }
+
+    fn load_snapshot(&self, game: GameId) -> Result<Option<Snapshot>, StoreError> {
+        match fs::read_to_string(self.snapshot_path(game)) {
+            Ok(json) => serde_json::from_str(&json).map(Some).map_err(|error| StoreError::Corrupt(format!("game {game}'s snapshot: {error}"))),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    fn append_events(&mut self, game: GameId, events: &[GameEvent]) -> Result<(), StoreError> {
+        let mut lines = String::new();
+
+        for event in events {
+            lines += &serde_json::to_string(event).map_err(|error| StoreError::Backend(Box::new(error)))?;
+            lines.push('\n');
+        }
+
+        OpenOptions::new().create(true).append(true).open(self.events_path(game))?.write_all(lines.as_bytes())?;
+        Ok(())
+    }
+
+    fn load_events(&self, game: GameId) -> Result<Vec<GameEvent>, StoreError> {
+        let lines = match fs::read_to_string(self.events_path(game)) {
+            Ok(lines) => lines,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(error) => return Err(error.into()),
+        };
+
+        lines
+            .lines()
+            .enumerate()
+            .map(|(line, json)| serde_json::from_str(json).map_err(|error| StoreError::Corrupt(format!("game {game}'s event on line {}: {error}", line + 1))))
+            .collect()
+    }
+
+    fn list_games(&self) -> Result<Vec<GameId>, StoreError> {
+        let mut games = vec![];
+
+        for entry in fs::read_dir(&self.directory)? {
+            let name = entry?.file_name();
+            let name = name.to_string_lossy();
+            let id = name.strip_suffix(".events.jsonl").or_else(|| name.strip_suffix(".json"));
+
+            if let Some(game) = id.and_then(|id| id.parse::<GameId>().ok()) {
+                games.push(game);
+            }
+        }
+
+        games.sort_unstable();
+        games.dedup();
+        Ok(games)
+    }
+}
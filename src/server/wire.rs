@@ -0,0 +1,48 @@
+use std::fmt::{self, Display, Formatter};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// How messages are written on a connection. Pick one per connection when it opens, for
+/// example from its WebSocket subprotocol, and use it for everything sent and received on it.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Encoding {
+    #[default]
+    Json,
+    /// A compact varint encoding, a fraction of the size of JSON, for clients on metered
+    /// connections and for spectator feeds.
+    Binary,
+}
+
+#[derive(Debug)]
+pub enum WireError {
+    Json(serde_json::Error),
+    Binary(postcard::Error),
+}
+
+impl Encoding {
+    pub fn encode<T: Serialize>(self, message: &T) -> Result<Vec<u8>, WireError> {
+        match self {
+            Encoding::Json => serde_json::to_vec(message).map_err(WireError::Json),
+            Encoding::Binary => postcard::to_stdvec(message).map_err(WireError::Binary),
+        }
+    }
+
+    pub fn decode<T: DeserializeOwned>(self, bytes: &[u8]) -> Result<T, WireError> {
+        match self {
+            Encoding::Json => serde_json::from_slice(bytes).map_err(WireError::Json),
+            Encoding::Binary => postcard::from_bytes(bytes).map_err(WireError::Binary),
+        }
+    }
+}
+
+impl Display for WireError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            WireError::Json(e) => write!(f, "invalid JSON message: {e}"),
+            WireError::Binary(e) => write!(f, "invalid binary message: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for WireError {}
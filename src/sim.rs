@@ -0,0 +1,455 @@
+//! Headless batches of games, for comparing strategies, and reports of how they went.
+
+use alloc::{format, string::{String, ToString}, vec, vec::Vec};
+#[cfg(feature = "report")]
+use std::io::{self, Write};
+
+use rand::prelude::SliceRandom;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::card::{Card, CardColor, Deck};
+use crate::game::{Direction, GameState, GameView, Turn, TurnResult};
+use crate::player::Player;
+use crate::rules::{Rules, TurnOrder};
+use crate::snapshot::Position;
+use crate::summary::GameOutcome;
+
+/// A batch of seeded games between the same players. Game `n` is seeded with `seed + n`, so a
+/// batch can be run again exactly, or any one game from it replayed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Simulation {
+    pub seed: u64,
+    pub games: u32,
+    /// Games still going after this many turns are aborted and counted as unfinished.
+    pub max_turns: u32,
+    pub rules: Rules,
+}
+
+impl Default for Simulation {
+    fn default() -> Self {
+        Simulation { seed: 0, games: 1000, max_turns: 2000, rules: Rules::default() }
+    }
+}
+
+impl Simulation {
+    /// Plays every game with the players in the same seats, without pausing between turns.
+    pub fn run(&self, players: &mut [&mut dyn Player]) -> SimulationReport {
+        let mut report = SimulationReport::new(players.iter().map(|player| player.name().to_string()).collect());
+
+        for game in 0..self.games {
+            let seed = self.seed.wrapping_add(game as u64);
+            let mut state = GameState::with_seed(players.iter_mut().map(|player| &mut **player as &mut dyn Player).collect(), seed);
+            state.set_rules(self.rules);
+
+            report.push(seed, &self.play(&mut state, |_| {}));
+        }
+
+        report
+    }
+
+    /// Plays every game like [`run`](Simulation::run), but counts wins by where the winner came in
+    /// the order of play rather than by who they were, to tell how much going first is worth
+    /// under [`rules`](Simulation::rules). The first turn goes round the table from one game to
+    /// the next, so every player starts as often as the others and how well they play evens out
+    /// over a batch whose size the table divides.
+    pub fn positions(&self, players: &mut [&mut dyn Player]) -> PositionReport {
+        let seats = players.len();
+        let mut report = PositionReport::new(seats);
+
+        for game in 0..self.games {
+            let seed = self.seed.wrapping_add(game as u64);
+            let mut state = GameState::with_seed(players.iter_mut().map(|player| &mut **player as &mut dyn Player).collect(), seed);
+            state.set_rules(self.rules);
+            state.set_first_player(game as usize % seats.max(1));
+
+            let mut opening = None;
+
+            // The first turn only deals, and whatever card it turned up has had its effect by the
+            // time it's over, so what comes after it is the order play really started in.
+            let outcome = self.play(&mut state, |state| {
+                opening.get_or_insert_with(|| (state.upcoming_turn_order(seats), state.view(None).direction));
+            });
+
+            report.push(opening.as_ref().map(|(order, direction)| (order.as_slice(), *direction)), &outcome);
+        }
+
+        report
+    }
+
+    /// Plays `state` out, calling `turn` after every turn but the last, and aborts it if it's still
+    /// going after [`max_turns`](Simulation::max_turns).
+    fn play(&self, state: &mut GameState, mut turn: impl FnMut(&GameState)) -> GameOutcome {
+        let mut turns = 0;
+
+        loop {
+            if let Some(outcome) = state.play_turn() {
+                return outcome;
+            }
+
+            turn(state);
+            turns += 1;
+
+            if turns >= self.max_turns {
+                return state.abort("turn limit reached");
+            }
+        }
+    }
+
+    /// Each seat's chance of winning from where `view` leaves a game played under `rules`,
+    /// going by how `games` quick games played on from there turn out. Game `n` deals the cards
+    /// the view doesn't show at random with `seed + n`, so a seat's own view gives the odds as
+    /// it sees them and a spectator's gives odds that can be shown to anyone. Everyone plays the
+    /// first card they can, and games still going after `max_turns` count for no one.
+    pub fn estimate_win_probabilities(&self, view: &GameView, rules: &Rules) -> Vec<f64> {
+        let seats = view.players.len();
+        let mut wins = vec![0u32; seats];
+
+        let Some(top) = view.top_card else {
+            return vec![0.0; seats];
+        };
+
+        // Whoever played last is the current player, so play goes on from them.
+        let out = view.players.iter().enumerate().filter(|(_, player)| player.out).map(|(seat, _)| seat).collect::<Vec<usize>>();
+        let to_play = TurnOrder {
+            current: view.current_player,
+            seats,
+            direction: view.direction,
+            to_draw: view.to_draw,
+            out: out.clone(),
+        }.next();
+
+        let mut unseen = Deck::new_deck();
+
+        for card in view.hand.iter().chain([&top]) {
+            if let Some(index) = unseen.iter().position(|unseen| unseen == card) {
+                unseen.swap_remove(index);
+            }
+        }
+
+        for game in 0..self.games {
+            let seed = self.seed.wrapping_add(game as u64);
+            let mut deck = unseen.clone();
+            deck.shuffle(&mut ChaCha8Rng::seed_from_u64(seed));
+
+            let hands = view.players.iter().enumerate().map(|(seat, player)| match Some(seat) == view.seat {
+                true => view.hand.clone(),
+                false => deck.split_off(deck.len().saturating_sub(player.cards)),
+            }).collect();
+
+            let position = Position { hands, deck, discard: vec![top], to_play, direction: view.direction, to_draw: view.to_draw, rules: *rules, out: out.clone(), seed };
+            let mut players = (0..seats).map(|seat| FirstPlayable { name: format!("Seat {seat}") }).collect::<Vec<FirstPlayable>>();
+            let Ok(mut state) = GameState::from_position(players.iter_mut().map(|player| player as &mut dyn Player).collect(), &position) else {
+                break;
+            };
+
+            let winner = (0..self.max_turns).find_map(|_| state.play_turn());
+
+            if let Some(GameOutcome::Won(summary)) = winner {
+                if let Some(seat) = summary.ranking.first() {
+                    wins[*seat] += 1;
+                }
+            }
+        }
+
+        wins.iter().map(|wins| if self.games == 0 { 0.0 } else { *wins as f64 / self.games as f64 }).collect()
+    }
+}
+
+/// Plays the first card it can, in the color it holds most of, to play a game out quickly.
+struct FirstPlayable {
+    name: String,
+}
+
+impl Player for FirstPlayable {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn execute_turn(&mut self, turn: &Turn) -> TurnResult {
+        let Some(mut card) = turn.playable_hand.first().copied() else {
+            return TurnResult::Drew;
+        };
+
+        if card.is_wild() {
+            let held = |color: &CardColor| turn.full_hand.iter().filter(|card| !card.is_wild() && card.color() == Some(*color)).count();
+            card.with_color(CardColor::ALL.into_iter().max_by_key(held).unwrap_or(CardColor::Red));
+        }
+
+        TurnResult::Played(card)
+    }
+
+    fn observe_turn(&self, _other: &dyn Player, _card: &Card) {}
+
+    fn observe_turn_skip(&self, _observed_cards: Option<&[Card]>) {}
+
+    fn wants_events(&self) -> bool {
+        false
+    }
+}
+
+/// One game from a batch.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameRecord {
+    pub seed: u64,
+    /// `None` for a game that was aborted.
+    pub winner: Option<String>,
+    pub winner_seat: Option<usize>,
+    pub turns: u32,
+    /// What the winner scored.
+    pub points: u32,
+}
+
+/// Every game in a batch, in the order they were played.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimulationReport {
+    /// In seating order.
+    pub players: Vec<String>,
+    pub games: Vec<GameRecord>,
+}
+
+/// Totals over a whole batch, from [`SimulationReport::summary`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimulationSummary {
+    pub games: usize,
+    pub unfinished: usize,
+    pub average_turns: f64,
+    /// In seating order.
+    pub seats: Vec<SeatSummary>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SeatSummary {
+    pub name: String,
+    pub wins: u32,
+    /// Out of every game, unfinished ones included.
+    pub win_rate: f64,
+    pub points: u32,
+}
+
+impl SimulationReport {
+    pub fn new(players: Vec<String>) -> SimulationReport {
+        SimulationReport { players, games: Vec::new() }
+    }
+
+    /// Adds a game, however it was played.
+    pub fn push(&mut self, seed: u64, outcome: &GameOutcome) {
+        let (winner, winner_seat, points) = match outcome {
+            GameOutcome::Won(summary) => (
+                Some(summary.winner.clone()),
+                // The winner is the only one who finishes without cards.
+                summary.players.iter().position(|player| player.hand.is_empty()),
+                summary.points,
+            ),
+            GameOutcome::Aborted { .. } => (None, None, 0),
+        };
+
+        self.games.push(GameRecord { seed, winner, winner_seat, turns: outcome.turns(), points });
+    }
+
+    pub fn summary(&self) -> SimulationSummary {
+        let games = self.games.len();
+        let turns = self.games.iter().map(|game| game.turns as f64).sum::<f64>();
+
+        let seats = self.players
+            .iter()
+            .enumerate()
+            .map(|(seat, name)| {
+                let won = self.games.iter().filter(|game| game.winner_seat == Some(seat));
+                let (wins, points) = won.fold((0, 0), |(wins, points), game| (wins + 1, points + game.points));
+
+                SeatSummary {
+                    name: name.clone(),
+                    wins,
+                    win_rate: if games == 0 { 0.0 } else { wins as f64 / games as f64 },
+                    points,
+                }
+            })
+            .collect();
+
+        SimulationSummary {
+            games,
+            unfinished: self.games.iter().filter(|game| game.winner.is_none()).count(),
+            average_turns: if games == 0 { 0.0 } else { turns / games as f64 },
+            seats,
+        }
+    }
+
+    /// Writes one row per game, with a header.
+    #[cfg(feature = "report")]
+    pub fn write_games_csv(&self, mut writer: impl Write) -> io::Result<()> {
+        writeln!(writer, "seed,winner,winner_seat,turns,points")?;
+
+        for game in &self.games {
+            writeln!(
+                writer,
+                "{},{},{},{},{}",
+                game.seed,
+                game.winner.as_deref().map(csv_field).unwrap_or_default(),
+                game.winner_seat.map(|seat| seat.to_string()).unwrap_or_default(),
+                game.turns,
+                game.points,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes one row per seat with its totals, with a header. Batch-wide figures are repeated on
+    /// every row so the file stands on its own as a table.
+    #[cfg(feature = "report")]
+    pub fn write_summary_csv(&self, mut writer: impl Write) -> io::Result<()> {
+        let summary = self.summary();
+
+        writeln!(writer, "seat,name,wins,win_rate,points,games,unfinished,average_turns")?;
+
+        for (seat, totals) in summary.seats.iter().enumerate() {
+            writeln!(
+                writer,
+                "{seat},{},{},{},{},{},{},{}",
+                csv_field(&totals.name),
+                totals.wins,
+                totals.win_rate,
+                totals.points,
+                summary.games,
+                summary.unfinished,
+                summary.average_turns,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the players, games and summary as one JSON document.
+    #[cfg(feature = "report")]
+    pub fn write_json(&self, writer: impl Write) -> io::Result<()> {
+        #[derive(serde::Serialize)]
+        struct Document<'a> {
+            players: &'a [String],
+            games: &'a [GameRecord],
+            summary: SimulationSummary,
+        }
+
+        let document = Document { players: &self.players, games: &self.games, summary: self.summary() };
+        serde_json::to_writer_pretty(writer, &document)?;
+        Ok(())
+    }
+}
+
+/// Wins by place in the order of play over a batch, from [`Simulation::positions`]. Place 0 is
+/// whoever took the first turn, place 1 whoever came after them, and so on.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PositionReport {
+    pub games: u32,
+    pub unfinished: u32,
+    /// Games that started clockwise, then those that started counterclockwise, whether from the
+    /// rules or a reverse turned up by the deal.
+    pub directions: [DirectionRecord; 2],
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DirectionRecord {
+    /// Finished or not.
+    pub games: u32,
+    /// By place in the order of play.
+    pub wins: Vec<u32>,
+}
+
+impl PositionReport {
+    pub fn new(seats: usize) -> PositionReport {
+        let direction = || DirectionRecord { games: 0, wins: vec![0; seats] };
+        PositionReport { games: 0, unfinished: 0, directions: [direction(), direction()] }
+    }
+
+    /// Adds a game, given the order play started in and its direction. A game that ended before
+    /// anyone took a turn has no order, and counts only as unfinished.
+    pub fn push(&mut self, opening: Option<(&[usize], Direction)>, outcome: &GameOutcome) {
+        self.games += 1;
+
+        let Some((order, direction)) = opening else {
+            self.unfinished += 1;
+            return;
+        };
+
+        let record = &mut self.directions[usize::from(direction == Direction::CounterClockwise)];
+        record.games += 1;
+
+        let place = match outcome {
+            GameOutcome::Won(summary) => summary.ranking.first().and_then(|winner| order.iter().position(|seat| seat == winner)),
+            GameOutcome::Aborted { .. } => None,
+        };
+
+        match place {
+            Some(place) => record.wins[place] += 1,
+            None => self.unfinished += 1,
+        }
+    }
+
+    /// Adds everything from another batch with as many seats, such as one played on another
+    /// thread.
+    pub fn merge(&mut self, other: &PositionReport) {
+        self.games += other.games;
+        self.unfinished += other.unfinished;
+
+        for (record, other) in self.directions.iter_mut().zip(&other.directions) {
+            record.games += other.games;
+
+            for (wins, other) in record.wins.iter_mut().zip(&other.wins) {
+                *wins += other;
+            }
+        }
+    }
+
+    /// Wins by place, whichever way play went.
+    pub fn wins(&self) -> Vec<u32> {
+        let [clockwise, counterclockwise] = &self.directions;
+        clockwise.wins.iter().zip(&counterclockwise.wins).map(|(clockwise, counterclockwise)| clockwise + counterclockwise).collect()
+    }
+
+    /// Each place's share of the wins, out of every game, unfinished ones included.
+    pub fn win_rates(&self) -> Vec<f64> {
+        self.wins().iter().map(|wins| if self.games == 0 { 0.0 } else { *wins as f64 / self.games as f64 }).collect()
+    }
+
+    /// How many times more often the first player won than an even share of the finished games
+    /// would have them win: 1.0 is fair, 1.2 is a fifth more often than fair.
+    pub fn first_player_advantage(&self) -> f64 {
+        let wins = self.wins();
+        let finished = wins.iter().sum::<u32>();
+
+        match wins.first() {
+            Some(first) if finished > 0 => *first as f64 * wins.len() as f64 / finished as f64,
+            _ => 0.0,
+        }
+    }
+
+    /// Writes one row per direction and place, with a header.
+    #[cfg(feature = "report")]
+    pub fn write_csv(&self, mut writer: impl Write) -> io::Result<()> {
+        writeln!(writer, "direction,place,wins,games")?;
+
+        for (direction, record) in ["clockwise", "counterclockwise"].iter().zip(&self.directions) {
+            for (place, wins) in record.wins.iter().enumerate() {
+                writeln!(writer, "{direction},{place},{wins},{}", record.games)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Quotes a field if it has anything in it that CSV would misread.
+#[cfg(feature = "report")]
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,122 @@
+//! Named save slots for games in progress, kept together in one JSON file, for frontends that
+//! want a "continue game" menu without keeping their own index of saves.
+
+use std::cmp::Reverse;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::GameState;
+use crate::player::Player;
+use crate::snapshot::{Snapshot, SnapshotError};
+
+/// A game saved under a name.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Slot {
+    pub name: String,
+    pub saved_at: SystemTime,
+    pub snapshot: Snapshot,
+}
+
+impl Slot {
+    /// Who was playing, in seating order.
+    pub fn players(&self) -> &[String] {
+        &self.snapshot.players
+    }
+
+    /// How many turns had been played when the game was saved.
+    pub fn turn(&self) -> u32 {
+        self.snapshot.turns
+    }
+}
+
+/// Why a slot couldn't be resumed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResumeError {
+    /// Nothing is saved under the name.
+    NoSuchSlot(String),
+    Snapshot(SnapshotError),
+}
+
+impl Display for ResumeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ResumeError::NoSuchSlot(name) => write!(f, "there's no game saved as {name}"),
+            ResumeError::Snapshot(error) => write!(f, "the game saved there couldn't be restored: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for ResumeError {}
+
+/// Save slots, kept in a JSON file between sessions.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SlotStore {
+    slots: Vec<Slot>,
+}
+
+impl SlotStore {
+    /// Reads the store at `path`, or starts an empty one if the file doesn't exist yet.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<SlotStore> {
+        match fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(SlotStore::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, json)
+    }
+
+    /// Saves `snapshot` as `name`, over whatever was saved there before.
+    pub fn put(&mut self, name: &str, snapshot: Snapshot) {
+        self.slots.retain(|slot| slot.name != name);
+        self.slots.push(Slot { name: name.to_string(), saved_at: SystemTime::now(), snapshot });
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Slot> {
+        self.slots.iter().find(|slot| slot.name == name)
+    }
+
+    /// Returns whether there was anything saved as `name`.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let count = self.slots.len();
+        self.slots.retain(|slot| slot.name != name);
+        self.slots.len() != count
+    }
+
+    /// Every slot, most recently saved first.
+    pub fn list(&self) -> Vec<&Slot> {
+        let mut slots = self.slots.iter().collect::<Vec<&Slot>>();
+        slots.sort_by_key(|slot| Reverse(slot.saved_at));
+        slots
+    }
+
+    /// The game to offer to continue.
+    pub fn latest(&self) -> Option<&Slot> {
+        self.slots.iter().max_by_key(|slot| slot.saved_at)
+    }
+
+    /// Restores the game saved as `name`, with `players` taking its seats in order. Unseeded
+    /// games need a generator to shuffle with, so they have to be restored from their
+    /// [`snapshot`](Slot::snapshot) with
+    /// [`from_snapshot_with_rng`](GameState::from_snapshot_with_rng) instead.
+    pub fn resume<'a>(&self, name: &str, players: Vec<&'a mut dyn Player>) -> Result<GameState<'a>, ResumeError> {
+        let slot = self.get(name).ok_or_else(|| ResumeError::NoSuchSlot(name.to_string()))?;
+        GameState::from_snapshot(players, &slot.snapshot).map_err(ResumeError::Snapshot)
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+}
@@ -0,0 +1,140 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Display, Formatter};
+
+use crate::card::{Card, CardColor};
+use crate::game::{Direction, Opening, StackedDraw, Tendencies};
+use crate::replay::ReplayAction;
+use crate::rules::{Rules, ENGINE_VERSION};
+
+/// Everything about a game in progress except the players themselves, from
+/// [`GameState::snapshot`](crate::game::GameState::snapshot). Save one to carry a game across a
+/// restart, then hand it to [`GameState::from_snapshot`](crate::game::GameState::from_snapshot)
+/// along with players for the same seats.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Snapshot {
+    /// In seating order, as they were when the snapshot was taken.
+    pub players: Vec<String>,
+    pub hands: Vec<Vec<Card>>,
+    pub deck: Vec<Card>,
+    pub discard: Vec<Card>,
+    pub current_player: usize,
+    pub direction: Direction,
+    pub to_draw: u8,
+    /// See [`GameView::draw_chain`](crate::game::GameView::draw_chain).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub draw_chain: Vec<StackedDraw>,
+    pub turns: u32,
+    pub seed: Option<u64>,
+    /// How far a seeded game has got through its seed's random stream, so that restoring it
+    /// shuffles exactly as the original would have.
+    pub stream_position: Option<u128>,
+    pub dealt: u64,
+    pub actions: Vec<ReplayAction>,
+    pub paused: bool,
+    pub aborted: Option<String>,
+    /// Snapshots from before rules could be changed were all taken under the defaults.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rules: Rules,
+    /// See [`GameState::seating`](crate::game::GameState::seating). Empty when the seats weren't
+    /// shuffled.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub seating: Vec<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub first_player: Option<usize>,
+    /// See [`GameState::set_opening`](crate::game::GameState::set_opening).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub opening: Opening,
+    /// See [`GameState::set_hand_sizes`](crate::game::GameState::set_hand_sizes).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub hand_sizes: Vec<u8>,
+    /// Seats out of the round, in the order they went out.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub out: Vec<usize>,
+    /// Those of `out` that went out by playing their last card.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub finished: Vec<usize>,
+    /// See [`PlayerView::tendencies`](crate::game::PlayerView::tendencies), by seat. Empty in
+    /// snapshots from before the engine kept them, which start everyone afresh.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tendencies: Vec<Tendencies>,
+    /// See [`GameState::active_colors`](crate::game::GameState::active_colors).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub second_color: Option<CardColor>,
+    /// The [`ENGINE_VERSION`] that took it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub engine: u32,
+    /// The game's fingerprint, checked again when it's restored.
+    pub fingerprint: u64,
+}
+
+/// A table set up by hand rather than dealt, such as a teaching position or a rule to check.
+/// Hand it to [`GameState::from_position`](crate::game::GameState::from_position) with a player
+/// for each hand.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Position {
+    pub hands: Vec<Vec<Card>>,
+    /// With the next card to be drawn last.
+    pub deck: Vec<Card>,
+    /// With the top card last. There has to be one.
+    pub discard: Vec<Card>,
+    /// The seat that plays first.
+    pub to_play: usize,
+    pub direction: Direction,
+    pub to_draw: u8,
+    pub rules: Rules,
+    /// Seats already out of the round, each once, which play passes over.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub out: Vec<usize>,
+    /// What the discard pile is shuffled with when it's made into a new deck.
+    pub seed: u64,
+}
+
+/// Why a position couldn't be set up.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PositionError {
+    /// A different number of players was given than there are hands.
+    SeatCount { expected: usize, actual: usize },
+    NoTopCard,
+    /// The seat to play first, or one of those out, isn't at the table.
+    NoSuchSeat(usize),
+}
+
+impl Display for PositionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PositionError::SeatCount { expected, actual } => write!(f, "the position has {expected} hands but {actual} players were given"),
+            PositionError::NoTopCard => write!(f, "the discard pile needs a top card"),
+            PositionError::NoSuchSeat(seat) => write!(f, "there's no seat {seat} at the table"),
+        }
+    }
+}
+
+impl core::error::Error for PositionError {}
+
+/// Why a snapshot couldn't be restored.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SnapshotError {
+    /// A different number of players was given than there are seats.
+    SeatCount { expected: usize, actual: usize },
+    /// The game shuffled with its own generator, so one has to be supplied to restore it.
+    Unseeded,
+    /// The restored game doesn't hash to the recorded fingerprint, so the snapshot is damaged.
+    Fingerprint { expected: u64, actual: u64 },
+    /// The snapshot was taken by a later engine than this one, at version `found`.
+    Version { found: u32 },
+}
+
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::SeatCount { expected, actual } => write!(f, "the game has {expected} seats but {actual} players were given"),
+            SnapshotError::Unseeded => write!(f, "the game wasn't seeded, so it needs a generator to shuffle with"),
+            SnapshotError::Fingerprint { .. } => write!(f, "the snapshot doesn't match its fingerprint"),
+            SnapshotError::Version { found } => write!(f, "the snapshot was taken by engine version {found}, but this is version {ENGINE_VERSION}"),
+        }
+    }
+}
+
+impl core::error::Error for SnapshotError {}
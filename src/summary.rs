@@ -0,0 +1,256 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::card::Card;
+#[cfg(feature = "report")]
+use crate::game::GameEvent;
+#[cfg(feature = "report")]
+use crate::history::History;
+#[cfg(feature = "std")]
+use crate::locale::Message;
+#[cfg(feature = "report")]
+use crate::notation;
+use crate::player::PlayerId;
+#[cfg(feature = "std")]
+use crate::render::RenderConfig;
+
+/// The version of the schema [`GameOutcome::to_json`] writes. It only goes up when a field
+/// changes meaning or goes away; new fields can turn up without it.
+#[cfg(feature = "report")]
+pub const JSON_SCHEMA: u32 = 1;
+
+/// How a round that someone won ended.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoundSummary {
+    pub winner: String,
+    pub winner_id: PlayerId,
+    /// What the winner scores: the value of every card left in the other hands.
+    pub points: u32,
+    /// Every player in seating order, the winner included.
+    pub players: Vec<PlayerSummary>,
+    pub turns: u32,
+    /// Seats in `players` from first place to last.
+    pub ranking: Vec<usize>,
+    /// The top of the discard pile, as it was played.
+    pub last_card: Option<Card>,
+    /// A draw nobody had taken yet when the round ended: the seat that faced it, and how many
+    /// cards.
+    pub left_to_draw: Option<(usize, u8)>,
+}
+
+/// How a game ended, returned by [`GameState::start`](crate::game::GameState::start).
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameOutcome {
+    Won(RoundSummary),
+    /// Stopped by [`GameState::abort`](crate::game::GameState::abort) before anyone went out.
+    Aborted { reason: String, players: Vec<PlayerSummary>, turns: u32 },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerSummary {
+    pub id: PlayerId,
+    pub name: String,
+    pub hand: Vec<Card>,
+    pub points: u32,
+    /// How many cards they were dealt, which is only other than
+    /// [`Rules::hand_size`](crate::rules::Rules::hand_size) under a
+    /// [handicap](crate::game::GameState::set_hand_sizes).
+    pub dealt: u8,
+}
+
+impl RoundSummary {
+    /// Lays the round out as a table, one row per player, followed by the result.
+    #[cfg(feature = "std")]
+    pub fn table(&self, render: &RenderConfig) -> String {
+        let locale = &render.locale;
+
+        let headers = [
+            locale.get(Message::SummaryPlayer),
+            locale.get(Message::SummaryCards),
+            locale.get(Message::SummaryPoints),
+            locale.get(Message::SummaryHand),
+        ];
+
+        let name_width = self.players
+            .iter()
+            .map(|player| player.name.chars().count())
+            .chain([headers[0].chars().count()])
+            .max()
+            .unwrap_or(0);
+        let cards_width = headers[1].chars().count().max(5);
+        let points_width = headers[2].chars().count().max(6);
+
+        let mut table = format!(
+            "{:<name_width$}  {:>cards_width$}  {:>points_width$}  {}\n",
+            headers[0], headers[1], headers[2], headers[3],
+        );
+
+        for player in &self.players {
+            // The hand goes last, since colored cards would throw off the column widths.
+            let hand = player.hand
+                .iter()
+                .map(|card| card.display(render).to_string())
+                .collect::<Vec<String>>()
+                .join(", ");
+
+            table += &format!(
+                "{:<name_width$}  {:>cards_width$}  {:>points_width$}  {hand}\n",
+                player.name, player.hand.len(), player.points,
+            );
+        }
+
+        table + &locale.format(Message::RoundResult, &[
+            ("player", &self.winner),
+            ("points", &self.points),
+            ("turns", &self.turns),
+        ])
+    }
+}
+
+impl GameOutcome {
+    /// The round's result, if someone won it.
+    pub fn won(&self) -> Option<&RoundSummary> {
+        match self {
+            GameOutcome::Won(summary) => Some(summary),
+            GameOutcome::Aborted { .. } => None,
+        }
+    }
+
+    /// Every player in seating order, with the cards they finished with.
+    pub fn players(&self) -> &[PlayerSummary] {
+        match self {
+            GameOutcome::Won(summary) => &summary.players,
+            GameOutcome::Aborted { players, .. } => players,
+        }
+    }
+
+    pub fn turns(&self) -> u32 {
+        match self {
+            GameOutcome::Won(summary) => summary.turns,
+            GameOutcome::Aborted { turns, .. } => *turns,
+        }
+    }
+
+    /// The result as a JSON document versioned by [`JSON_SCHEMA`], for tools that shouldn't
+    /// depend on the crate's types: the players with their places and what they were left
+    /// holding, the winner, the score and the number of turns. Cards are in the
+    /// [notation](crate::notation).
+    #[cfg(feature = "report")]
+    pub fn to_json(&self) -> String {
+        self.json(None)
+    }
+
+    /// [`to_json`](GameOutcome::to_json), with the game's notable events from `history` as
+    /// well: Draw Fours, stacks, traded hands, and players going out or leaving.
+    #[cfg(feature = "report")]
+    pub fn to_json_with(&self, history: &History) -> String {
+        self.json(Some(history))
+    }
+
+    #[cfg(feature = "report")]
+    fn json(&self, history: Option<&History>) -> String {
+        let summary = self.won();
+        let ranking = summary.map_or(&[][..], |summary| &summary.ranking);
+
+        let document = Document {
+            schema: JSON_SCHEMA,
+            result: match self {
+                GameOutcome::Won(_) => "won",
+                GameOutcome::Aborted { .. } => "aborted",
+            },
+            winner: ranking.first().copied(),
+            reason: match self {
+                GameOutcome::Won(_) => None,
+                GameOutcome::Aborted { reason, .. } => Some(reason),
+            },
+            score: summary.map(|summary| summary.points),
+            turns: self.turns(),
+            players: self.players().iter().enumerate().map(|(seat, player)| JsonPlayer {
+                seat,
+                name: &player.name,
+                place: ranking.iter().position(|ranked| *ranked == seat).map(|place| place + 1),
+                hand: player.hand.iter().map(notation::notate_card).collect(),
+                points: player.points,
+                dealt: player.dealt,
+            }).collect(),
+            notable: history.map_or(vec![], notable),
+        };
+
+        serde_json::to_string(&document).expect("a summary always serializes")
+    }
+}
+
+/// What's worth calling out from a game's history.
+#[cfg(feature = "report")]
+fn notable(history: &History) -> Vec<Notable<'_>> {
+    let mut notable = vec![];
+    // What the next player has to draw, so adding to it can be told from starting it.
+    let mut pending = 0;
+
+    let name = |player: &PlayerId| history.name_of(*player).unwrap_or_default();
+
+    for entry in &history.entries {
+        let turn = entry.turn;
+
+        notable.push(match &entry.event {
+            GameEvent::Played { player, card: Card::DrawFour { .. } } => Notable::DrawFour { turn, player: name(player) },
+            GameEvent::Stacked { player, to_draw } => {
+                let stacked = pending > 0;
+                pending = *to_draw;
+
+                match stacked {
+                    true => Notable::Stacked { turn, player: name(player), to_draw: *to_draw },
+                    false => continue,
+                }
+            }
+            GameEvent::Drew { .. } => {
+                pending = 0;
+                continue;
+            }
+            GameEvent::Traded { player, with } => Notable::Traded { turn, player: name(player), with: name(with) },
+            GameEvent::Eliminated { player } => Notable::Eliminated { turn, player: name(player) },
+            GameEvent::Finished { player, place } => Notable::Finished { turn, player: name(player), place: *place },
+            GameEvent::PlayerLeft { player, .. } => Notable::Left { turn, player: name(player) },
+            _ => continue,
+        });
+    }
+
+    notable
+}
+
+#[cfg(feature = "report")]
+#[derive(serde::Serialize)]
+struct Document<'a> {
+    schema: u32,
+    result: &'static str,
+    /// The winner's seat.
+    winner: Option<usize>,
+    reason: Option<&'a str>,
+    score: Option<u32>,
+    turns: u32,
+    players: Vec<JsonPlayer<'a>>,
+    notable: Vec<Notable<'a>>,
+}
+
+#[cfg(feature = "report")]
+#[derive(serde::Serialize)]
+struct JsonPlayer<'a> {
+    seat: usize,
+    name: &'a str,
+    /// From 1, for the places the game got as far as deciding.
+    place: Option<usize>,
+    hand: Vec<String>,
+    points: u32,
+    dealt: u8,
+}
+
+#[cfg(feature = "report")]
+#[derive(serde::Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Notable<'a> {
+    DrawFour { turn: u32, player: &'a str },
+    Stacked { turn: u32, player: &'a str, to_draw: u8 },
+    Traded { turn: u32, player: &'a str, with: &'a str },
+    Eliminated { turn: u32, player: &'a str },
+    Finished { turn: u32, player: &'a str, place: usize },
+    Left { turn: u32, player: &'a str },
+}
@@ -0,0 +1,192 @@
+//! Supervised-learning data: one record per decision a player made, with what they could see,
+//! what they couldn't, what they did and how the game turned out for them.
+//!
+//! Turn recording on with [`GameState::record_decisions`](crate::game::GameState::record_decisions),
+//! then pass [`GameState::decisions`](crate::game::GameState::decisions) and the game's outcome to
+//! [`records`] once it's over.
+//!
+//! Cards are encoded as [`PackedCard`] codes and kinds. An observation is the deciding seat's
+//! count of each of the [`KINDS`] kinds of card, then the top card's code, the pending draw, the
+//! direction (0 for clockwise) and the number of cards each other seat holds, clockwise from the
+//! decider. The hidden part is each other seat's count of every kind, in the same order, followed
+//! by the deck's. Actions are the code of the card played, with any chosen color, or
+//! [`DRAW`] for a draw.
+
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use crate::card::{Card, CardColor};
+use crate::game::{Direction, GameView, TurnResult};
+use crate::packed::{PackedCard, KINDS};
+use crate::summary::GameOutcome;
+
+/// The action code for drawing instead of playing.
+pub const DRAW: u8 = 62;
+
+/// A move a player chose, with the whole table as it was when they chose it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decision {
+    pub seat: usize,
+    pub turn: u32,
+    /// What the player could see.
+    pub view: GameView,
+    /// Every hand in seating order, the player's own included.
+    pub hands: Vec<Vec<Card>>,
+    /// The deck, in the order it will be drawn from.
+    pub deck: Vec<Card>,
+    pub playable: Vec<Card>,
+    pub action: TurnResult,
+}
+
+/// A [`Decision`], encoded, with the result of the game it was made in.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TrainingRecord {
+    pub seat: usize,
+    pub turn: u32,
+    pub observation: Vec<u8>,
+    pub hidden: Vec<u8>,
+    /// Every action the player could have taken.
+    pub legal: Vec<u8>,
+    pub action: u8,
+    /// False when the game was aborted, so nobody won.
+    pub finished: bool,
+    pub won: bool,
+    /// The points the winner scored, or what was left in a loser's hand as a negative.
+    pub reward: i32,
+}
+
+/// Encodes every decision and labels it with how the game ended for the player who made it.
+pub fn records(decisions: &[Decision], outcome: &GameOutcome) -> Vec<TrainingRecord> {
+    let players = outcome.players();
+
+    decisions
+        .iter()
+        .map(|decision| {
+            let player = &players[decision.seat];
+            let (finished, won, reward) = match outcome {
+                GameOutcome::Won(summary) if player.hand.is_empty() => (true, true, summary.points as i32),
+                GameOutcome::Won(_) => (true, false, -(player.points as i32)),
+                GameOutcome::Aborted { .. } => (false, false, 0),
+            };
+
+            TrainingRecord {
+                seat: decision.seat,
+                turn: decision.turn,
+                observation: observation(decision),
+                hidden: hidden(decision),
+                legal: legal(decision),
+                action: action(decision.action),
+                finished,
+                won,
+                reward,
+            }
+        })
+        .collect()
+}
+
+/// Writes one JSON object per line.
+pub fn write_jsonl(records: &[TrainingRecord], mut writer: impl Write) -> io::Result<()> {
+    for record in records {
+        serde_json::to_writer(&mut writer, record)?;
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
+
+/// Writes a header and one row per record, with each list of numbers space-separated in a
+/// single column.
+pub fn write_csv(records: &[TrainingRecord], mut writer: impl Write) -> io::Result<()> {
+    writeln!(writer, "seat,turn,action,finished,won,reward,observation,hidden,legal")?;
+
+    for record in records {
+        writeln!(
+            writer,
+            "{},{},{},{},{},{},{},{},{}",
+            record.seat,
+            record.turn,
+            record.action,
+            record.finished,
+            record.won,
+            record.reward,
+            joined(&record.observation),
+            joined(&record.hidden),
+            joined(&record.legal),
+        )?;
+    }
+
+    Ok(())
+}
+
+fn observation(decision: &Decision) -> Vec<u8> {
+    let view = &decision.view;
+    let mut encoded = counts(&decision.hands[decision.seat]).to_vec();
+
+    encoded.push(view.top_card.map_or(u8::MAX, |card| PackedCard::from(card).code()));
+    encoded.push(view.to_draw);
+    encoded.push(match view.direction {
+        Direction::Clockwise => 0,
+        Direction::CounterClockwise => 1,
+    });
+    encoded.extend(others(decision).map(|seat| view.players[seat].cards.min(u8::MAX as usize) as u8));
+
+    encoded
+}
+
+fn hidden(decision: &Decision) -> Vec<u8> {
+    others(decision)
+        .flat_map(|seat| counts(&decision.hands[seat]))
+        .chain(counts(&decision.deck))
+        .collect()
+}
+
+fn legal(decision: &Decision) -> Vec<u8> {
+    let mut legal = decision.playable
+        .iter()
+        .flat_map(|&card| match card {
+            // A wild can be played as any of the four colors.
+            card if card.is_wild() => CardColor::ALL
+                .iter()
+                .map(|&color| {
+                    let mut chosen = card;
+                    chosen.with_color(color);
+                    action(TurnResult::Played(chosen))
+                })
+                .collect(),
+            card => vec![action(TurnResult::Played(card))],
+        })
+        .collect::<Vec<u8>>();
+
+    legal.sort_unstable();
+    legal.dedup();
+    legal.push(DRAW);
+    legal
+}
+
+fn action(result: TurnResult) -> u8 {
+    match result {
+        TurnResult::Played(card) => PackedCard::from(card).code(),
+        TurnResult::Drew => DRAW,
+    }
+}
+
+/// The other seats, clockwise from the one deciding.
+fn others(decision: &Decision) -> impl Iterator<Item = usize> + '_ {
+    let count = decision.hands.len();
+    (1..count).map(move |offset| (decision.seat + offset) % count)
+}
+
+fn counts(cards: &[Card]) -> [u8; KINDS] {
+    let mut counts = [0; KINDS];
+
+    for card in cards {
+        counts[PackedCard::from(*card).kind()] += 1;
+    }
+
+    counts
+}
+
+fn joined(values: &[u8]) -> String {
+    values.iter().map(u8::to_string).collect::<Vec<String>>().join(" ")
+}
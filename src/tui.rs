@@ -0,0 +1,450 @@
+use std::cmp::min;
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Alignment, Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::card::{Card, CardColor};
+use crate::game::{Direction, GameEvent, GameView, Turn, TurnResult};
+use crate::locale::{Locale, Message};
+use crate::player::Player;
+use crate::render::{CardNames, RenderConfig, RenderMode};
+use crate::summary::RoundSummary;
+use crate::window::Interjection;
+
+/// A human player driven through a full-screen terminal interface.
+///
+/// The terminal is switched to the alternate screen on creation and restored when dropped.
+pub struct TuiPlayer {
+    name: String,
+    locale: Locale,
+    names: CardNames,
+    terminal: DefaultTerminal,
+    view: Option<GameView>,
+    log: Vec<Line<'static>>,
+    status: String,
+    selected: usize,
+}
+
+enum Prompt {
+    Waiting,
+    Card,
+    Color,
+}
+
+impl TuiPlayer {
+    pub fn new(name: String, locale: Locale) -> TuiPlayer {
+        TuiPlayer {
+            name,
+            locale,
+            names: CardNames::default(),
+            terminal: ratatui::init(),
+            view: None,
+            log: vec![],
+            status: String::new(),
+            selected: 0,
+        }
+    }
+
+    /// Writes cards with the house names in `names`.
+    pub fn set_card_names(&mut self, names: CardNames) {
+        self.names = names;
+    }
+
+    /// Shows the round's results and waits for the player to choose between another round and quitting.
+    pub fn play_again(&mut self, summary: &RoundSummary) -> bool {
+        let result = if self.view.as_ref().and_then(GameView::player_id) == Some(summary.winner_id) {
+            self.locale.get(Message::YouWon).to_string()
+        } else {
+            self.locale.format(Message::Won, &[("player", &summary.winner)])
+        };
+
+        let render = self.render();
+
+        for line in summary.table(&render).lines() {
+            self.push_log(vec![Span::raw(line.to_string())]);
+        }
+
+        self.status = format!("{result} {}", self.locale.get(Message::PlayAgain));
+
+        loop {
+            self.redraw(None, &Prompt::Waiting);
+
+            match Self::read_key() {
+                KeyCode::Char('p') => {
+                    self.log.clear();
+                    self.view = None;
+                    self.selected = 0;
+                    return true;
+                }
+                KeyCode::Char('q') | KeyCode::Esc => return false,
+                _ => {}
+            }
+        }
+    }
+
+    fn redraw(&mut self, hand: Option<&[Card]>, prompt: &Prompt) {
+        let hand = hand.or(self.view.as_ref().map(|view| view.hand.as_slice())).unwrap_or(&[]);
+
+        self.terminal
+            .draw(|frame| draw(frame, &self.locale, &self.names, self.view.as_ref(), hand, self.selected, &self.log, &self.status, prompt))
+            .unwrap();
+    }
+
+    fn read_key() -> KeyCode {
+        loop {
+            if let Event::Key(key) = event::read().unwrap() {
+                if key.kind == KeyEventKind::Press {
+                    return key.code;
+                }
+            }
+        }
+    }
+
+    fn quit() -> ! {
+        ratatui::restore();
+        std::process::exit(0);
+    }
+
+    fn choose_color(&mut self, hand: &[Card]) -> Option<CardColor> {
+        loop {
+            self.redraw(Some(hand), &Prompt::Color);
+
+            match Self::read_key() {
+                KeyCode::Char('r') => return Some(CardColor::Red),
+                KeyCode::Char('g') => return Some(CardColor::Green),
+                KeyCode::Char('b') => return Some(CardColor::Blue),
+                KeyCode::Char('y') => return Some(CardColor::Yellow),
+                KeyCode::Esc => return None,
+                KeyCode::Char('q') => Self::quit(),
+                _ => {}
+            }
+        }
+    }
+
+    fn push_log(&mut self, spans: Vec<Span<'static>>) {
+        self.log.push(Line::from(spans));
+    }
+
+    fn render(&self) -> RenderConfig {
+        RenderConfig { mode: RenderMode::Plain, locale: self.locale.clone(), names: self.names.clone() }
+    }
+
+    /// Formats `message`, swapping its `{card}` placeholder for a colored span.
+    fn card_message(&self, message: Message, args: &[(&str, &dyn std::fmt::Display)], card: Card) -> Vec<Span<'static>> {
+        let text = self.locale.format(message, args);
+        let mut spans = vec![];
+
+        for (i, part) in text.split("{card}").enumerate() {
+            if i > 0 {
+                spans.push(card_span(card, &self.names));
+            }
+            spans.push(Span::raw(part.to_string()));
+        }
+
+        spans
+    }
+}
+
+impl Drop for TuiPlayer {
+    fn drop(&mut self) {
+        ratatui::restore();
+    }
+}
+
+impl Player for TuiPlayer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn execute_turn(&mut self, turn: &Turn) -> TurnResult {
+        let hand = turn.full_hand.to_vec();
+
+        self.selected = min(self.selected, hand.len().saturating_sub(1));
+        self.status = if turn.playable_hand.is_empty() {
+            self.locale.get(Message::TuiMustDraw).to_string()
+        } else {
+            self.locale.get(Message::YourTurn).to_string()
+        };
+
+        loop {
+            self.redraw(Some(&hand), &Prompt::Card);
+
+            match Self::read_key() {
+                KeyCode::Left | KeyCode::Char('h') => self.selected = self.selected.saturating_sub(1),
+                KeyCode::Right | KeyCode::Char('l') => self.selected = min(self.selected + 1, hand.len().saturating_sub(1)),
+                KeyCode::Char('d') => return TurnResult::Drew,
+                KeyCode::Char('q') => Self::quit(),
+                KeyCode::Enter | KeyCode::Char(' ') => {
+                    let mut card = hand[self.selected];
+
+                    if !turn.playable_hand.contains(&card) {
+                        self.status = self.locale.format(Message::CannotPlay, &[("card", &card)]);
+                        continue;
+                    }
+
+                    if !card.is_wild() {
+                        return TurnResult::Played(card);
+                    }
+
+                    if let Some(color) = self.choose_color(&hand) {
+                        return TurnResult::Played(*card.with_color(color).unwrap());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn observe_turn(&self, _other: &dyn Player, _card: &Card) {
+        // Plays are picked up from the event stream instead.
+    }
+
+    fn observe_turn_skip(&self, _observed_cards: Option<&[Card]>) {
+        // Draws and skips are picked up from the event stream instead.
+    }
+
+    fn observe_event(&mut self, event: &GameEvent, view: &GameView) {
+        self.view = Some(view.clone());
+
+        match event {
+            GameEvent::Started { top_card } => {
+                let spans = self.card_message(Message::TopCard, &[], *top_card);
+                self.push_log(spans);
+            }
+            GameEvent::TurnStarted { player } => {
+                if view.player_id() != Some(*player) {
+                    self.status = self.locale.format(Message::WaitingFor, &[("player", &view.name_of(*player))]);
+                }
+            }
+            GameEvent::Played { player, card } => {
+                let spans = self.card_message(Message::Played, &[("player", &view.name_of(*player))], *card);
+                self.push_log(spans);
+            }
+            GameEvent::Drew { player, count } if view.player_id() == Some(*player) => {
+                // Drawn cards are appended to the end of the hand.
+                let drawn = view.hand.iter().rev().take(*count).rev().copied().collect::<Vec<Card>>();
+
+                let spans = match drawn.as_slice() {
+                    [card] => self.card_message(Message::YouDrewCard, &[], *card),
+                    _ => {
+                        let mut spans = vec![];
+
+                        for (i, part) in self.locale.format(Message::YouDrewCards, &[("count", count)]).split("{cards}").enumerate() {
+                            if i > 0 {
+                                for (i, card) in drawn.iter().enumerate() {
+                                    if i > 0 {
+                                        spans.push(Span::raw(", "));
+                                    }
+                                    spans.push(card_span(*card, &self.names));
+                                }
+                            }
+                            spans.push(Span::raw(part.to_string()));
+                        }
+
+                        spans
+                    }
+                };
+
+                self.push_log(spans);
+            }
+            GameEvent::Drew { player, count } => {
+                self.push_log(vec![Span::raw(self.locale.format(Message::Drew, &[("player", &view.name_of(*player)), ("count", count)]))]);
+            }
+            GameEvent::Skipped { player } if view.player_id() == Some(*player) => {
+                self.push_log(vec![Span::raw(self.locale.get(Message::YouWereSkipped).to_string())]);
+            }
+            GameEvent::Skipped { player } => {
+                self.push_log(vec![Span::raw(self.locale.format(Message::Skipped, &[("player", &view.name_of(*player))]))]);
+            }
+            GameEvent::Traded { player, with } => {
+                self.push_log(vec![Span::raw(self.locale.format(Message::Traded, &[("player", &view.name_of(*player)), ("with", &view.name_of(*with))]))]);
+            }
+            GameEvent::Discarded { player, cards } => {
+                self.push_log(vec![Span::raw(self.locale.format(Message::Discarded, &[("player", &view.name_of(*player)), ("count", &cards.len())]))]);
+            }
+            GameEvent::Spared { player, count } => {
+                self.push_log(vec![Span::raw(self.locale.format(Message::Spared, &[("player", &view.name_of(*player)), ("count", count)]))]);
+            }
+            GameEvent::Eliminated { player } => {
+                let text = self.locale.format(Message::Eliminated, &[("player", &view.name_of(*player))]);
+                self.push_log(vec![Span::styled(text, Style::new().add_modifier(Modifier::BOLD))]);
+            }
+            GameEvent::PlayerLeft { player, .. } => {
+                let text = self.locale.format(Message::PlayerLeft, &[("player", &view.name_of(*player))]);
+                self.push_log(vec![Span::styled(text, Style::new().add_modifier(Modifier::BOLD))]);
+            }
+            GameEvent::Interjected { player, interjection } => {
+                let spans = match interjection {
+                    Interjection::CallOut => vec![Span::raw(self.locale.format(Message::CalledOut, &[("player", &view.name_of(*player))]))],
+                    Interjection::Challenge => vec![Span::raw(self.locale.format(Message::Challenged, &[("player", &view.name_of(*player))]))],
+                    Interjection::JumpIn(card) => self.card_message(Message::JumpedIn, &[("player", &view.name_of(*player))], *card),
+                };
+                self.push_log(spans);
+            }
+            GameEvent::Stacked { player, to_draw } => {
+                let text = self.locale.format(Message::Stacked, &[("player", &view.name_of(*player)), ("count", to_draw)]);
+                self.push_log(vec![Span::styled(text, Style::new().add_modifier(Modifier::BOLD))]);
+            }
+            GameEvent::Finished { player, place } => {
+                let text = self.locale.format(Message::Finished, &[("player", &view.name_of(*player)), ("place", place)]);
+                self.push_log(vec![Span::styled(text, Style::new().add_modifier(Modifier::BOLD))]);
+            }
+            GameEvent::Won { player } => {
+                let text = self.locale.format(Message::Won, &[("player", &view.name_of(*player))]);
+                self.push_log(vec![Span::styled(text, Style::new().add_modifier(Modifier::BOLD))]);
+            }
+            GameEvent::Aborted { reason } => {
+                let text = self.locale.format(Message::Aborted, &[("reason", reason)]);
+                self.push_log(vec![Span::styled(text, Style::new().add_modifier(Modifier::BOLD))]);
+            }
+            GameEvent::Paused => {
+                self.push_log(vec![Span::raw(self.locale.get(Message::Paused).to_string())]);
+            }
+            GameEvent::Resumed => {
+                self.push_log(vec![Span::raw(self.locale.get(Message::Resumed).to_string())]);
+            }
+            GameEvent::Emote { player, to, emote } => {
+                let message = if to.is_some() && *to == view.player_id() { Message::PrivateEmote } else { Message::Emote };
+                let text = self.locale.format(message, &[("player", &view.name_of(*player)), ("emote", &self.locale.get(Message::emote(*emote)))]);
+                self.push_log(vec![Span::styled(text, Style::new().add_modifier(Modifier::ITALIC))]);
+            }
+            GameEvent::Revealed { player, cards, points } if !cards.is_empty() => {
+                let text = self.locale.format(Message::Revealed, &[("player", &view.name_of(*player)), ("points", points)]);
+                let mut spans = vec![];
+
+                for (i, part) in text.split("{cards}").enumerate() {
+                    if i > 0 {
+                        for (j, card) in cards.iter().enumerate() {
+                            if j > 0 {
+                                spans.push(Span::raw(", "));
+                            }
+                            spans.push(card_span(*card, &self.names));
+                        }
+                    }
+                    spans.push(Span::raw(part.to_string()));
+                }
+
+                self.push_log(spans);
+            }
+            GameEvent::ColorsNamed { player, colors } => {
+                self.push_log(vec![Span::raw(self.locale.format(Message::ColorsNamed, &[("player", &view.name_of(*player)), ("colors", colors)]))]);
+            }
+            // Only spectators are sent everyone's hands.
+            GameEvent::Setup { .. } | GameEvent::DebugHands { .. } | GameEvent::WindowOpened { .. } | GameEvent::Revealed { .. } => {}
+        }
+
+        self.redraw(None, &Prompt::Waiting);
+    }
+}
+
+fn card_color(card: Card) -> Color {
+    match card.color() {
+        Some(CardColor::Red) => Color::Red,
+        Some(CardColor::Green) => Color::Green,
+        Some(CardColor::Blue) => Color::Blue,
+        Some(CardColor::Yellow) => Color::Yellow,
+        None => Color::Magenta,
+    }
+}
+
+fn card_span(card: Card, names: &CardNames) -> Span<'static> {
+    Span::styled(names.text(&card), Style::new().fg(card_color(card)).add_modifier(Modifier::BOLD))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn draw(frame: &mut Frame, locale: &Locale, names: &CardNames, view: Option<&GameView>, hand: &[Card], selected: usize, log: &[Line], status: &str, prompt: &Prompt) {
+    let [players_area, middle_area, hand_area, help_area] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(8),
+        Constraint::Length(4),
+        Constraint::Length(1),
+    ]).areas(frame.area());
+
+    let [discard_area, log_area] = Layout::horizontal([Constraint::Length(28), Constraint::Min(20)]).areas(middle_area);
+
+    // Opponents, their card counts and whose turn it is.
+    let mut players = vec![];
+
+    if let Some(view) = view {
+        players.push(Span::raw(match view.direction {
+            Direction::Clockwise => "↻ ",
+            Direction::CounterClockwise => "↺ ",
+        }));
+
+        for (i, player) in view.players.iter().enumerate() {
+            let style = if i == view.current_player {
+                Style::new().add_modifier(Modifier::BOLD | Modifier::REVERSED)
+            } else {
+                Style::new()
+            };
+
+            players.push(Span::styled(format!(" {} ({}) ", player.name, player.cards), style));
+            players.push(Span::raw(" "));
+        }
+    }
+
+    frame.render_widget(
+        Paragraph::new(Line::from(players)).block(Block::new().borders(Borders::ALL).title(format!(" {} ", locale.get(Message::TuiPlayers)))),
+        players_area,
+    );
+
+    // The discard pile and any pending draw penalty.
+    let mut discard = vec![Line::default()];
+
+    if let Some(card) = view.and_then(|view| view.top_card) {
+        discard.push(Line::from(card_span(card, names)).alignment(Alignment::Center));
+    }
+
+    if let Some(view) = view.filter(|view| view.to_draw > 0) {
+        discard.push(Line::default());
+        discard.push(Line::from(locale.format(Message::PendingDraw, &[("count", &view.to_draw)])).alignment(Alignment::Center));
+        discard.push(Line::from(view.draw_chain.iter().map(|stacked| card_span(stacked.card, names)).collect::<Vec<_>>()).alignment(Alignment::Center));
+    }
+
+    frame.render_widget(
+        Paragraph::new(discard).block(Block::new().borders(Borders::ALL).title(format!(" {} ", locale.get(Message::TuiDiscard)))),
+        discard_area,
+    );
+
+    // Keep the most recent entries of the log in view.
+    let visible = log_area.height.saturating_sub(2) as usize;
+    let log = log.iter().skip(log.len().saturating_sub(visible)).cloned().collect::<Vec<Line>>();
+
+    frame.render_widget(
+        Paragraph::new(log).block(Block::new().borders(Borders::ALL).title(format!(" {} ", locale.get(Message::TuiLog)))),
+        log_area,
+    );
+
+    // The hand, with the selected card highlighted while it's our turn.
+    let mut cards = vec![];
+
+    for (i, card) in hand.iter().enumerate() {
+        let mut span = card_span(*card, names);
+
+        if i == selected && !matches!(prompt, Prompt::Waiting) {
+            span = span.add_modifier(Modifier::REVERSED);
+        }
+
+        cards.push(span);
+        cards.push(Span::raw("  "));
+    }
+
+    frame.render_widget(
+        Paragraph::new(vec![Line::from(status.to_string()), Line::from(cards)])
+            .wrap(Wrap { trim: false })
+            .block(Block::new().borders(Borders::TOP).title(format!(" {} ", locale.get(Message::TuiHand)))),
+        hand_area,
+    );
+
+    let help = match prompt {
+        Prompt::Waiting => Message::TuiHelpWaiting,
+        Prompt::Card => Message::TuiHelpCard,
+        Prompt::Color => Message::TuiHelpColor,
+    };
+
+    frame.render_widget(Paragraph::new(locale.get(help)).style(Style::new().add_modifier(Modifier::DIM)), help_area);
+}
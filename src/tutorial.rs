@@ -0,0 +1,228 @@
+//! Guided lessons for learning to play: a position set up to teach one rule, the moves the
+//! learner is allowed to make in it, and what to tell them about each.
+//!
+//! A [`Tutorial`] holds a [`Lesson`] and plays the learner's attempts through the engine. Moves
+//! the lesson doesn't allow are turned away with its hint, so the learner can only go wrong
+//! in ways that teach something. [`lessons`] has the ones that come with the crate, worded in
+//! the [locale](crate::locale).
+
+use crate::card::Card;
+use crate::game::{Direction, GameEvent, GameState, GameView, Turn, TurnResult};
+use crate::locale::Message;
+use crate::notation;
+use crate::player::Player;
+use crate::rules::Rules;
+use crate::snapshot::{Position, PositionError};
+
+/// One rule to teach, at the point in a game where it matters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lesson {
+    pub id: &'static str,
+    /// The learner plays first, from the `to_play` seat.
+    pub position: Position,
+    /// What the learner is asked to do.
+    pub prompt: Message,
+    /// What they're told when they try a move the lesson doesn't allow.
+    pub hint: Message,
+    /// The moves they may make, each with what it teaches. A wild stands for itself in any
+    /// color.
+    pub moves: Vec<(TurnResult, Message)>,
+}
+
+/// What came of a move the lesson allows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Learned {
+    pub explanation: Message,
+    /// The turn as the learner saw it.
+    pub events: Vec<GameEvent>,
+    /// The table once the turn was over.
+    pub view: GameView,
+}
+
+/// Walks a learner through a [`Lesson`]. Every attempt starts again from the lesson's
+/// position, so they can try each of its moves in turn.
+pub struct Tutorial {
+    lesson: Lesson,
+    names: Vec<String>,
+    view: GameView,
+}
+
+impl Tutorial {
+    /// Sets up `lesson` with `names` for its seats, the learner's included.
+    pub fn new(lesson: Lesson, names: Vec<String>) -> Result<Tutorial, PositionError> {
+        let mut seats = names.iter().map(|name| Seat::new(name, None)).collect::<Vec<Seat>>();
+        let game = GameState::from_position(seats.iter_mut().map(|seat| seat as &mut dyn Player).collect(), &lesson.position)?;
+        let view = game.view(Some(lesson.position.to_play));
+        drop(game);
+
+        Ok(Tutorial { lesson, names, view })
+    }
+
+    pub fn lesson(&self) -> &Lesson {
+        &self.lesson
+    }
+
+    pub fn prompt(&self) -> Message {
+        self.lesson.prompt
+    }
+
+    /// The table as the learner sees it before they move.
+    pub fn view(&self) -> &GameView {
+        &self.view
+    }
+
+    /// The moves the lesson lets the learner make, whatever else the rules would allow.
+    pub fn legal(&self) -> impl Iterator<Item = TurnResult> + '_ {
+        self.lesson.moves.iter().map(|(action, _)| *action)
+    }
+
+    /// Plays `action` for the learner if the lesson allows it, or returns the lesson's hint.
+    /// A wild has to be given its color.
+    pub fn attempt(&self, action: TurnResult) -> Result<Learned, Message> {
+        if let TurnResult::Played(card) = action {
+            if card.is_wild() && card.color().is_none() {
+                return Err(self.lesson.hint);
+            }
+        }
+
+        let Some((_, explanation)) = self.lesson.moves.iter().find(|(allowed, _)| *allowed == action) else {
+            return Err(self.lesson.hint);
+        };
+
+        let learner = self.lesson.position.to_play;
+        let mut seats = self.names.iter().enumerate()
+            .map(|(seat, name)| Seat::new(name, (seat == learner).then_some(action)))
+            .collect::<Vec<Seat>>();
+        let mut game = GameState::from_position(seats.iter_mut().map(|seat| seat as &mut dyn Player).collect(), &self.lesson.position)
+            .expect("the position was set up when the tutorial was made");
+
+        game.play_turn();
+        let view = game.view(Some(learner));
+        drop(game);
+
+        Ok(Learned { explanation: *explanation, events: seats.swap_remove(learner).events, view })
+    }
+}
+
+/// A seat in a lesson. The learner's makes the move being tried, then draws if the rules ask
+/// again; everyone else only ever draws, since the lesson ends with the learner's turn.
+struct Seat<'n> {
+    name: &'n str,
+    action: Option<TurnResult>,
+    events: Vec<GameEvent>,
+}
+
+impl<'n> Seat<'n> {
+    fn new(name: &'n str, action: Option<TurnResult>) -> Seat<'n> {
+        Seat { name, action, events: vec![] }
+    }
+}
+
+impl Player for Seat<'_> {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn execute_turn(&mut self, _turn: &Turn) -> TurnResult {
+        self.action.take().unwrap_or(TurnResult::Drew)
+    }
+
+    fn observe_turn(&self, _other: &dyn Player, _card: &Card) {}
+
+    fn observe_turn_skip(&self, _observed_cards: Option<&[Card]>) {}
+
+    fn observe_event(&mut self, event: &GameEvent, _view: &GameView) {
+        self.events.push(event.clone());
+    }
+}
+
+/// The lessons that come with the crate, in the order they're best taken, each for two seats
+/// with the learner in the first.
+pub fn lessons() -> Vec<Lesson> {
+    vec![
+        Lesson {
+            id: "matching",
+            position: position(["R3 G7 B2 Y9", "G2 Y5 B1"], "R7", Rules::official(), 0),
+            prompt: Message::TutorialMatchPrompt,
+            hint: Message::TutorialMatchHint,
+            moves: vec![
+                (TurnResult::Played(card("R3")), Message::TutorialMatchedColor),
+                (TurnResult::Played(card("G7")), Message::TutorialMatchedNumber),
+            ],
+        },
+        Lesson {
+            id: "draw-two",
+            position: position(["B+2 R5 G1", "Y3 G8 R4"], "B+2", Rules::classic_stacking(), 2),
+            prompt: Message::TutorialDrawTwoPrompt,
+            hint: Message::TutorialDrawTwoHint,
+            moves: vec![
+                (TurnResult::Played(card("B+2")), Message::TutorialStacked),
+                (TurnResult::Drew, Message::TutorialTookDraw),
+            ],
+        },
+        Lesson {
+            id: "wild",
+            position: position(["W R2 B8", "Y1 G6 R9"], "G4", Rules::official(), 0),
+            prompt: Message::TutorialWildPrompt,
+            hint: Message::TutorialWildHint,
+            moves: vec![(TurnResult::Played(card("W(red)")), Message::TutorialPlayedWild)],
+        },
+    ]
+}
+
+fn position(hands: [&str; 2], top: &str, rules: Rules, to_draw: u8) -> Position {
+    Position {
+        hands: hands.iter().map(|hand| hand.split(' ').map(card).collect()).collect(),
+        deck: "B3 Y7 G+2 R6 Y0 B9".split(' ').map(card).collect(),
+        discard: vec![card(top)],
+        to_play: 0,
+        direction: Direction::Clockwise,
+        to_draw,
+        rules,
+        out: vec![],
+        seed: 0,
+    }
+}
+
+fn card(card: &str) -> Card {
+    notation::parse_card(card).expect("the built-in lessons are written in valid notation")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tutorial(lesson: Lesson) -> Tutorial {
+        Tutorial::new(lesson, vec!["Learner".to_string(), "Teacher".to_string()]).unwrap()
+    }
+
+    #[test]
+    fn every_lesson_plays_its_moves_and_turns_others_away() {
+        for lesson in lessons() {
+            let tutorial = tutorial(lesson.clone());
+
+            for (action, explanation) in &lesson.moves {
+                let learned = tutorial.attempt(*action).unwrap_or_else(|_| panic!("{} refused {action:?}", lesson.id));
+                assert_eq!(learned.explanation, *explanation);
+                assert_eq!(learned.view.current_player, 0, "{}: the learner was the last to play", lesson.id);
+
+                match action {
+                    TurnResult::Played(card) => assert_eq!(learned.view.top_card, Some(*card)),
+                    TurnResult::Drew => assert!(learned.view.hand.len() > tutorial.view().hand.len()),
+                }
+            }
+
+            // Every hand has a card the lesson doesn't teach with.
+            let untaught = tutorial.view().hand.iter().find(|card| tutorial.legal().all(|action| action != TurnResult::Played(**card))).unwrap();
+            assert_eq!(tutorial.attempt(TurnResult::Played(*untaught)), Err(lesson.hint), "{}", lesson.id);
+        }
+    }
+
+    #[test]
+    fn a_wild_needs_its_color() {
+        let tutorial = tutorial(lessons().pop().unwrap());
+
+        assert_eq!(tutorial.attempt(TurnResult::Played(card("W"))), Err(Message::TutorialWildHint));
+        assert!(tutorial.attempt(TurnResult::Played(card("W(blue)"))).is_ok());
+    }
+}
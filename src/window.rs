@@ -0,0 +1,89 @@
+//! Chances to act out of turn, which a game offers after each card once
+//! [`GameState::set_window`](crate::game::GameState::set_window) turns them on: calling out a
+//! player down to one card, challenging a Draw Four, or jumping in with the same card.
+//!
+//! The engine opens the window, asks every other player still in the round, and settles who
+//! gets to act. Nothing it decides changes the game yet; what an interjection does is left to
+//! the [hooks](crate::hook::Hook) and rules built on it.
+
+use core::cmp::Reverse;
+use core::time::Duration;
+
+use crate::card::Card;
+use crate::rules::{self, TurnOrder};
+
+/// A card that's just been played, open to answers from the rest of the table.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Window {
+    /// Who played it.
+    pub seat: usize,
+    pub card: Card,
+    /// What they hold now it's down.
+    pub cards_left: usize,
+    /// How long players acting through a [`GameHandle`](crate::game::GameHandle) have to answer.
+    /// Seats the game asks itself answer at once.
+    pub timeout: Duration,
+}
+
+/// Something done out of turn, in answer to a [`Window`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Interjection {
+    /// The player who opened the window is down to one card and didn't say so.
+    CallOut,
+    /// The Draw Four that opened the window wasn't its player's only choice.
+    Challenge,
+    /// The same card again, played out of turn.
+    JumpIn(Card),
+}
+
+impl Interjection {
+    /// Which is heard when several players answer one window, the highest first. A challenge
+    /// has to be settled before anyone else acts on the card, and a call-out is about the
+    /// player who opened the window rather than the pile.
+    pub fn priority(&self) -> u8 {
+        match self {
+            Interjection::Challenge => 2,
+            Interjection::CallOut => 1,
+            Interjection::JumpIn(_) => 0,
+        }
+    }
+}
+
+/// Which of `answers`, each a seat and what it did, is heard: the highest
+/// [`priority`](Interjection::priority), and after that whoever would play soonest from
+/// `order`'s current player.
+pub fn settle(answers: &[(usize, Interjection)], order: &TurnOrder) -> Option<(usize, Interjection)> {
+    let distance = |seat: usize| {
+        core::iter::successors(Some(order.current), |&next| Some(rules::next_seat(next, order.seats, order.direction)))
+            .skip(1)
+            .take(order.seats)
+            .position(|next| next == seat)
+    };
+
+    answers
+        .iter()
+        .copied()
+        .min_by_key(|(seat, interjection)| (Reverse(interjection.priority()), distance(*seat)))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+    use crate::card::CardColor;
+    use crate::game::Direction;
+
+    #[test]
+    fn priority_comes_before_seating() {
+        let order = TurnOrder { current: 0, seats: 4, direction: Direction::Clockwise, to_draw: 0, out: vec![] };
+        let jump_in = Interjection::JumpIn(Card::Skip { color: CardColor::Red });
+
+        assert_eq!(settle(&[(1, jump_in), (3, Interjection::CallOut)], &order), Some((3, Interjection::CallOut)));
+        assert_eq!(settle(&[(3, jump_in), (1, jump_in)], &order), Some((1, jump_in)));
+        assert_eq!(settle(&[(3, jump_in), (1, jump_in)], &TurnOrder { direction: Direction::CounterClockwise, ..order.clone() }), Some((3, jump_in)));
+        assert_eq!(settle(&[], &order), None);
+    }
+}